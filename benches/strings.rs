@@ -0,0 +1,69 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use jni::{objects::JObject, InitArgsBuilder, JNIEnv, JavaVM};
+use jni_utils::strings;
+
+const INPUTS: &[(&str, &str)] = &[
+    (
+        "ascii",
+        "The quick brown fox jumps over the lazy dog. 0123456789",
+    ),
+    (
+        "non_ascii",
+        "Z\u{fc}rich, \u{65e5}\u{672c}\u{8a9e}, \u{1f980} and \u{3b1}\u{3b2}\u{3b3}",
+    ),
+];
+
+fn repeat(s: &str) -> String {
+    s.repeat(32)
+}
+
+fn bench_to_string(c: &mut Criterion, env: &JNIEnv) {
+    let mut group = c.benchmark_group("to_string");
+    for (name, input) in INPUTS {
+        let input = repeat(input);
+        let obj: JObject = env.new_string(&input).unwrap().into();
+
+        group.bench_with_input(BenchmarkId::new("get_string", name), &obj, |b, obj| {
+            b.iter(|| -> String { env.get_string((*obj).into()).unwrap().into() })
+        });
+        group.bench_with_input(BenchmarkId::new("strings", name), &obj, |b, obj| {
+            b.iter(|| strings::to_string(env, *obj).unwrap())
+        });
+
+        env.delete_local_ref(obj).unwrap();
+    }
+    group.finish();
+}
+
+fn bench_new_string(c: &mut Criterion, env: &JNIEnv) {
+    let mut group = c.benchmark_group("new_string");
+    for (name, input) in INPUTS {
+        let input = repeat(input);
+
+        group.bench_with_input(BenchmarkId::new("jnienv", name), &input, |b, input| {
+            b.iter(|| {
+                let obj = env.new_string(input).unwrap();
+                env.delete_local_ref(obj.into()).unwrap();
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("strings", name), &input, |b, input| {
+            b.iter(|| {
+                let obj = strings::new_string(env, input).unwrap();
+                env.delete_local_ref(obj.into()).unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_strings(c: &mut Criterion) {
+    let jvm_args = InitArgsBuilder::new().build().unwrap();
+    let jvm = JavaVM::new(jvm_args).unwrap();
+    let env = jvm.attach_current_thread_permanently().unwrap();
+
+    bench_to_string(c, &env);
+    bench_new_string(c, &env);
+}
+
+criterion_group!(benches, bench_strings);
+criterion_main!(benches);
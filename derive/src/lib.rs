@@ -0,0 +1,399 @@
+//! Derive macros for [`jni-utils`](https://crates.io/crates/jni-utils).
+//!
+//! This crate should not be used directly. Instead, enable the `derive`
+//! feature of `jni-utils` and use the macros re-exported from
+//! `jni_utils::convert`.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, GenericArgument, LitStr,
+    PathArguments, Result, Type,
+};
+
+/// Derive `jni_utils::convert::FromJava` for a struct. See the documentation
+/// of `jni_utils::convert` for details.
+#[proc_macro_derive(FromJava, attributes(java))]
+pub fn derive_from_java(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, from_java)
+}
+
+/// Derive `jni_utils::convert::IntoJava` for a struct. See the documentation
+/// of `jni_utils::convert` for details.
+#[proc_macro_derive(IntoJava, attributes(java))]
+pub fn derive_into_java(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, into_java)
+}
+
+fn expand(input: &DeriveInput, f: fn(&Container) -> TokenStream2) -> TokenStream {
+    match Container::parse(input) {
+        Ok(container) => f(&container).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Fields,
+    Constructor,
+    Record,
+}
+
+struct Container<'a> {
+    input: &'a DeriveInput,
+    class: String,
+    mode: Mode,
+    fields: Vec<Field<'a>>,
+}
+
+struct Field<'a> {
+    ident: &'a syn::Ident,
+    ty: &'a Type,
+    name: String,
+    getter: String,
+    sig: String,
+    skip: bool,
+}
+
+impl<'a> Field<'a> {
+    fn is_primitive(&self) -> bool {
+        self.sig.len() == 1
+    }
+}
+
+impl<'a> Container<'a> {
+    fn parse(input: &'a DeriveInput) -> Result<Self> {
+        let mut class = None;
+        let mut mode = Mode::Fields;
+        for attr in input.attrs.iter().filter(|a| a.path().is_ident("java")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("class") {
+                    class = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("constructor") {
+                    mode = Mode::Constructor;
+                } else if meta.path.is_ident("record") {
+                    mode = Mode::Record;
+                } else {
+                    return Err(meta.error("expected `class`, `constructor` or `record`"));
+                }
+                Ok(())
+            })?;
+        }
+        let class = class.ok_or_else(|| {
+            Error::new(
+                Span::call_site(),
+                "missing #[java(class = \"...\")] attribute",
+            )
+        })?;
+
+        let named = match &input.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(fields) => &fields.named,
+                _ => {
+                    return Err(Error::new(
+                        input.ident.span(),
+                        "only structs with named fields are supported",
+                    ))
+                }
+            },
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "only structs are supported; use java_enum! for enums",
+                ))
+            }
+        };
+
+        let mut fields = Vec::new();
+        for field in named {
+            let ident = field.ident.as_ref().unwrap();
+            let camel = camel_case(&ident.to_string());
+            let mut name = None;
+            let mut getter = None;
+            let mut sig = None;
+            let mut skip = false;
+            for attr in field.attrs.iter().filter(|a| a.path().is_ident("java")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        name = Some(meta.value()?.parse::<LitStr>()?.value());
+                    } else if meta.path.is_ident("getter") {
+                        if matches!(mode, Mode::Fields) {
+                            return Err(Error::new(
+                                meta.path.span(),
+                                "getter is only used with constructor or record",
+                            ));
+                        }
+                        getter = Some(meta.value()?.parse::<LitStr>()?.value());
+                    } else if meta.path.is_ident("sig") {
+                        sig = Some(meta.value()?.parse::<LitStr>()?.value());
+                    } else if meta.path.is_ident("skip") {
+                        skip = true;
+                    } else {
+                        return Err(meta.error("expected `name`, `getter`, `sig` or `skip`"));
+                    }
+                    Ok(())
+                })?;
+            }
+
+            let sig = match sig.or_else(|| type_signature(&field.ty)) {
+                Some(sig) => sig,
+                None if skip => String::new(),
+                None => return Err(Error::new(
+                    field.ty.span(),
+                    "cannot determine the Java signature of this type; add #[java(sig = \"...\")]",
+                )),
+            };
+            let name = name.unwrap_or(camel);
+            let getter = getter.unwrap_or_else(|| match mode {
+                Mode::Record | Mode::Fields => name.clone(),
+                Mode::Constructor if sig == "Z" => format!("is{}", capitalize(&name)),
+                Mode::Constructor => format!("get{}", capitalize(&name)),
+            });
+            fields.push(Field {
+                ident,
+                ty: &field.ty,
+                name,
+                getter,
+                sig,
+                skip,
+            });
+        }
+
+        Ok(Self {
+            input,
+            class,
+            mode,
+            fields,
+        })
+    }
+
+    fn mapped_fields(&self) -> impl Iterator<Item = &Field<'a>> {
+        self.fields.iter().filter(|f| !f.skip)
+    }
+
+    fn constructor_sig(&self) -> String {
+        let args: String = self.mapped_fields().map(|f| f.sig.as_str()).collect();
+        format!("({})V", args)
+    }
+}
+
+fn from_java(container: &Container) -> TokenStream2 {
+    let ident = &container.input.ident;
+    let (impl_generics, ty_generics, where_clause) = container.input.generics.split_for_impl();
+    let class = &container.class;
+
+    let members = container.mapped_fields().map(|f| match container.mode {
+        Mode::Fields => {
+            let (name, sig) = (&f.name, &f.sig);
+            quote!(::jni_utils::convert::JavaMember::Field(#name, #sig))
+        }
+        Mode::Constructor | Mode::Record => {
+            let (name, sig) = (&f.getter, format!("(){}", f.sig));
+            quote!(::jni_utils::convert::JavaMember::Method(#name, #sig))
+        }
+    });
+
+    let mut index = 0usize;
+    let values = container.fields.iter().map(|f| {
+        let field = f.ident;
+        let ty = f.ty;
+        if f.skip {
+            return quote!(#field: ::std::default::Default::default());
+        }
+        let get = match container.mode {
+            Mode::Fields => quote!(class.get_field(env, obj, #index)?),
+            Mode::Constructor | Mode::Record => quote!(class.call_method(env, obj, #index, &[])?),
+        };
+        index += 1;
+        if f.is_primitive() {
+            quote!(#field: <#ty as ::jni_utils::primitives::JavaPrimitive>::from_jvalue(#get)?)
+        } else {
+            quote!(#field: {
+                let value = env.auto_local(#get.l()?);
+                <#ty as ::jni_utils::convert::FromJava>::from_java(env, value.as_obj())?
+            })
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::jni_utils::convert::FromJava for #ident #ty_generics #where_clause {
+            fn from_java<'a: 'b, 'b>(
+                env: &'b ::jni::JNIEnv<'a>,
+                obj: ::jni::objects::JObject<'a>,
+            ) -> ::jni::errors::Result<Self> {
+                static CACHE: ::jni_utils::convert::JavaClassCache =
+                    ::jni_utils::convert::JavaClassCache::new();
+                let class = CACHE.get(env, #class, &[#(#members),*])?;
+                class.check_instance(env, obj)?;
+                Ok(Self {
+                    #(#values,)*
+                })
+            }
+        }
+    }
+}
+
+fn into_java(container: &Container) -> TokenStream2 {
+    let ident = &container.input.ident;
+    let (impl_generics, ty_generics, where_clause) = container.input.generics.split_for_impl();
+    let class = &container.class;
+
+    let bindings = container.fields.iter().enumerate().map(|(i, f)| {
+        let field = f.ident;
+        if f.skip {
+            quote!(#field: _)
+        } else {
+            let binding = format_ident!("__field{}", i);
+            quote!(#field: #binding)
+        }
+    });
+
+    let mut locals = Vec::new();
+    let mut values = Vec::new();
+    for (i, f) in container.fields.iter().enumerate() {
+        if f.skip {
+            continue;
+        }
+        let binding = format_ident!("__field{}", i);
+        if f.is_primitive() {
+            values.push(quote!(::jni_utils::primitives::JavaPrimitive::into_jvalue(#binding)));
+        } else {
+            let local = format_ident!("__local{}", i);
+            locals.push(quote! {
+                let #local = env.auto_local(::jni_utils::convert::IntoJava::into_java(#binding, env)?);
+            });
+            values.push(quote!(::jni::objects::JValue::Object(#local.as_obj())));
+        }
+    }
+
+    let body = match container.mode {
+        Mode::Fields => {
+            let members = container.mapped_fields().map(|f| {
+                let (name, sig) = (&f.name, &f.sig);
+                quote!(::jni_utils::convert::JavaMember::Field(#name, #sig))
+            });
+            let indices = 1..=values.len();
+            quote! {
+                let class = CACHE.get(
+                    env,
+                    #class,
+                    &[::jni_utils::convert::JavaMember::Constructor("()V"), #(#members),*],
+                )?;
+                #(#locals)*
+                let obj = class.new_object(env, 0, &[])?;
+                #(class.set_field(env, obj, #indices, #values)?;)*
+                Ok(obj)
+            }
+        }
+        Mode::Constructor | Mode::Record => {
+            let sig = container.constructor_sig();
+            quote! {
+                let class = CACHE.get(
+                    env,
+                    #class,
+                    &[::jni_utils::convert::JavaMember::Constructor(#sig)],
+                )?;
+                #(#locals)*
+                class.new_object(env, 0, &[#(#values),*])
+            }
+        }
+    };
+
+    quote! {
+        impl #impl_generics ::jni_utils::convert::IntoJava for #ident #ty_generics #where_clause {
+            fn into_java<'a: 'b, 'b>(
+                self,
+                env: &'b ::jni::JNIEnv<'a>,
+            ) -> ::jni::errors::Result<::jni::objects::JObject<'a>> {
+                static CACHE: ::jni_utils::convert::JavaClassCache =
+                    ::jni_utils::convert::JavaClassCache::new();
+                let Self { #(#bindings),* } = self;
+                #body
+            }
+        }
+    }
+}
+
+// Determines the Java signature of a field from its Rust type, for the types
+// which have an unambiguous mapping.
+fn type_signature(ty: &Type) -> Option<String> {
+    let segment = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+    let name = segment.ident.to_string();
+    let arg = match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        // Maps have more than one argument, and only their name matters.
+        PathArguments::AngleBracketed(_) => None,
+        PathArguments::None => None,
+        _ => return None,
+    };
+
+    let sig = match (name.as_str(), arg) {
+        ("bool", None) => "Z",
+        ("i8", None) | ("jbyte", None) => "B",
+        ("u16", None) | ("jchar", None) => "C",
+        ("i16", None) | ("jshort", None) => "S",
+        ("i32", None) | ("jint", None) => "I",
+        ("i64", None) | ("jlong", None) => "J",
+        ("f32", None) | ("jfloat", None) => "F",
+        ("f64", None) | ("jdouble", None) => "D",
+        ("String", None) => "Ljava/lang/String;",
+        ("Uuid", None) => "Ljava/util/UUID;",
+        ("Duration", None) => "Ljava/time/Duration;",
+        ("SystemTime", None) => "Ljava/time/Instant;",
+        ("PathBuf", None) => "Ljava/nio/file/Path;",
+        ("GlobalRef", None) => "Ljava/lang/Object;",
+        ("JavaFile", None) => "Ljava/io/File;",
+        ("i128", None) | ("u128", None) => "Ljava/math/BigInteger;",
+        ("Vec", Some(Type::Path(inner))) if inner.path.is_ident("u8") => "[B",
+        ("JavaList", Some(_)) => "Ljava/util/List;",
+        ("JavaOptional", Some(_)) => "Ljava/util/Optional;",
+        ("JavaOptionalInt", None) => "Ljava/util/OptionalInt;",
+        ("JavaOptionalLong", None) => "Ljava/util/OptionalLong;",
+        ("JavaOptionalDouble", None) => "Ljava/util/OptionalDouble;",
+        ("HashMap", _) | ("BTreeMap", _) => "Ljava/util/Map;",
+        ("HashSet", Some(_)) | ("BTreeSet", Some(_)) => "Ljava/util/Set;",
+        ("Option", Some(inner)) => {
+            let sig = type_signature(inner)?;
+            return Some(match sig.as_str() {
+                "Z" => "Ljava/lang/Boolean;".to_string(),
+                "B" => "Ljava/lang/Byte;".to_string(),
+                "C" => "Ljava/lang/Character;".to_string(),
+                "S" => "Ljava/lang/Short;".to_string(),
+                "I" => "Ljava/lang/Integer;".to_string(),
+                "J" => "Ljava/lang/Long;".to_string(),
+                "F" => "Ljava/lang/Float;".to_string(),
+                "D" => "Ljava/lang/Double;".to_string(),
+                _ => sig,
+            });
+        }
+        _ => return None,
+    };
+    Some(sig.to_string())
+}
+
+fn camel_case(name: &str) -> String {
+    let name = name.strip_prefix("r#").unwrap_or(name);
+    let mut parts = name.split('_').filter(|p| !p.is_empty());
+    let mut result = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        result.push_str(&capitalize(part));
+    }
+    result
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,30 @@
+package io.github.gedgygedgy.rust.future;
+
+import java.nio.channels.CompletionHandler;
+
+/**
+ * Implementation of {@link Future} which is woken by a
+ * {@link CompletionHandler}. This allows asynchronous NIO operations, such as
+ * those provided by {@link java.nio.channels.AsynchronousByteChannel}, to be
+ * {@code await}ed on from Rust code.
+ * <p>
+ * If the operation completes successfully, the future is woken with its
+ * result. If the operation fails, the future is woken with the exception, as
+ * in {@link SimpleFuture#wakeWithThrowable}.
+ */
+public class CompletionHandlerFuture<V, A> extends SimpleFuture<V> implements CompletionHandler<V, A> {
+    /**
+     * Creates a new {@link CompletionHandlerFuture} object.
+     */
+    public CompletionHandlerFuture() {}
+
+    @Override
+    public void completed(V result, A attachment) {
+        this.wake(result);
+    }
+
+    @Override
+    public void failed(Throwable exc, A attachment) {
+        this.wakeWithThrowable(exc);
+    }
+}
@@ -0,0 +1,79 @@
+package io.github.gedgygedgy.rust.io;
+
+import io.github.gedgygedgy.rust.ops.FnBiFunction;
+
+import java.io.IOException;
+import java.io.InputStream;
+
+/**
+ * Wraps a Rust {@code std::io::Read} in an {@link InputStream}.
+ * <p>
+ * Instances of this class cannot be obtained directly from Java. Instead, call
+ * {@code jni_utils::io::input_stream()} from Rust code to obtain an instance
+ * of this class.
+ */
+final class ReadInputStream extends InputStream {
+    private final FnBiFunction<byte[], Integer, Integer> readFn;
+    private final byte[] single = new byte[1];
+    private byte[] scratch = null;
+    private boolean closed = false;
+
+    private ReadInputStream(FnBiFunction<byte[], Integer, Integer> readFn) {
+        this.readFn = readFn;
+    }
+
+    @Override
+    public int read() throws IOException {
+        int result = this.read(this.single, 0, 1);
+        return result < 0 ? -1 : this.single[0] & 0xFF;
+    }
+
+    @Override
+    public int read(byte[] b, int off, int len) throws IOException {
+        if (off < 0 || len < 0 || len > b.length - off) {
+            throw new IndexOutOfBoundsException();
+        }
+        this.ensureOpen();
+        if (len == 0) {
+            return 0;
+        }
+
+        byte[] target = b;
+        if (off != 0) {
+            if (this.scratch == null || this.scratch.length < len) {
+                this.scratch = new byte[len];
+            }
+            target = this.scratch;
+        }
+
+        Integer result = this.readFn.apply(target, len);
+        if (result == null) {
+            throw new IOException("Stream closed");
+        }
+        if (result == 0) {
+            return -1;
+        }
+        if (target != b) {
+            System.arraycopy(target, 0, b, off, result);
+        }
+        return result;
+    }
+
+    /**
+     * Frees the {@code std::io::Read} associated with this object.
+     * <p>
+     * This method is idempotent - if it's called twice, the second call is a
+     * no-op.
+     */
+    @Override
+    public void close() {
+        this.closed = true;
+        this.readFn.close();
+    }
+
+    private void ensureOpen() throws IOException {
+        if (this.closed) {
+            throw new IOException("Stream closed");
+        }
+    }
+}
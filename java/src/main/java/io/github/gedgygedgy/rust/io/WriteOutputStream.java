@@ -0,0 +1,80 @@
+package io.github.gedgygedgy.rust.io;
+
+import io.github.gedgygedgy.rust.ops.FnBiFunction;
+import io.github.gedgygedgy.rust.ops.FnRunnable;
+
+import java.io.IOException;
+import java.io.OutputStream;
+import java.util.Arrays;
+
+/**
+ * Wraps a Rust {@code std::io::Write} in an {@link OutputStream}.
+ * <p>
+ * Instances of this class cannot be obtained directly from Java. Instead, call
+ * {@code jni_utils::io::output_stream()} from Rust code to obtain an instance
+ * of this class.
+ */
+final class WriteOutputStream extends OutputStream {
+    private final FnBiFunction<byte[], Integer, Void> writeFn;
+    private final FnRunnable flushFn;
+    private final byte[] single = new byte[1];
+    private boolean closed = false;
+
+    private WriteOutputStream(FnBiFunction<byte[], Integer, Void> writeFn, FnRunnable flushFn) {
+        this.writeFn = writeFn;
+        this.flushFn = flushFn;
+    }
+
+    @Override
+    public void write(int b) throws IOException {
+        this.single[0] = (byte) b;
+        this.write(this.single, 0, 1);
+    }
+
+    @Override
+    public void write(byte[] b, int off, int len) throws IOException {
+        if (off < 0 || len < 0 || len > b.length - off) {
+            throw new IndexOutOfBoundsException();
+        }
+        this.ensureOpen();
+        if (len == 0) {
+            return;
+        }
+
+        byte[] source = off == 0 ? b : Arrays.copyOfRange(b, off, off + len);
+        this.writeFn.apply(source, len);
+    }
+
+    @Override
+    public void flush() throws IOException {
+        this.ensureOpen();
+        this.flushFn.run();
+    }
+
+    /**
+     * Flushes and frees the {@code std::io::Write} associated with this
+     * object.
+     * <p>
+     * This method is idempotent - if it's called twice, the second call is a
+     * no-op.
+     */
+    @Override
+    public void close() throws IOException {
+        if (this.closed) {
+            return;
+        }
+        try {
+            this.flushFn.run();
+        } finally {
+            this.closed = true;
+            this.writeFn.close();
+            this.flushFn.close();
+        }
+    }
+
+    private void ensureOpen() throws IOException {
+        if (this.closed) {
+            throw new IOException("Stream closed");
+        }
+    }
+}
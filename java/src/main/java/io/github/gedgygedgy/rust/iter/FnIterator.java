@@ -0,0 +1,65 @@
+package io.github.gedgygedgy.rust.iter;
+
+import io.github.gedgygedgy.rust.ops.FnFunction;
+
+import java.io.Closeable;
+import java.util.Iterator;
+import java.util.NoSuchElementException;
+
+/**
+ * Wraps a Rust {@code std::iter::Iterator} in a Java {@link Iterator}. Items
+ * are computed lazily, one at a time, as {@link hasNext} and {@link next} are
+ * called.
+ * <p>
+ * Instances of this class cannot be obtained directly from Java. Instead, call
+ * {@code jni_utils::iter::iterator()} from Rust code to obtain an instance of
+ * this class.
+ */
+public final class FnIterator<T> implements Iterator<T>, Closeable {
+    private final FnFunction<Object[], Object[]> nextFn;
+    private Object[] next = null;
+    private boolean finished = false;
+
+    private FnIterator(FnFunction<Object[], Object[]> nextFn) {
+        this.nextFn = nextFn;
+    }
+
+    @Override
+    public boolean hasNext() {
+        if (this.next == null && !this.finished) {
+            Object[] slot = this.nextFn.apply(new Object[1]);
+            if (slot == null) {
+                this.close();
+            } else {
+                this.next = slot;
+            }
+        }
+        return this.next != null;
+    }
+
+    @Override
+    @SuppressWarnings("unchecked")
+    public T next() {
+        if (!this.hasNext()) {
+            throw new NoSuchElementException();
+        }
+        T item = (T) this.next[0];
+        this.next = null;
+        return item;
+    }
+
+    /**
+     * Frees the {@code std::iter::Iterator} associated with this object. This
+     * is done automatically when the iterator is exhausted. After this method
+     * is called, {@link hasNext} returns {@code false}.
+     * <p>
+     * This method is idempotent - if it's called twice, the second call is a
+     * no-op.
+     */
+    @Override
+    public void close() {
+        this.finished = true;
+        this.next = null;
+        this.nextFn.close();
+    }
+}
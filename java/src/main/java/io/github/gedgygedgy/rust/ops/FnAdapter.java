@@ -19,6 +19,30 @@ final class FnAdapter<O, T, U, R> implements Closeable {
 
     private native R callInternal(O self, T arg1, U arg2);
 
+    // The following methods call closures which take or return primitives
+    // directly, so that the values don't need to be boxed.
+
+    public int callInt(O self, int arg) {
+        this.threadChecker.check();
+        return this.callIntInternal(self, arg);
+    }
+
+    private native int callIntInternal(O self, int arg);
+
+    public long callLong(O self, T arg) {
+        this.threadChecker.check();
+        return this.callLongInternal(self, arg);
+    }
+
+    private native long callLongInternal(O self, T arg);
+
+    public double callDouble(O self, double arg1, double arg2) {
+        this.threadChecker.check();
+        return this.callDoubleInternal(self, arg1, arg2);
+    }
+
+    private native double callDoubleInternal(O self, double arg1, double arg2);
+
     @Override
     public void close() {
         this.threadChecker.check();
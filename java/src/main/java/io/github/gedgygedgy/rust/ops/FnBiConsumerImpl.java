@@ -0,0 +1,21 @@
+package io.github.gedgygedgy.rust.ops;
+
+import java.io.Closeable;
+
+final class FnBiConsumerImpl<T, U> implements FnBiConsumer<T, U> {
+    private final FnAdapter<FnBiConsumerImpl<T, U>, T, U, Void> adapter;
+
+    private FnBiConsumerImpl(FnAdapter<FnBiConsumerImpl<T, U>, T, U, Void> adapter) {
+        this.adapter = adapter;
+    }
+
+    @Override
+    public void accept(T t, U u) {
+        this.adapter.call(this, t, u);
+    }
+
+    @Override
+    public void close() {
+        this.adapter.close();
+    }
+}
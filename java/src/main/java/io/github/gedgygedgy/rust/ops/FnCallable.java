@@ -0,0 +1,39 @@
+package io.github.gedgygedgy.rust.ops;
+
+import java.io.Closeable;
+import java.util.concurrent.Callable;
+
+/**
+ * Wraps a closure in a Java object.
+ * <p>
+ * Instances of this class cannot be obtained directly from Java. Instead, call
+ * {@code jni_utils::ops::fn_callable()},
+ * {@code jni_utils::ops::fn_mut_callable()}, or
+ * {@code jni_utils::ops::fn_once_callable()} from Rust code to obtain an
+ * instance of this class.
+ */
+public interface FnCallable<V> extends Callable<V>, Closeable {
+    /**
+     * Runs the closure associated with this object.
+     * <p>
+     * If the closure is a {@code std::ops::Fn} or {@code std::ops::FnMut},
+     * calling this method twice will call the associated closure twice. If
+     * the closure is a {@code std::ops::FnOnce}, the second call will return
+     * {@code null}. If {@link close} has already been called, this method
+     * returns {@code null}.
+     * <p>
+     * If the closure returns an error, the error is thrown from this method
+     * as an exception of the class chosen by the Rust code.
+     */
+    @Override
+    public V call() throws Exception;
+
+    /**
+     * Disposes of the closure associated with this object.
+     * <p>
+     * This method is idempotent - if it's called twice, the second call is a
+     * no-op.
+     */
+    @Override
+    public void close();
+}
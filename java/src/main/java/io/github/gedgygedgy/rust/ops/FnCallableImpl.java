@@ -0,0 +1,19 @@
+package io.github.gedgygedgy.rust.ops;
+
+final class FnCallableImpl<V> implements FnCallable<V> {
+    private final FnAdapter<FnCallableImpl<V>, Void, Void, V> adapter;
+
+    private FnCallableImpl(FnAdapter<FnCallableImpl<V>, Void, Void, V> adapter) {
+        this.adapter = adapter;
+    }
+
+    @Override
+    public V call() throws Exception {
+        return this.adapter.call(this, null, null);
+    }
+
+    @Override
+    public void close() {
+        this.adapter.close();
+    }
+}
@@ -0,0 +1,20 @@
+package io.github.gedgygedgy.rust.ops;
+
+final class FnComparatorImpl<T> implements FnComparator<T> {
+    private final FnAdapter<FnComparatorImpl<T>, T, T, Integer> adapter;
+
+    private FnComparatorImpl(FnAdapter<FnComparatorImpl<T>, T, T, Integer> adapter) {
+        this.adapter = adapter;
+    }
+
+    @Override
+    public int compare(T o1, T o2) {
+        Integer result = this.adapter.call(this, o1, o2);
+        return result == null ? 0 : result;
+    }
+
+    @Override
+    public void close() {
+        this.adapter.close();
+    }
+}
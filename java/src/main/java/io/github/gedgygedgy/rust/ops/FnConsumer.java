@@ -0,0 +1,41 @@
+package io.github.gedgygedgy.rust.ops;
+
+import java.io.Closeable;
+import java.util.function.Consumer;
+
+/**
+ * Wraps a closure in a Java object.
+ * <p>
+ * Instances of this class cannot be obtained directly from Java. Instead, call
+ * {@code jni_utils::ops::fn_consumer()},
+ * {@code jni_utils::ops::fn_mut_consumer()}, or
+ * {@code jni_utils::ops::fn_once_consumer()} from Rust code to obtain an
+ * instance of this class.
+ * <p>
+ * The {@code try_} variants of these functions, such as
+ * {@code jni_utils::ops::try_fn_consumer()}, accept closures which return a
+ * {@code Result}. If the closure returns an error, it is thrown as an
+ * exception.
+ */
+public interface FnConsumer<T> extends Consumer<T>, Closeable {
+    /**
+     * Runs the closure associated with this object.
+     * <p>
+     * If the closure is a {@code std::ops::Fn} or {@code std::ops::FnMut},
+     * calling this method twice will call the associated closure twice. If
+     * the closure is a {@code std::ops::FnOnce}, this method is idempotent -
+     * calling it a second time will have no effect. If {@link close} has
+     * already been called, this method is a no-op.
+     */
+    @Override
+    public void accept(T t);
+
+    /**
+     * Disposes of the closure associated with this object.
+     * <p>
+     * This method is idempotent - if it's called twice, the second call is a
+     * no-op.
+     */
+    @Override
+    public void close();
+}
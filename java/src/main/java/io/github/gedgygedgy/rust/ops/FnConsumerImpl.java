@@ -0,0 +1,21 @@
+package io.github.gedgygedgy.rust.ops;
+
+import java.io.Closeable;
+
+final class FnConsumerImpl<T> implements FnConsumer<T> {
+    private final FnAdapter<FnConsumerImpl<T>, T, Void, Void> adapter;
+
+    private FnConsumerImpl(FnAdapter<FnConsumerImpl<T>, T, Void, Void> adapter) {
+        this.adapter = adapter;
+    }
+
+    @Override
+    public void accept(T t) {
+        this.adapter.call(this, t, null);
+    }
+
+    @Override
+    public void close() {
+        this.adapter.close();
+    }
+}
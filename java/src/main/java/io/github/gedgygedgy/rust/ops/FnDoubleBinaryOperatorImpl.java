@@ -0,0 +1,19 @@
+package io.github.gedgygedgy.rust.ops;
+
+final class FnDoubleBinaryOperatorImpl implements FnDoubleBinaryOperator {
+    private final FnAdapter<FnDoubleBinaryOperatorImpl, Void, Void, Void> adapter;
+
+    private FnDoubleBinaryOperatorImpl(FnAdapter<FnDoubleBinaryOperatorImpl, Void, Void, Void> adapter) {
+        this.adapter = adapter;
+    }
+
+    @Override
+    public double applyAsDouble(double left, double right) {
+        return this.adapter.callDouble(this, left, right);
+    }
+
+    @Override
+    public void close() {
+        this.adapter.close();
+    }
+}
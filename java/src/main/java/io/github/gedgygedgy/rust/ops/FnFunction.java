@@ -11,6 +11,11 @@ import java.util.function.Function;
  * {@code jni_utils::ops::fn_mut_function()}, or
  * {@code jni_utils::ops::fn_once_function()} from Rust code to obtain an
  * instance of this class.
+ * <p>
+ * The {@code try_} variants of these functions, such as
+ * {@code jni_utils::ops::try_fn_function()}, accept closures which return a
+ * {@code Result}. If the closure returns an error, it is thrown as an
+ * exception.
  */
 public interface FnFunction<T, R> extends Function<T, R>, Closeable {
     /**
@@ -0,0 +1,19 @@
+package io.github.gedgygedgy.rust.ops;
+
+final class FnIntUnaryOperatorImpl implements FnIntUnaryOperator {
+    private final FnAdapter<FnIntUnaryOperatorImpl, Void, Void, Void> adapter;
+
+    private FnIntUnaryOperatorImpl(FnAdapter<FnIntUnaryOperatorImpl, Void, Void, Void> adapter) {
+        this.adapter = adapter;
+    }
+
+    @Override
+    public int applyAsInt(int operand) {
+        return this.adapter.callInt(this, operand);
+    }
+
+    @Override
+    public void close() {
+        this.adapter.close();
+    }
+}
@@ -0,0 +1,74 @@
+package io.github.gedgygedgy.rust.ops;
+
+import java.io.Closeable;
+import java.lang.invoke.MethodType;
+import java.lang.reflect.InvocationHandler;
+import java.lang.reflect.Method;
+import java.lang.reflect.Proxy;
+import java.util.ArrayList;
+import java.util.List;
+
+final class FnInvocationHandler implements InvocationHandler {
+    private static final Object[] NO_ARGS = new Object[0];
+
+    private final FnAdapter<Object, String, Object[], Object> adapter;
+
+    private FnInvocationHandler(FnAdapter<Object, String, Object[], Object> adapter) {
+        this.adapter = adapter;
+    }
+
+    private static Object newProxy(Class<?>[] interfaces, FnAdapter<Object, String, Object[], Object> adapter) {
+        List<Class<?>> allInterfaces = new ArrayList<>();
+        for (Class<?> c : interfaces) {
+            if (c != Closeable.class) {
+                allInterfaces.add(c);
+            }
+        }
+        allInterfaces.add(Closeable.class);
+
+        return Proxy.newProxyInstance(
+            findClassLoader(interfaces),
+            allInterfaces.toArray(new Class<?>[0]),
+            new FnInvocationHandler(adapter)
+        );
+    }
+
+    private static ClassLoader findClassLoader(Class<?>[] interfaces) {
+        // JDK interfaces have a null (bootstrap) class loader, which can't see
+        // application interfaces, so use the first loader that isn't null.
+        for (Class<?> c : interfaces) {
+            ClassLoader loader = c.getClassLoader();
+            if (loader != null) {
+                return loader;
+            }
+        }
+        return FnInvocationHandler.class.getClassLoader();
+    }
+
+    @Override
+    public Object invoke(Object proxy, Method method, Object[] args) {
+        String name = method.getName();
+        int parameterCount = method.getParameterCount();
+
+        if (method.getDeclaringClass() == Object.class) {
+            switch (name) {
+            case "equals":
+                return proxy == args[0];
+            case "hashCode":
+                return System.identityHashCode(proxy);
+            default:
+                return proxy.getClass().getName() + "@" + Integer.toHexString(System.identityHashCode(proxy));
+            }
+        }
+        if (name.equals("close") && parameterCount == 0) {
+            this.adapter.close();
+            return null;
+        }
+
+        // Method names can't contain '(', so the name and descriptor are
+        // passed as one string and split apart on the Rust side.
+        String descriptor = MethodType.methodType(method.getReturnType(), method.getParameterTypes())
+            .toMethodDescriptorString();
+        return this.adapter.call(proxy, name + descriptor, args == null ? NO_ARGS : args);
+    }
+}
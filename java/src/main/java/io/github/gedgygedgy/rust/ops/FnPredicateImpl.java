@@ -0,0 +1,20 @@
+package io.github.gedgygedgy.rust.ops;
+
+final class FnPredicateImpl<T> implements FnPredicate<T> {
+    private final FnAdapter<FnPredicateImpl<T>, T, Void, Boolean> adapter;
+
+    private FnPredicateImpl(FnAdapter<FnPredicateImpl<T>, T, Void, Boolean> adapter) {
+        this.adapter = adapter;
+    }
+
+    @Override
+    public boolean test(T t) {
+        Boolean result = this.adapter.call(this, t, null);
+        return result == null ? false : result;
+    }
+
+    @Override
+    public void close() {
+        this.adapter.close();
+    }
+}
@@ -10,6 +10,11 @@ import java.io.Closeable;
  * {@code jni_utils::ops::fn_mut_runnable()}, or
  * {@code jni_utils::ops::fn_once_runnable()} from Rust code to obtain an
  * instance of this class.
+ * <p>
+ * The {@code try_} variants of these functions, such as
+ * {@code jni_utils::ops::try_fn_runnable()}, accept closures which return a
+ * {@code Result}. If the closure returns an error, it is thrown as an
+ * exception.
  */
 public interface FnRunnable extends Runnable, Closeable {
     /**
@@ -0,0 +1,21 @@
+package io.github.gedgygedgy.rust.ops;
+
+import java.io.Closeable;
+
+final class FnSupplierImpl<T> implements FnSupplier<T> {
+    private final FnAdapter<FnSupplierImpl<T>, Void, Void, T> adapter;
+
+    private FnSupplierImpl(FnAdapter<FnSupplierImpl<T>, Void, Void, T> adapter) {
+        this.adapter = adapter;
+    }
+
+    @Override
+    public T get() {
+        return this.adapter.call(this, null, null);
+    }
+
+    @Override
+    public void close() {
+        this.adapter.close();
+    }
+}
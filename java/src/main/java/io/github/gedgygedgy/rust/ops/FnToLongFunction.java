@@ -0,0 +1,36 @@
+package io.github.gedgygedgy.rust.ops;
+
+import java.io.Closeable;
+import java.util.function.ToLongFunction;
+
+/**
+ * Wraps a closure in a Java object.
+ * <p>
+ * Instances of this class cannot be obtained directly from Java. Instead, call
+ * {@code jni_utils::ops::fn_to_long_function()},
+ * {@code jni_utils::ops::fn_mut_to_long_function()}, or
+ * {@code jni_utils::ops::fn_once_to_long_function()} from Rust code to obtain an
+ * instance of this class.
+ */
+public interface FnToLongFunction<T> extends ToLongFunction<T>, Closeable {
+    /**
+     * Runs the closure associated with this object.
+     * <p>
+     * If the closure is a {@code std::ops::Fn} or {@code std::ops::FnMut},
+     * calling this method twice will call the associated closure twice. If
+     * the closure is a {@code std::ops::FnOnce}, the second call will return
+     * {@code 0}. If {@link close} has already been called, this method
+     * returns {@code 0}.
+     */
+    @Override
+    public long applyAsLong(T value);
+
+    /**
+     * Disposes of the closure associated with this object.
+     * <p>
+     * This method is idempotent - if it's called twice, the second call is a
+     * no-op.
+     */
+    @Override
+    public void close();
+}
@@ -0,0 +1,19 @@
+package io.github.gedgygedgy.rust.ops;
+
+final class FnToLongFunctionImpl<T> implements FnToLongFunction<T> {
+    private final FnAdapter<FnToLongFunctionImpl<T>, T, Void, Void> adapter;
+
+    private FnToLongFunctionImpl(FnAdapter<FnToLongFunctionImpl<T>, T, Void, Void> adapter) {
+        this.adapter = adapter;
+    }
+
+    @Override
+    public long applyAsLong(T value) {
+        return this.adapter.callLong(this, value);
+    }
+
+    @Override
+    public void close() {
+        this.adapter.close();
+    }
+}
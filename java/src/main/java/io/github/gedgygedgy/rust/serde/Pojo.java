@@ -0,0 +1,140 @@
+package io.github.gedgygedgy.rust.serde;
+
+import java.lang.reflect.Constructor;
+import java.lang.reflect.Field;
+import java.lang.reflect.InvocationTargetException;
+import java.lang.reflect.Modifier;
+import java.util.LinkedHashMap;
+import java.util.Map;
+
+/**
+ * Converts plain Java objects to and from {@link Map}s using field
+ * reflection.
+ * <p>
+ * This class is used by {@code jni_utils::serde} and is not intended to be
+ * used directly from Java code.
+ */
+public final class Pojo {
+    private Pojo() {}
+
+    /**
+     * Reads the non-static, non-transient fields of an object, including
+     * inherited fields, into a map from field name to value.
+     *
+     * @param obj Object to read.
+     * @return Map of field names to values.
+     * @throws IllegalAccessException if a field could not be read.
+     */
+    public static Map<String, Object> toMap(Object obj) throws IllegalAccessException {
+        Map<String, Object> result = new LinkedHashMap<>();
+        for (Class<?> c = obj.getClass(); c != null && c != Object.class; c = c.getSuperclass()) {
+            for (Field field : c.getDeclaredFields()) {
+                int modifiers = field.getModifiers();
+                if (Modifier.isStatic(modifiers) || Modifier.isTransient(modifiers) || field.isSynthetic()) {
+                    continue;
+                }
+                field.setAccessible(true);
+                result.putIfAbsent(field.getName(), field.get(obj));
+            }
+        }
+        return result;
+    }
+
+    /**
+     * Creates a new instance of a class with its no-argument constructor and
+     * sets its fields from a map of field names to values. Fields which are
+     * not in the map keep their default values.
+     * <p>
+     * Nested maps are converted recursively if the field's type is not itself
+     * a {@link Map}. Integers are converted to the field's integer type if
+     * they are in range, any number can be converted to {@code float} or
+     * {@code double}, and single-character strings are converted to
+     * {@code char}.
+     *
+     * @param map Map of field names to values.
+     * @param cls Class to instantiate.
+     * @param <T> Type of the new object.
+     * @return New object.
+     * @throws ReflectiveOperationException if the object could not be created
+     *         or a field could not be set.
+     * @throws IllegalArgumentException if the map contains a key which is not
+     *         a field of the class, or a value which cannot be converted to
+     *         the field's type.
+     */
+    public static <T> T fromMap(Map<?, ?> map, Class<T> cls) throws ReflectiveOperationException {
+        Constructor<T> constructor = cls.getDeclaredConstructor();
+        constructor.setAccessible(true);
+        T obj;
+        try {
+            obj = constructor.newInstance();
+        } catch (InvocationTargetException e) {
+            throw new IllegalArgumentException("Could not create " + cls.getName(), e.getCause());
+        }
+
+        for (Map.Entry<?, ?> entry : map.entrySet()) {
+            String name = String.valueOf(entry.getKey());
+            Field field = findField(cls, name);
+            if (field == null) {
+                throw new IllegalArgumentException("No field " + name + " in " + cls.getName());
+            }
+            field.setAccessible(true);
+            field.set(obj, convert(entry.getValue(), field.getType()));
+        }
+        return obj;
+    }
+
+    private static Field findField(Class<?> cls, String name) {
+        for (Class<?> c = cls; c != null && c != Object.class; c = c.getSuperclass()) {
+            try {
+                Field field = c.getDeclaredField(name);
+                if (!Modifier.isStatic(field.getModifiers())) {
+                    return field;
+                }
+            } catch (NoSuchFieldException e) {
+                // Try the superclass
+            }
+        }
+        return null;
+    }
+
+    private static Object convert(Object value, Class<?> type) throws ReflectiveOperationException {
+        if (value == null) {
+            return null;
+        }
+        if (value instanceof Map && !type.isAssignableFrom(value.getClass())) {
+            return fromMap((Map<?, ?>) value, type);
+        }
+        if (value instanceof Number) {
+            Number n = (Number) value;
+            if (type == byte.class || type == Byte.class) {
+                return (byte) checkRange(n, Byte.MIN_VALUE, Byte.MAX_VALUE, type);
+            } else if (type == short.class || type == Short.class) {
+                return (short) checkRange(n, Short.MIN_VALUE, Short.MAX_VALUE, type);
+            } else if (type == int.class || type == Integer.class) {
+                return (int) checkRange(n, Integer.MIN_VALUE, Integer.MAX_VALUE, type);
+            } else if (type == long.class || type == Long.class) {
+                return checkRange(n, Long.MIN_VALUE, Long.MAX_VALUE, type);
+            } else if (type == float.class || type == Float.class) {
+                return n.floatValue();
+            } else if (type == double.class || type == Double.class) {
+                return n.doubleValue();
+            }
+        }
+        if (value instanceof String && (type == char.class || type == Character.class)) {
+            String s = (String) value;
+            if (s.length() == 1) {
+                return s.charAt(0);
+            }
+        }
+        return value;
+    }
+
+    private static long checkRange(Number n, long min, long max, Class<?> type) {
+        long l = n.longValue();
+        boolean integral = n instanceof Byte || n instanceof Short || n instanceof Integer || n instanceof Long;
+        if (!integral || l < min || l > max) {
+            throw new IllegalArgumentException("Cannot convert " + n + " to " + type.getName());
+        }
+        return l;
+    }
+}
@@ -0,0 +1,325 @@
+use crate::primitives::JavaPrimitive;
+use jni::{
+    errors::Result,
+    objects::{JObject, JValue},
+    signature::{JavaType, Primitive},
+};
+
+/// Declare a wrapper struct for [`JObject`]s of a given Java class, with a
+/// typed Rust method for each Java method.
+///
+/// This generates the same kind of wrapper as [`JUuid`](crate::uuid::JUuid)
+/// and the other wrappers in this crate:
+///
+/// * A `from_env()` function which looks up the class and method IDs once, so
+///   that extra work doesn't need to be done on every method call.
+/// * One function for each constructor in the optional `constructors` block,
+///   which creates a new object and wraps it.
+/// * One method for each Java method in the optional `methods` block, which
+///   calls the Java method with the cached method ID.
+/// * Implementations of [`Deref<Target = JObject>`](std::ops::Deref) and
+///   `From<...> for JObject`.
+/// * An implementation of [`JavaSignature`](crate::signature::JavaSignature).
+///
+/// Each method is given a Rust signature, followed by the name of the Java
+/// method and optionally its JNI type signature. Arguments can be of any type
+/// which can be converted into a [`JValue`], and the return type can be `()`,
+/// a primitive type, or [`JObject`]. If the JNI signature is omitted, it is
+/// built from the Rust types with [`sig!`](crate::sig), so they must all
+/// implement [`JavaSignature`](crate::signature::JavaSignature). Otherwise, the
+/// signatures are not checked against each other, so they must agree.
+/// Constructors work the same way, except that they have no name or return
+/// type. The lifetimes `'a` (for local references) and `'b` (for the
+/// [`JNIEnv`](jni::JNIEnv)) are in scope, and methods cannot be named
+/// `internal` or `env`.
+///
+/// ```
+/// use jni::{
+///     objects::{JObject, JString},
+///     sys::jint,
+/// };
+///
+/// jni_utils::java_class! {
+///     /// Wrapper for `java.lang.StringBuilder`.
+///     pub struct JStringBuilder = "java/lang/StringBuilder" {
+///         constructors {
+///             /// Create an empty `StringBuilder`.
+///             pub fn new();
+///
+///             /// Create a `StringBuilder` with the given contents.
+///             pub fn with_string(s: JString<'a>);
+///         }
+///
+///         methods {
+///             /// Append a string.
+///             pub fn append(s: JString<'a>) -> JObject<'a> =
+///                 "append", "(Ljava/lang/String;)Ljava/lang/StringBuilder;";
+///
+///             /// Get the length of the contents.
+///             pub fn length() -> jint = "length";
+///         }
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! java_class {
+    (@sig [$sig:literal] $($rest:tt)*) => {
+        $sig
+    };
+    (@sig [] $($rest:tt)*) => {
+        $crate::sig!($($rest)*)
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident = $class:literal {
+            $(
+                constructors {
+                    $(
+                        $(#[$ctor_meta:meta])*
+                        $ctor_vis:vis fn $ctor:ident(
+                            $($ctor_arg:ident: $ctor_arg_ty:ty),* $(,)?
+                        ) $(= $ctor_sig:literal)?;
+                    )*
+                }
+            )?
+            $(
+                methods {
+                    $(
+                        $(#[$method_meta:meta])*
+                        $method_vis:vis fn $method:ident(
+                            $($arg:ident: $arg_ty:ty),* $(,)?
+                        ) -> $ret:ty = $java_name:literal $(, $sig:literal)?;
+                    )*
+                }
+            )?
+        }
+    ) => {
+        $(#[$meta])*
+        ///
+        /// Looks up the class and method IDs on creation rather than for every
+        /// method call.
+        $vis struct $name<'a: 'b, 'b> {
+            internal: ::jni::objects::JObject<'a>,
+            $($($method: ::jni::objects::JMethodID<'a>,)*)?
+            env: &'b ::jni::JNIEnv<'a>,
+        }
+
+        #[allow(dead_code)]
+        impl<'a: 'b, 'b> $name<'a, 'b> {
+            #[doc = concat!(
+                "Create a [`", stringify!($name), "`] from the environment and an ",
+                "object. This looks up the necessary class and method IDs to call ",
+                "all of the methods on it so that extra work doesn't need to be ",
+                "done on every method call.\n\n",
+                "# Arguments\n\n",
+                "* `env` - Java environment to use.\n",
+                "* `obj` - Object to wrap.",
+            )]
+            pub fn from_env(
+                env: &'b ::jni::JNIEnv<'a>,
+                obj: ::jni::objects::JObject<'a>,
+            ) -> ::jni::errors::Result<Self> {
+                let class = env.auto_local(env.find_class($class)?);
+                Self::from_env_impl(env, obj, class)
+            }
+
+            $($(
+                $(#[$ctor_meta])*
+                $ctor_vis fn $ctor(
+                    env: &'b ::jni::JNIEnv<'a>,
+                    $($ctor_arg: $ctor_arg_ty),*
+                ) -> ::jni::errors::Result<Self> {
+                    let class = env.auto_local(env.find_class($class)?);
+                    let obj = env.new_object(
+                        &class,
+                        $crate::java_class!(@sig [$($ctor_sig)?] ($($ctor_arg_ty),*) -> ()),
+                        &[$(::jni::objects::JValue::from($ctor_arg)),*],
+                    )?;
+                    Self::from_env_impl(env, obj, class)
+                }
+            )*)?
+
+            $($(
+                $(#[$method_meta])*
+                $method_vis fn $method(
+                    &self,
+                    $($arg: $arg_ty),*
+                ) -> ::jni::errors::Result<$ret> {
+                    let result = self.env.call_method_unchecked(
+                        self.internal,
+                        self.$method,
+                        <$ret as $crate::class::JavaReturn>::java_type(),
+                        &[$(::jni::objects::JValue::from($arg)),*],
+                    )?;
+                    <$ret as $crate::class::JavaReturn>::from_jvalue(result)
+                }
+            )*)?
+
+            fn from_env_impl(
+                env: &'b ::jni::JNIEnv<'a>,
+                obj: ::jni::objects::JObject<'a>,
+                class: ::jni::objects::AutoLocal<'a, 'b>,
+            ) -> ::jni::errors::Result<Self> {
+                $($(
+                    let $method = env.get_method_id(
+                        &class,
+                        $java_name,
+                        $crate::java_class!(@sig [$($sig)?] ($($arg_ty),*) -> $ret),
+                    )?;
+                )*)?
+                Ok(Self {
+                    internal: obj,
+                    $($($method,)*)?
+                    env,
+                })
+            }
+        }
+
+        impl<'a: 'b, 'b> ::std::ops::Deref for $name<'a, 'b> {
+            type Target = ::jni::objects::JObject<'a>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.internal
+            }
+        }
+
+        impl<'a: 'b, 'b> ::std::convert::From<$name<'a, 'b>> for ::jni::objects::JObject<'a> {
+            fn from(other: $name<'a, 'b>) -> ::jni::objects::JObject<'a> {
+                other.internal
+            }
+        }
+
+        impl<'a: 'b, 'b> $crate::signature::JavaSignature for $name<'a, 'b> {
+            const SIGNATURE: &'static str = concat!("L", $class, ";");
+        }
+    };
+}
+
+/// Trait for types which can be returned from a method declared with
+/// [`java_class!`](crate::java_class). This is an implementation detail of
+/// [`java_class!`](crate::java_class).
+#[doc(hidden)]
+pub trait JavaReturn<'a>: Sized {
+    /// Get the type to pass to
+    /// [`JNIEnv::call_method_unchecked()`](jni::JNIEnv::call_method_unchecked).
+    fn java_type() -> JavaType;
+
+    /// Convert the value returned by the method.
+    fn from_jvalue(value: JValue<'a>) -> Result<Self>;
+}
+
+impl<'a> JavaReturn<'a> for () {
+    fn java_type() -> JavaType {
+        JavaType::Primitive(Primitive::Void)
+    }
+
+    fn from_jvalue(value: JValue<'a>) -> Result<Self> {
+        value.v()
+    }
+}
+
+impl<'a> JavaReturn<'a> for JObject<'a> {
+    fn java_type() -> JavaType {
+        JavaType::Object("java/lang/Object".into())
+    }
+
+    fn from_jvalue(value: JValue<'a>) -> Result<Self> {
+        value.l()
+    }
+}
+
+impl<'a, T: JavaPrimitive> JavaReturn<'a> for T {
+    fn java_type() -> JavaType {
+        T::SIG.parse().unwrap()
+    }
+
+    fn from_jvalue(value: JValue<'a>) -> Result<Self> {
+        JavaPrimitive::from_jvalue(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{strings, test_utils};
+    use jni::{
+        objects::{JObject, JString},
+        sys::jint,
+    };
+
+    crate::java_class! {
+        /// Wrapper for `java.lang.StringBuilder`.
+        struct JStringBuilder = "java/lang/StringBuilder" {
+            constructors {
+                fn new();
+                fn with_string(s: JString<'a>);
+            }
+
+            methods {
+                fn append(s: JObject<'a>) -> JObject<'a> =
+                    "append", "(Ljava/lang/String;)Ljava/lang/StringBuilder;";
+                fn append_char(c: u16) -> JObject<'a> =
+                    "append", "(C)Ljava/lang/StringBuilder;";
+                fn length() -> jint = "length";
+                fn set_length(length: jint) -> () = "setLength";
+                fn to_string() -> JObject<'a> = "toString", "()Ljava/lang/String;";
+            }
+        }
+    }
+
+    crate::java_class! {
+        struct JMissing = "java/lang/StringBuilder" {
+            methods {
+                fn missing() -> () = "missing", "()V";
+            }
+        }
+    }
+
+    #[test]
+    fn test_java_class() {
+        test_utils::JVM_ENV.with(|env| {
+            let builder = JStringBuilder::new(env).unwrap();
+            assert_eq!(builder.length().unwrap(), 0);
+
+            let s = env.new_string("Hello").unwrap();
+            let result = builder.append(s.into()).unwrap();
+            assert!(env.is_same_object(result, *builder).unwrap());
+            builder.append_char(b'!' as u16).unwrap();
+            assert_eq!(builder.length().unwrap(), 6);
+
+            let s = builder.to_string().unwrap();
+            assert_eq!(strings::to_string(env, s).unwrap(), "Hello!");
+
+            builder.set_length(5).unwrap();
+            let s = builder.to_string().unwrap();
+            assert_eq!(strings::to_string(env, s).unwrap(), "Hello");
+
+            let s = env.new_string("Hi").unwrap();
+            let builder = JStringBuilder::with_string(env, s).unwrap();
+            assert_eq!(builder.length().unwrap(), 2);
+
+            let obj: JObject = builder.into();
+            let builder = JStringBuilder::from_env(env, obj).unwrap();
+            assert_eq!(builder.length().unwrap(), 2);
+        });
+    }
+
+    #[test]
+    fn test_java_class_signature() {
+        assert_eq!(crate::sig!(JStringBuilder), "Ljava/lang/StringBuilder;");
+    }
+
+    #[test]
+    fn test_java_class_missing_method() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = env
+                .new_object("java/lang/StringBuilder", "()V", &[])
+                .unwrap();
+            assert!(JMissing::from_env(env, obj).is_err());
+            let ex = env.exception_occurred().unwrap();
+            env.exception_clear().unwrap();
+            assert!(env
+                .is_instance_of(ex, "java/lang/NoSuchMethodError")
+                .unwrap());
+        });
+    }
+}
@@ -0,0 +1,1256 @@
+use crate::{
+    arrays::{byte_array_to_vec, slice_to_byte_array},
+    list::{list_to_vec, JList},
+    map::{map_to_btree_map, map_to_hash_map, JMap},
+    math::JBigInteger,
+    optional::{
+        option_to_optional, option_to_optional_double, option_to_optional_int,
+        option_to_optional_long, optional_double_to_option, optional_int_to_option,
+        optional_long_to_option, optional_to_option,
+    },
+    primitives::{box_primitive, check_instance_of, unbox_primitive},
+    set::{set_to_btree_set, set_to_hash_set, JSet},
+    strings::{new_string, to_string},
+    time::{JDuration, JInstant},
+    uuid::JUuid,
+};
+use jni::{
+    errors::{Error, Result},
+    objects::{GlobalRef, JClass, JFieldID, JMethodID, JObject, JValue},
+    signature::{JavaType, TypeSignature},
+    sys::{jfieldID, jmethodID},
+    JNIEnv,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    hash::Hash,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+    time::{Duration, SystemTime},
+};
+use uuid::Uuid;
+
+/// Derive [`FromJava`] or [`IntoJava`] for a struct with named fields, mapping
+/// it to a Java class. Requires the `derive` feature.
+///
+/// The class is given with `#[java(class = "com/example/Point")]`, and the
+/// conversion can work in one of three ways:
+///
+/// * By default, the Java class's fields are read and written directly, and
+///   [`IntoJava`] uses the class's no-argument constructor.
+/// * With `#[java(class = "...", constructor)]`, [`IntoJava`] calls a
+///   constructor which takes every field in declaration order, and
+///   [`FromJava`] calls a getter for each field (`getFoo()`, or `isFoo()` for
+///   `bool` fields).
+/// * With `#[java(class = "...", record)]`, the class is a Java record, and the
+///   accessors have the same names as the fields.
+///
+/// Java names default to the camel case version of the Rust field names. The
+/// following attributes can be put on individual fields:
+///
+/// * `#[java(name = "...")]` - Name of the Java field.
+/// * `#[java(getter = "...")]` - Name of the getter or accessor method. Only
+///   allowed with `constructor` or `record`.
+/// * `#[java(sig = "...")]` - JNI type signature of the field. This is
+///   required for types other than primitives, [`String`], [`Uuid`],
+///   [`Duration`], [`SystemTime`], [`PathBuf`], `Vec<u8>`, [`GlobalRef`],
+///   [`i128`], [`u128`], [`JavaList`], [`JavaFile`], [`JavaOptional`],
+///   [`JavaOptionalInt`], [`JavaOptionalLong`], [`JavaOptionalDouble`],
+///   [`HashMap`], [`BTreeMap`], [`HashSet`], [`BTreeSet`] and [`Option`]s of
+///   those. A field with a single-character signature is
+///   converted as a primitive, and any other field must implement
+///   [`FromJava`] and [`IntoJava`].
+/// * `#[java(skip)]` - Don't map the field. It is set to
+///   [`Default::default()`] by [`FromJava`], and is not passed to the
+///   constructor.
+///
+/// Field and method IDs are looked up the first time a conversion is done,
+/// and are cached for the lifetime of the program.
+///
+/// ```no_run
+/// use jni_utils::convert::{FromJava, IntoJava};
+///
+/// #[derive(FromJava, IntoJava)]
+/// #[java(class = "java/awt/Point")]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// #[derive(FromJava, IntoJava)]
+/// #[java(class = "com/example/Person", record)]
+/// struct Person {
+///     name: String,
+///     #[java(sig = "Ljava/awt/Point;")]
+///     location: Point,
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use jni_utils_derive::{FromJava, IntoJava};
+
+/// Trait for Rust types which can be created from a Java object.
+///
+/// This allows generic code to decode Java objects without knowing which
+/// conversion function to call. Implementations are provided for all of the
+/// conversions in this crate which don't need extra arguments, and
+/// [`Option<T>`] can be used to accept `null`.
+pub trait FromJava: Sized {
+    /// Convert a Java object into a Rust value. The object is borrowed, and its
+    /// local reference is not deleted.
+    ///
+    /// Unless the implementation says otherwise, a `null` object results in an
+    /// error.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to convert.
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self>;
+}
+
+/// Trait for Rust types which can be converted into a Java object.
+///
+/// This is the counterpart of [`FromJava`]. [`Option<T>`] converts [`None`]
+/// into `null`.
+pub trait IntoJava {
+    /// Convert a Rust value into a new local reference to a Java object.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment in which to create the new object.
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>>;
+}
+
+impl<T: FromJava> FromJava for Option<T> {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        if env.is_same_object(obj, JObject::null())? {
+            Ok(None)
+        } else {
+            T::from_java(env, obj).map(Some)
+        }
+    }
+}
+
+impl<T: IntoJava> IntoJava for Option<T> {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        match self {
+            Some(value) => value.into_java(env),
+            None => Ok(JObject::null()),
+        }
+    }
+}
+
+macro_rules! impl_primitive {
+    ($($t:ty),*) => {
+        $(
+            impl FromJava for $t {
+                fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+                    unbox_primitive(env, obj)
+                }
+            }
+
+            impl IntoJava for $t {
+                fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+                    box_primitive(env, self)
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive!(bool, i8, u16, i16, i32, i64, f32, f64);
+
+impl FromJava for String {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        to_string(env, obj)
+    }
+}
+
+impl IntoJava for String {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        self.as_str().into_java(env)
+    }
+}
+
+impl IntoJava for &str {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        Ok(new_string(env, self)?.into())
+    }
+}
+
+impl FromJava for Vec<u8> {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        check_object(env, obj, "[B")?;
+        byte_array_to_vec(env, obj.into_inner())
+    }
+}
+
+impl IntoJava for Vec<u8> {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        self.as_slice().into_java(env)
+    }
+}
+
+impl IntoJava for &[u8] {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        Ok(slice_to_byte_array(env, self)?.into())
+    }
+}
+
+impl FromJava for Uuid {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        check_object(env, obj, "java/util/UUID")?;
+        JUuid::from_env(env, obj)?.as_uuid()
+    }
+}
+
+impl IntoJava for Uuid {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        Ok(JUuid::new(env, self)?.into())
+    }
+}
+
+impl FromJava for Duration {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        check_object(env, obj, "java/time/Duration")?;
+        JDuration::from_env(env, obj)?.as_duration()
+    }
+}
+
+impl IntoJava for Duration {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        Ok(JDuration::new(env, self)?.into())
+    }
+}
+
+/// Converts to and from `java.time.Instant`.
+impl FromJava for SystemTime {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        check_object(env, obj, "java/time/Instant")?;
+        JInstant::from_env(env, obj)?.as_system_time()
+    }
+}
+
+impl IntoJava for SystemTime {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        Ok(JInstant::new(env, self)?.into())
+    }
+}
+
+/// Converts from `java.nio.file.Path`.
+impl FromJava for PathBuf {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        crate::path::path_to_path_buf(env, obj)
+    }
+}
+
+/// Converts into `java.nio.file.Path`.
+impl IntoJava for PathBuf {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        self.as_path().into_java(env)
+    }
+}
+
+/// Converts into `java.nio.file.Path`.
+impl IntoJava for &Path {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        crate::path::path_buf_to_path(env, self)
+    }
+}
+
+/// Creates a new global reference to any object. `null` is allowed.
+impl FromJava for GlobalRef {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        env.new_global_ref(obj)
+    }
+}
+
+/// Converts to and from `java.math.BigInteger`. If the value doesn't fit, a
+/// `java.lang.ArithmeticException` is thrown.
+impl FromJava for i128 {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        check_object(env, obj, "java/math/BigInteger")?;
+        JBigInteger::from_env(env, obj)?.as_i128()
+    }
+}
+
+impl IntoJava for i128 {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        Ok(JBigInteger::from_i128(env, self)?.into())
+    }
+}
+
+/// Converts to and from `java.math.BigInteger`. If the value doesn't fit, a
+/// `java.lang.ArithmeticException` is thrown.
+impl FromJava for u128 {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        check_object(env, obj, "java/math/BigInteger")?;
+        JBigInteger::from_env(env, obj)?.as_u128()
+    }
+}
+
+impl IntoJava for u128 {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        Ok(JBigInteger::from_u128(env, self)?.into())
+    }
+}
+
+/// [`Vec`] which converts to and from a `java.util.List`, converting each
+/// element with [`FromJava`] and [`IntoJava`]. This is needed because
+/// `Vec<u8>` already converts to and from `byte[]`.
+///
+/// Lists are converted into a new `java.util.ArrayList`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct JavaList<T>(pub Vec<T>);
+
+impl<T: FromJava> FromJava for JavaList<T> {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        check_object(env, obj, "java/util/List")?;
+        list_to_vec(env, obj, T::from_java).map(Self)
+    }
+}
+
+impl<T: IntoJava> IntoJava for JavaList<T> {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        let list = JList::with_capacity(env, self.0.len())?;
+        for item in self.0 {
+            let obj = env.auto_local(item.into_java(env)?);
+            list.add(obj.as_obj())?;
+        }
+        Ok(list.into())
+    }
+}
+
+/// Converts from any `java.util.Map`, and into a new `java.util.HashMap`.
+impl<K: FromJava + Eq + Hash, V: FromJava> FromJava for HashMap<K, V> {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        check_object(env, obj, "java/util/Map")?;
+        map_to_hash_map(env, obj, K::from_java, V::from_java)
+    }
+}
+
+impl<K: IntoJava, V: IntoJava, S> IntoJava for HashMap<K, V, S> {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        fill_map(env, JMap::new(env)?, self)
+    }
+}
+
+/// Converts from any `java.util.Map`, and into a new
+/// `java.util.LinkedHashMap` which keeps the order of the keys.
+impl<K: FromJava + Ord, V: FromJava> FromJava for BTreeMap<K, V> {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        check_object(env, obj, "java/util/Map")?;
+        map_to_btree_map(env, obj, K::from_java, V::from_java)
+    }
+}
+
+impl<K: IntoJava, V: IntoJava> IntoJava for BTreeMap<K, V> {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        fill_map(env, JMap::new_linked(env)?, self)
+    }
+}
+
+/// Converts from any `java.util.Set`, and into a new `java.util.HashSet`.
+impl<T: FromJava + Eq + Hash> FromJava for HashSet<T> {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        check_object(env, obj, "java/util/Set")?;
+        set_to_hash_set(env, obj, T::from_java)
+    }
+}
+
+impl<T: IntoJava, S> IntoJava for HashSet<T, S> {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        fill_set(env, JSet::new(env)?, self)
+    }
+}
+
+/// Converts from any `java.util.Set`, and into a new
+/// `java.util.LinkedHashSet` which keeps the order of the elements.
+impl<T: FromJava + Ord> FromJava for BTreeSet<T> {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        check_object(env, obj, "java/util/Set")?;
+        set_to_btree_set(env, obj, T::from_java)
+    }
+}
+
+impl<T: IntoJava> IntoJava for BTreeSet<T> {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        fill_set(env, JSet::new_linked(env)?, self)
+    }
+}
+
+/// [`Option`] which converts to and from a `java.util.Optional`, converting
+/// the contained value with [`FromJava`] and [`IntoJava`]. Unlike [`Option`]
+/// itself, [`None`] is converted into `Optional.empty()` rather than `null`.
+///
+/// A `null` reference is converted into [`None`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct JavaOptional<T>(pub Option<T>);
+
+impl<T: FromJava> FromJava for JavaOptional<T> {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        optional_to_option(env, obj, T::from_java).map(Self)
+    }
+}
+
+impl<T: IntoJava> IntoJava for JavaOptional<T> {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        option_to_optional(env, self.0, |env, value| value.into_java(env))
+    }
+}
+
+macro_rules! impl_optional_primitive {
+    ($(#[$meta:meta])* $name:ident, $t:ty, $to_option:ident, $to_optional:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        pub struct $name(pub Option<$t>);
+
+        impl FromJava for $name {
+            fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+                $to_option(env, obj).map(Self)
+            }
+        }
+
+        impl IntoJava for $name {
+            fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+                $to_optional(env, self.0)
+            }
+        }
+    };
+}
+
+impl_optional_primitive!(
+    /// [`Option`] which converts to and from a `java.util.OptionalInt`. A
+    /// `null` reference is converted into [`None`].
+    JavaOptionalInt,
+    i32,
+    optional_int_to_option,
+    option_to_optional_int
+);
+impl_optional_primitive!(
+    /// [`Option`] which converts to and from a `java.util.OptionalLong`. A
+    /// `null` reference is converted into [`None`].
+    JavaOptionalLong,
+    i64,
+    optional_long_to_option,
+    option_to_optional_long
+);
+impl_optional_primitive!(
+    /// [`Option`] which converts to and from a `java.util.OptionalDouble`. A
+    /// `null` reference is converted into [`None`].
+    JavaOptionalDouble,
+    f64,
+    optional_double_to_option,
+    option_to_optional_double
+);
+
+/// [`PathBuf`] which converts to and from a `java.io.File`. This is needed
+/// because [`PathBuf`] already converts to and from `java.nio.file.Path`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct JavaFile(pub PathBuf);
+
+impl FromJava for JavaFile {
+    fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        check_object(env, obj, "java/io/File")?;
+        crate::path::file_to_path_buf(env, obj).map(Self)
+    }
+}
+
+impl IntoJava for JavaFile {
+    fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+        crate::path::path_buf_to_file(env, self.0)
+    }
+}
+
+/// Member of a Java class used by the derive macros. This is an implementation
+/// detail of [`FromJava`] and [`IntoJava`] derives.
+#[doc(hidden)]
+pub enum JavaMember {
+    /// Field with a name and type signature.
+    Field(&'static str, &'static str),
+    /// Method with a name and type signature.
+    Method(&'static str, &'static str),
+    /// Constructor with a type signature.
+    Constructor(&'static str),
+}
+
+/// Cache of a Java class and its member IDs. This is an implementation detail
+/// of [`FromJava`] and [`IntoJava`] derives.
+#[doc(hidden)]
+pub struct JavaClassCache(OnceLock<CachedClass>);
+
+impl JavaClassCache {
+    pub const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    /// Get the cached class, looking up the class and its members the first
+    /// time this is called.
+    pub fn get(
+        &self,
+        env: &JNIEnv,
+        class: &'static str,
+        members: &[JavaMember],
+    ) -> Result<&CachedClass> {
+        if let Some(cached) = self.0.get() {
+            return Ok(cached);
+        }
+
+        let local = env.auto_local(env.find_class(class)?);
+        let local_class = JClass::from(local.as_obj());
+        let mut ids = Vec::with_capacity(members.len());
+        for member in members {
+            ids.push(match member {
+                JavaMember::Field(name, sig) => (
+                    env.get_field_id(local_class, *name, *sig)?.into_inner() as usize,
+                    JavaType::from_str(sig)?,
+                ),
+                JavaMember::Method(name, sig) => (
+                    env.get_method_id(local_class, *name, *sig)?.into_inner() as usize,
+                    TypeSignature::from_str(sig)?.ret,
+                ),
+                JavaMember::Constructor(sig) => (
+                    env.get_method_id(local_class, "<init>", *sig)?.into_inner() as usize,
+                    TypeSignature::from_str(sig)?.ret,
+                ),
+            });
+        }
+
+        // If another thread got here first, its IDs are equivalent.
+        let _ = self.0.set(CachedClass {
+            name: class,
+            class: env.new_global_ref(local.as_obj())?,
+            ids,
+        });
+        Ok(self.0.get().unwrap())
+    }
+}
+
+impl Default for JavaClassCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Java class and member IDs cached by a [`JavaClassCache`]. Members are
+/// referred to by their index in the list passed to [`JavaClassCache::get()`].
+#[doc(hidden)]
+pub struct CachedClass {
+    name: &'static str,
+    class: GlobalRef,
+    // Raw IDs are stored as integers so that the cache can be shared between
+    // threads.
+    ids: Vec<(usize, JavaType)>,
+}
+
+impl CachedClass {
+    /// Check that an object is a non-`null` instance of the class.
+    pub fn check_instance<'a: 'b, 'b>(&self, env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<()> {
+        check_object(env, obj, self.name)
+    }
+
+    /// Get the value of a field.
+    pub fn get_field<'a: 'b, 'b>(
+        &self,
+        env: &'b JNIEnv<'a>,
+        obj: JObject<'a>,
+        index: usize,
+    ) -> Result<JValue<'a>> {
+        let (id, ty) = &self.ids[index];
+        env.get_field_unchecked(obj, JFieldID::from(*id as jfieldID), ty.clone())
+    }
+
+    /// Set the value of a field.
+    pub fn set_field<'a: 'b, 'b>(
+        &self,
+        env: &'b JNIEnv<'a>,
+        obj: JObject<'a>,
+        index: usize,
+        value: JValue,
+    ) -> Result<()> {
+        let (id, _) = &self.ids[index];
+        env.set_field_unchecked(obj, JFieldID::from(*id as jfieldID), value)
+    }
+
+    /// Call a method.
+    pub fn call_method<'a: 'b, 'b>(
+        &self,
+        env: &'b JNIEnv<'a>,
+        obj: JObject<'a>,
+        index: usize,
+        args: &[JValue],
+    ) -> Result<JValue<'a>> {
+        let (id, ty) = &self.ids[index];
+        env.call_method_unchecked(obj, JMethodID::from(*id as jmethodID), ty.clone(), args)
+    }
+
+    /// Create a new object with a constructor.
+    pub fn new_object<'a: 'b, 'b>(
+        &self,
+        env: &'b JNIEnv<'a>,
+        index: usize,
+        args: &[JValue],
+    ) -> Result<JObject<'a>> {
+        let (id, _) = &self.ids[index];
+        env.new_object_unchecked(
+            JClass::from(self.class.as_obj()),
+            JMethodID::from(*id as jmethodID),
+            args,
+        )
+    }
+}
+
+// Returns an error for null, and throws ClassCastException if the object isn't
+// an instance of the class.
+fn check_object<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>, class: &str) -> Result<()> {
+    if obj.is_null() {
+        return Err(Error::NullPtr("from_java obj argument"));
+    }
+    check_instance_of(env, obj, class)
+}
+
+fn fill_map<'a: 'b, 'b, K: IntoJava, V: IntoJava>(
+    env: &'b JNIEnv<'a>,
+    map: JMap<'a, 'b>,
+    entries: impl IntoIterator<Item = (K, V)>,
+) -> Result<JObject<'a>> {
+    for (key, value) in entries {
+        let key = env.auto_local(key.into_java(env)?);
+        let value = env.auto_local(value.into_java(env)?);
+        let _old = env.auto_local(map.put(key.as_obj(), value.as_obj())?);
+    }
+    Ok(map.into())
+}
+
+fn fill_set<'a: 'b, 'b, T: IntoJava>(
+    env: &'b JNIEnv<'a>,
+    set: JSet<'a, 'b>,
+    elements: impl IntoIterator<Item = T>,
+) -> Result<JObject<'a>> {
+    for element in elements {
+        let obj = env.auto_local(element.into_java(env)?);
+        set.add(obj.as_obj())?;
+    }
+    Ok(set.into())
+}
+
+#[cfg(feature = "url")]
+mod url_support {
+    use super::{FromJava, IntoJava};
+    use jni::{errors::Result, objects::JObject, JNIEnv};
+    use url::Url;
+
+    /// Converts from `java.net.URI`.
+    impl FromJava for Url {
+        fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+            crate::net::uri_to_url(env, obj)
+        }
+    }
+
+    /// Converts into `java.net.URI`.
+    impl IntoJava for &Url {
+        fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+            crate::net::url_to_uri(env, self)
+        }
+    }
+
+    /// Converts into `java.net.URI`.
+    impl IntoJava for Url {
+        fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+            (&self).into_java(env)
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_support {
+    use super::{check_object, FromJava, IntoJava};
+    use crate::time::{JInstant, JLocalDate, JLocalDateTime, JZonedDateTime};
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+    use jni::{errors::Result, objects::JObject, JNIEnv};
+
+    /// Converts to and from `java.time.Instant`.
+    impl FromJava for DateTime<Utc> {
+        fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+            check_object(env, obj, "java/time/Instant")?;
+            JInstant::from_env(env, obj)?.as_date_time()
+        }
+    }
+
+    impl IntoJava for DateTime<Utc> {
+        fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+            Ok(JInstant::from_date_time(env, &self)?.into())
+        }
+    }
+
+    /// Converts to and from `java.time.ZonedDateTime`. The time zone is
+    /// converted into a fixed offset from UTC.
+    impl FromJava for DateTime<FixedOffset> {
+        fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+            check_object(env, obj, "java/time/ZonedDateTime")?;
+            JZonedDateTime::from_env(env, obj)?.as_date_time()
+        }
+    }
+
+    impl IntoJava for DateTime<FixedOffset> {
+        fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+            Ok(JZonedDateTime::from_date_time(env, &self)?.into())
+        }
+    }
+
+    /// Converts to and from `java.time.LocalDate`.
+    impl FromJava for NaiveDate {
+        fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+            check_object(env, obj, "java/time/LocalDate")?;
+            JLocalDate::from_env(env, obj)?.as_naive_date()
+        }
+    }
+
+    impl IntoJava for NaiveDate {
+        fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+            Ok(JLocalDate::from_naive_date(env, self)?.into())
+        }
+    }
+
+    /// Converts to and from `java.time.LocalDateTime`.
+    impl FromJava for NaiveDateTime {
+        fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+            check_object(env, obj, "java/time/LocalDateTime")?;
+            JLocalDateTime::from_env(env, obj)?.as_naive_date_time()
+        }
+    }
+
+    impl IntoJava for NaiveDateTime {
+        fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+            Ok(JLocalDateTime::from_naive_date_time(env, self)?.into())
+        }
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+mod num_bigint_support {
+    use super::{check_object, FromJava, IntoJava};
+    use crate::math::JBigInteger;
+    use jni::{errors::Result, objects::JObject, JNIEnv};
+    use num_bigint::BigInt;
+
+    impl FromJava for BigInt {
+        fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+            check_object(env, obj, "java/math/BigInteger")?;
+            JBigInteger::from_env(env, obj)?.as_big_int()
+        }
+    }
+
+    impl IntoJava for &BigInt {
+        fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+            Ok(JBigInteger::from_big_int(env, self)?.into())
+        }
+    }
+
+    impl IntoJava for BigInt {
+        fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+            (&self).into_java(env)
+        }
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal_support {
+    use super::{check_object, FromJava, IntoJava};
+    use crate::math::JBigDecimal;
+    use jni::{errors::Result, objects::JObject, JNIEnv};
+    use rust_decimal::Decimal;
+
+    impl FromJava for Decimal {
+        fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+            check_object(env, obj, "java/math/BigDecimal")?;
+            JBigDecimal::from_env(env, obj)?.as_decimal()
+        }
+    }
+
+    impl IntoJava for Decimal {
+        fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+            Ok(JBigDecimal::from_decimal(env, self)?.into())
+        }
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+mod bigdecimal_support {
+    use super::{check_object, FromJava, IntoJava};
+    use crate::math::JBigDecimal;
+    use bigdecimal::BigDecimal;
+    use jni::{errors::Result, objects::JObject, JNIEnv};
+
+    impl FromJava for BigDecimal {
+        fn from_java<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+            check_object(env, obj, "java/math/BigDecimal")?;
+            JBigDecimal::from_env(env, obj)?.as_big_decimal()
+        }
+    }
+
+    impl IntoJava for &BigDecimal {
+        fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+            Ok(JBigDecimal::from_big_decimal(env, self)?.into())
+        }
+    }
+
+    impl IntoJava for BigDecimal {
+        fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> Result<JObject<'a>> {
+            (&self).into_java(env)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        FromJava, IntoJava, JavaFile, JavaList, JavaOptional, JavaOptionalDouble, JavaOptionalInt,
+        JavaOptionalLong,
+    };
+    use crate::test_utils::{self, assert_exception};
+    use jni::{errors::Error, objects::JObject, JNIEnv};
+    use std::{
+        collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+        fmt::Debug,
+        path::PathBuf,
+        time::{Duration, SystemTime},
+    };
+    use uuid::Uuid;
+
+    fn roundtrip<T: IntoJava + FromJava + Clone + PartialEq + Debug>(
+        env: &JNIEnv,
+        value: T,
+        class: &str,
+    ) {
+        let obj = value.clone().into_java(env).unwrap();
+        assert!(env.is_instance_of(obj, class).unwrap());
+        assert_eq!(T::from_java(env, obj).unwrap(), value);
+        env.delete_local_ref(obj).unwrap();
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        test_utils::JVM_ENV.with(|env| {
+            roundtrip(env, true, "java/lang/Boolean");
+            roundtrip(env, -5i8, "java/lang/Byte");
+            roundtrip(env, 0x263au16, "java/lang/Character");
+            roundtrip(env, -300i16, "java/lang/Short");
+            roundtrip(env, i32::MIN, "java/lang/Integer");
+            roundtrip(env, i64::MAX, "java/lang/Long");
+            roundtrip(env, 1.5f32, "java/lang/Float");
+            roundtrip(env, -0.25f64, "java/lang/Double");
+            roundtrip(env, "\u{1f980}".to_string(), "java/lang/String");
+            roundtrip(env, vec![0u8, 1, 255], "[B");
+            roundtrip(
+                env,
+                Uuid::parse_str("d4ec4b5e-cf1d-4e6d-9c06-9a7f1e7e2b5c").unwrap(),
+                "java/util/UUID",
+            );
+            roundtrip(env, Duration::new(5, 10), "java/time/Duration");
+            roundtrip(
+                env,
+                SystemTime::UNIX_EPOCH + Duration::new(1_000_000, 7),
+                "java/time/Instant",
+            );
+            roundtrip(env, PathBuf::from("some/path"), "java/nio/file/Path");
+            roundtrip(env, i128::MIN, "java/math/BigInteger");
+            roundtrip(env, u128::MAX, "java/math/BigInteger");
+            roundtrip(env, JavaFile(PathBuf::from("some/file")), "java/io/File");
+
+            #[cfg(feature = "url")]
+            roundtrip(
+                env,
+                url::Url::parse("https://example.com/a?b=c").unwrap(),
+                "java/net/URI",
+            );
+            #[cfg(feature = "chrono")]
+            roundtrip(
+                env,
+                chrono::DateTime::from_timestamp(1_000_000, 7).unwrap(),
+                "java/time/Instant",
+            );
+            #[cfg(feature = "chrono")]
+            roundtrip(
+                env,
+                chrono::DateTime::from_timestamp(1_000_000, 7)
+                    .unwrap()
+                    .with_timezone(&chrono::FixedOffset::east_opt(-5 * 3600).unwrap()),
+                "java/time/ZonedDateTime",
+            );
+            #[cfg(feature = "chrono")]
+            roundtrip(
+                env,
+                chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+                "java/time/LocalDate",
+            );
+            #[cfg(feature = "chrono")]
+            roundtrip(
+                env,
+                chrono::NaiveDate::from_ymd_opt(-300, 12, 31)
+                    .unwrap()
+                    .and_hms_nano_opt(23, 59, 58, 999_999_999)
+                    .unwrap(),
+                "java/time/LocalDateTime",
+            );
+            #[cfg(feature = "num-bigint")]
+            roundtrip(
+                env,
+                num_bigint::BigInt::from(-1i64) << 100,
+                "java/math/BigInteger",
+            );
+            #[cfg(feature = "rust_decimal")]
+            roundtrip(
+                env,
+                rust_decimal::Decimal::new(-12345, 3),
+                "java/math/BigDecimal",
+            );
+            #[cfg(feature = "bigdecimal")]
+            roundtrip(
+                env,
+                bigdecimal::BigDecimal::new(12345.into(), -3),
+                "java/math/BigDecimal",
+            );
+        });
+    }
+
+    #[test]
+    fn test_collections() {
+        test_utils::JVM_ENV.with(|env| {
+            roundtrip(
+                env,
+                JavaList(vec!["a".to_string(), "b".to_string(), "a".to_string()]),
+                "java/util/ArrayList",
+            );
+            roundtrip(env, JavaList(vec![1i8, 2, 3]), "java/util/List");
+            roundtrip(env, JavaList(Vec::<i32>::new()), "java/util/List");
+            roundtrip(
+                env,
+                JavaList(vec![JavaList(vec![Some(1i64), None])]),
+                "java/util/List",
+            );
+
+            let map: HashMap<String, i32> = vec![("one".to_string(), 1), ("two".to_string(), 2)]
+                .into_iter()
+                .collect();
+            roundtrip(env, map, "java/util/HashMap");
+            let map: BTreeMap<i32, JavaList<String>> =
+                vec![(3, JavaList(vec!["c".to_string()])), (1, JavaList(vec![]))]
+                    .into_iter()
+                    .collect();
+            roundtrip(env, map, "java/util/LinkedHashMap");
+
+            let set: HashSet<Uuid> = vec![Uuid::nil(), Uuid::from_u128(1)].into_iter().collect();
+            roundtrip(env, set, "java/util/HashSet");
+            let set: BTreeSet<String> =
+                vec!["z".to_string(), "y".to_string()].into_iter().collect();
+            roundtrip(env, set, "java/util/LinkedHashSet");
+
+            // Sorted collections keep their order in Java.
+            let set: BTreeSet<i32> = vec![3, 1, 2].into_iter().collect();
+            let obj = set.into_java(env).unwrap();
+            let string = env
+                .call_method(obj, "toString", "()Ljava/lang/String;", &[])
+                .unwrap()
+                .l()
+                .unwrap();
+            assert_eq!(String::from_java(env, string).unwrap(), "[1, 2, 3]");
+
+            let obj = "not a list".into_java(env).unwrap();
+            assert_exception(
+                env,
+                JavaList::<String>::from_java(env, obj),
+                "java/lang/ClassCastException",
+            );
+            assert!(matches!(
+                HashMap::<String, String>::from_java(env, JObject::null()),
+                Err(Error::NullPtr(_))
+            ));
+        });
+    }
+
+    #[test]
+    fn test_optional() {
+        test_utils::JVM_ENV.with(|env| {
+            roundtrip(
+                env,
+                JavaOptional(Some("hello".to_string())),
+                "java/util/Optional",
+            );
+            roundtrip(env, JavaOptional(None::<String>), "java/util/Optional");
+            roundtrip(
+                env,
+                JavaOptional(Some(JavaList(vec![1i32, 2]))),
+                "java/util/Optional",
+            );
+            roundtrip(env, JavaOptionalInt(Some(-7)), "java/util/OptionalInt");
+            roundtrip(env, JavaOptionalInt(None), "java/util/OptionalInt");
+            roundtrip(
+                env,
+                JavaOptionalLong(Some(i64::MIN)),
+                "java/util/OptionalLong",
+            );
+            roundtrip(env, JavaOptionalLong(None), "java/util/OptionalLong");
+            roundtrip(
+                env,
+                JavaOptionalDouble(Some(0.5)),
+                "java/util/OptionalDouble",
+            );
+            roundtrip(env, JavaOptionalDouble(None), "java/util/OptionalDouble");
+
+            // A null reference is treated as empty.
+            assert_eq!(
+                JavaOptional::<Uuid>::from_java(env, JObject::null()).unwrap(),
+                JavaOptional(None)
+            );
+            assert_eq!(
+                JavaOptionalInt::from_java(env, JObject::null()).unwrap(),
+                JavaOptionalInt(None)
+            );
+
+            let obj = JavaOptionalInt(Some(1)).into_java(env).unwrap();
+            assert_exception(
+                env,
+                JavaOptionalLong::from_java(env, obj),
+                "java/lang/ClassCastException",
+            );
+        });
+    }
+
+    #[test]
+    fn test_option() {
+        test_utils::JVM_ENV.with(|env| {
+            roundtrip(env, Some(42i32), "java/lang/Integer");
+            roundtrip(env, Some("hello".to_string()), "java/lang/String");
+
+            let obj = None::<String>.into_java(env).unwrap();
+            assert!(obj.is_null());
+            assert_eq!(Option::<String>::from_java(env, obj).unwrap(), None);
+            assert_eq!(Option::<Uuid>::from_java(env, obj).unwrap(), None);
+
+            assert!(matches!(
+                Uuid::from_java(env, JObject::null()),
+                Err(Error::NullPtr(_))
+            ));
+        });
+    }
+
+    #[test]
+    fn test_wrong_type() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = "not a UUID".into_java(env).unwrap();
+            assert_exception(
+                env,
+                Uuid::from_java(env, obj),
+                "java/lang/ClassCastException",
+            );
+        });
+    }
+
+    #[cfg(feature = "derive")]
+    #[derive(FromJava, IntoJava, Clone, PartialEq, Debug)]
+    #[java(class = "java/awt/Point")]
+    struct Point {
+        x: i32,
+        #[java(name = "y")]
+        vertical: i32,
+        #[java(skip)]
+        label: String,
+    }
+
+    #[cfg(feature = "derive")]
+    #[derive(FromJava, IntoJava, Clone, PartialEq, Debug)]
+    #[java(class = "java/lang/StackTraceElement", constructor)]
+    struct StackFrame {
+        #[java(getter = "getClassName")]
+        declaring_class: String,
+        method_name: String,
+        file_name: Option<String>,
+        line_number: i32,
+    }
+
+    #[cfg(feature = "derive")]
+    #[derive(FromJava, IntoJava, Clone, PartialEq, Debug)]
+    #[java(class = "java/util/concurrent/locks/ReentrantLock", constructor)]
+    struct Lock {
+        fair: bool,
+        #[java(skip)]
+        hold_count: i32,
+    }
+
+    #[cfg(feature = "derive")]
+    #[derive(FromJava, IntoJava)]
+    #[java(class = "jdk/net/UnixDomainPrincipal", record)]
+    struct UnixPrincipal {
+        #[java(sig = "Ljava/nio/file/attribute/UserPrincipal;")]
+        user: ObjectRef,
+        #[java(sig = "Ljava/nio/file/attribute/GroupPrincipal;")]
+        group: ObjectRef,
+    }
+
+    #[cfg(feature = "derive")]
+    struct ObjectRef(jni::objects::GlobalRef);
+
+    #[cfg(feature = "derive")]
+    impl FromJava for ObjectRef {
+        fn from_java<'a: 'b, 'b>(
+            env: &'b JNIEnv<'a>,
+            obj: JObject<'a>,
+        ) -> jni::errors::Result<Self> {
+            Ok(Self(env.new_global_ref(obj)?))
+        }
+    }
+
+    #[cfg(feature = "derive")]
+    impl IntoJava for ObjectRef {
+        fn into_java<'a: 'b, 'b>(self, env: &'b JNIEnv<'a>) -> jni::errors::Result<JObject<'a>> {
+            // The global reference outlives the call, so its raw pointer can be
+            // used to create a new local reference.
+            env.new_local_ref::<JObject>(self.0.as_obj().into_inner().into())
+        }
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_fields() {
+        test_utils::JVM_ENV.with(|env| {
+            let point = Point {
+                x: 3,
+                vertical: -4,
+                label: String::new(),
+            };
+            roundtrip(env, point.clone(), "java/awt/Point");
+
+            let obj = env
+                .new_object("java/awt/Point", "(II)V", &[5.into(), 6.into()])
+                .unwrap();
+            let point = Point::from_java(env, obj).unwrap();
+            assert_eq!(point.x, 5);
+            assert_eq!(point.vertical, 6);
+            assert_eq!(point.label, "");
+
+            let obj = "not a point".into_java(env).unwrap();
+            assert_exception(
+                env,
+                Point::from_java(env, obj),
+                "java/lang/ClassCastException",
+            );
+            assert!(matches!(
+                Point::from_java(env, JObject::null()),
+                Err(Error::NullPtr(_))
+            ));
+        });
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_constructor() {
+        test_utils::JVM_ENV.with(|env| {
+            let frame = StackFrame {
+                declaring_class: "com.example.Main".to_string(),
+                method_name: "main".to_string(),
+                file_name: Some("Main.java".to_string()),
+                line_number: 42,
+            };
+            roundtrip(env, frame.clone(), "java/lang/StackTraceElement");
+
+            let obj = frame.clone().into_java(env).unwrap();
+            let string = env
+                .call_method(obj, "toString", "()Ljava/lang/String;", &[])
+                .unwrap()
+                .l()
+                .unwrap();
+            assert_eq!(
+                String::from_java(env, string).unwrap(),
+                "com.example.Main.main(Main.java:42)"
+            );
+
+            let frame = StackFrame {
+                file_name: None,
+                line_number: -1,
+                ..frame
+            };
+            roundtrip(env, frame, "java/lang/StackTraceElement");
+
+            // Boolean properties are read with an `is` getter.
+            for fair in [true, false] {
+                let lock = Lock {
+                    fair,
+                    hold_count: 0,
+                };
+                roundtrip(env, lock, "java/util/concurrent/locks/ReentrantLock");
+            }
+
+            // Skipped fields aren't passed to the constructor, and are set to
+            // their default value.
+            let lock = Lock {
+                fair: true,
+                hold_count: 3,
+            };
+            let obj = lock.into_java(env).unwrap();
+            assert_eq!(
+                Lock::from_java(env, obj).unwrap(),
+                Lock {
+                    fair: true,
+                    hold_count: 0,
+                }
+            );
+        });
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_record() {
+        fn principal(env: &JNIEnv, name: &'static str) -> jni::objects::GlobalRef {
+            let proxy = crate::ops::fn_proxy(
+                env,
+                &["java/nio/file/attribute/GroupPrincipal"],
+                move |e, _o, method, _descriptor, _args| match method {
+                    "getName" => e.new_string(name).unwrap().into(),
+                    _ => JObject::null(),
+                },
+            )
+            .unwrap();
+            env.new_global_ref(proxy).unwrap()
+        }
+
+        test_utils::JVM_ENV.with(|env| {
+            // Records were added in Java 16.
+            if env.find_class("jdk/net/UnixDomainPrincipal").is_err() {
+                env.exception_clear().unwrap();
+                return;
+            }
+
+            let user = principal(env, "user");
+            let group = principal(env, "group");
+            let value = UnixPrincipal {
+                user: ObjectRef(user.clone()),
+                group: ObjectRef(group.clone()),
+            };
+            let obj = value.into_java(env).unwrap();
+            assert!(env
+                .is_instance_of(obj, "jdk/net/UnixDomainPrincipal")
+                .unwrap());
+            let actual_user = env
+                .call_method(
+                    obj,
+                    "user",
+                    "()Ljava/nio/file/attribute/UserPrincipal;",
+                    &[],
+                )
+                .unwrap()
+                .l()
+                .unwrap();
+            assert!(env.is_same_object(actual_user, user.as_obj()).unwrap());
+
+            let value = UnixPrincipal::from_java(env, obj).unwrap();
+            assert!(env
+                .is_same_object(value.user.0.as_obj(), user.as_obj())
+                .unwrap());
+            assert!(env
+                .is_same_object(value.group.0.as_obj(), group.as_obj())
+                .unwrap());
+        });
+    }
+}
@@ -0,0 +1,312 @@
+use crate::{exceptions::throw_error, primitives::check_instance_of};
+use jni::{
+    errors::{Error, Result},
+    objects::{GlobalRef, JObject},
+    JNIEnv,
+};
+use std::sync::OnceLock;
+
+/// Declare a Rust fieldless enum which mirrors a Java `enum` class, and
+/// implement [`JavaEnum`](crate::enums::JavaEnum),
+/// [`FromJava`](crate::convert::FromJava) and
+/// [`IntoJava`](crate::convert::IntoJava) for it.
+///
+/// Each variant is followed by the name of the corresponding Java enum
+/// constant. The enum must derive [`Clone`] and [`Copy`]. The Java enum
+/// constants are looked up once and cached as [`GlobalRef`](jni::objects::GlobalRef)s.
+/// Conversion from Java goes through the constant's `ordinal()`, so the Rust
+/// variants may be declared in any order and may cover only a subset of the
+/// Java constants.
+///
+/// ```
+/// jni_utils::java_enum! {
+///     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///     pub enum TimeUnit = "java/util/concurrent/TimeUnit" {
+///         Seconds = "SECONDS",
+///         Milliseconds = "MILLISECONDS",
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! java_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident = $class:literal {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $java_name:literal
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+        }
+
+        impl $crate::enums::JavaEnum for $name {
+            const CLASS: &'static str = $class;
+            const VARIANTS: &'static [(Self, &'static str)] = &[
+                $(($name::$variant, $java_name),)*
+            ];
+
+            fn index(self) -> usize {
+                self as usize
+            }
+
+            fn cache() -> &'static $crate::enums::JavaEnumCache {
+                static CACHE: $crate::enums::JavaEnumCache =
+                    $crate::enums::JavaEnumCache::new();
+                &CACHE
+            }
+        }
+
+        impl $crate::convert::FromJava for $name {
+            fn from_java<'a: 'b, 'b>(
+                env: &'b ::jni::JNIEnv<'a>,
+                obj: ::jni::objects::JObject<'a>,
+            ) -> ::jni::errors::Result<Self> {
+                $crate::enums::from_java(env, obj)
+            }
+        }
+
+        impl $crate::convert::IntoJava for $name {
+            fn into_java<'a: 'b, 'b>(
+                self,
+                env: &'b ::jni::JNIEnv<'a>,
+            ) -> ::jni::errors::Result<::jni::objects::JObject<'a>> {
+                $crate::enums::to_java(env, self)
+            }
+        }
+    };
+}
+
+/// Trait for Rust enums which mirror a Java `enum` class. This should be
+/// implemented with the [`java_enum!`](crate::java_enum) macro rather than by
+/// hand.
+pub trait JavaEnum: Copy + 'static {
+    /// Name of the Java enum class, such as
+    /// `"java/util/concurrent/TimeUnit"`.
+    const CLASS: &'static str;
+
+    /// Each Rust variant along with the name of its Java enum constant. The
+    /// variant at position `i` must have an [`index()`](JavaEnum::index) of
+    /// `i`.
+    const VARIANTS: &'static [(Self, &'static str)];
+
+    /// Get the position of this variant in [`VARIANTS`](JavaEnum::VARIANTS).
+    fn index(self) -> usize;
+
+    /// Get the cache of Java enum constants for this enum.
+    #[doc(hidden)]
+    fn cache() -> &'static JavaEnumCache;
+}
+
+/// Cache of Java enum constants for a [`JavaEnum`]. This is an implementation
+/// detail of [`java_enum!`](crate::java_enum).
+#[doc(hidden)]
+pub struct JavaEnumCache(OnceLock<Constants>);
+
+impl JavaEnumCache {
+    pub const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+}
+
+impl Default for JavaEnumCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct Constants {
+    // Indexed by Rust variant.
+    constants: Vec<GlobalRef>,
+    // Indexed by Java ordinal.
+    variants: Vec<Option<usize>>,
+}
+
+/// Look up and cache the Java enum constants for a [`JavaEnum`], checking that
+/// every Rust variant has a corresponding constant on the Java side.
+///
+/// This is done automatically the first time a value is converted, but calling
+/// it ahead of time (for example, right after [`crate::init()`]) surfaces
+/// mismatches between the Rust and Java definitions early. If a constant is
+/// missing, the `java.lang.NoSuchFieldError` is left pending and
+/// [`Error::JavaException`] is returned.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+pub fn init<T: JavaEnum>(env: &JNIEnv) -> Result<()> {
+    constants::<T>(env).map(|_| ())
+}
+
+/// Get the Java enum constant corresponding to a Rust variant.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `value` - Variant to convert.
+pub fn to_java<'a: 'b, 'b, T: JavaEnum>(env: &'b JNIEnv<'a>, value: T) -> Result<JObject<'a>> {
+    let constants = constants::<T>(env)?;
+    env.new_local_ref::<JObject>(constants.constants[value.index()].as_obj())
+}
+
+/// Get the Rust variant corresponding to a Java enum constant.
+///
+/// If the object is not an instance of the enum class, a
+/// `java.lang.ClassCastException` is thrown. If the Java constant has no
+/// corresponding Rust variant, a `java.lang.IllegalArgumentException` is
+/// thrown.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - Enum constant to convert.
+pub fn from_java<'a: 'b, 'b, T: JavaEnum>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<T> {
+    if obj.is_null() {
+        return Err(Error::NullPtr("from_java obj argument"));
+    }
+    let constants = constants::<T>(env)?;
+    check_instance_of(env, obj, T::CLASS)?;
+
+    let ordinal = env.call_method(obj, "ordinal", "()I", &[])?.i()?;
+    match constants.variants.get(ordinal as usize).copied().flatten() {
+        Some(index) => Ok(T::VARIANTS[index].0),
+        None => {
+            let name = env.auto_local(
+                env.call_method(obj, "name", "()Ljava/lang/String;", &[])?
+                    .l()?,
+            );
+            let name: String = env.get_string(name.as_obj().into())?.into();
+            throw_error(
+                env,
+                "java/lang/IllegalArgumentException",
+                format!(
+                    "No Rust variant for {}.{}",
+                    T::CLASS.replace('/', "."),
+                    name
+                ),
+            )
+        }
+    }
+}
+
+fn constants<T: JavaEnum>(env: &JNIEnv) -> Result<&'static Constants> {
+    let cache = &T::cache().0;
+    if let Some(constants) = cache.get() {
+        return Ok(constants);
+    }
+
+    let class = env.auto_local(env.find_class(T::CLASS)?);
+    let sig = format!("L{};", T::CLASS);
+    let mut constants = Vec::with_capacity(T::VARIANTS.len());
+    let mut variants = Vec::new();
+    for (index, (variant, name)) in T::VARIANTS.iter().enumerate() {
+        debug_assert_eq!(variant.index(), index);
+        let constant = env.auto_local(env.get_static_field(&class, *name, &sig)?.l()?);
+        let ordinal = env
+            .call_method(constant.as_obj(), "ordinal", "()I", &[])?
+            .i()? as usize;
+        if variants.len() <= ordinal {
+            variants.resize(ordinal + 1, None);
+        }
+        variants[ordinal] = Some(index);
+        constants.push(env.new_global_ref(constant.as_obj())?);
+    }
+
+    // If another thread got here first, its constants are equivalent.
+    let _ = cache.set(Constants {
+        constants,
+        variants,
+    });
+    Ok(cache.get().unwrap())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::{self, assert_exception};
+    use jni::JNIEnv;
+
+    crate::java_enum! {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        enum TimeUnit = "java/util/concurrent/TimeUnit" {
+            // Deliberately out of order and incomplete.
+            Seconds = "SECONDS",
+            Nanoseconds = "NANOSECONDS",
+            Hours = "HOURS",
+        }
+    }
+
+    crate::java_enum! {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        enum Missing = "java/util/concurrent/TimeUnit" {
+            Seconds = "SECONDS",
+            Fortnights = "FORTNIGHTS",
+        }
+    }
+
+    fn time_unit<'a>(env: &JNIEnv<'a>, name: &str) -> jni::objects::JObject<'a> {
+        env.get_static_field(
+            "java/util/concurrent/TimeUnit",
+            name,
+            "Ljava/util/concurrent/TimeUnit;",
+        )
+        .unwrap()
+        .l()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        test_utils::JVM_ENV.with(|env| {
+            super::init::<TimeUnit>(env).unwrap();
+            for (variant, name) in &[
+                (TimeUnit::Seconds, "SECONDS"),
+                (TimeUnit::Nanoseconds, "NANOSECONDS"),
+                (TimeUnit::Hours, "HOURS"),
+            ] {
+                let obj = super::to_java(env, *variant).unwrap();
+                assert!(env.is_same_object(obj, time_unit(env, name)).unwrap());
+                assert_eq!(super::from_java::<TimeUnit>(env, obj).unwrap(), *variant);
+            }
+        });
+    }
+
+    #[test]
+    fn test_unmapped() {
+        test_utils::JVM_ENV.with(|env| {
+            assert_exception(
+                env,
+                super::from_java::<TimeUnit>(env, time_unit(env, "DAYS")),
+                "java/lang/IllegalArgumentException",
+            );
+
+            let obj = env.new_string("SECONDS").unwrap();
+            assert_exception(
+                env,
+                super::from_java::<TimeUnit>(env, obj.into()),
+                "java/lang/ClassCastException",
+            );
+        });
+    }
+
+    #[test]
+    fn test_missing_constant() {
+        test_utils::JVM_ENV.with(|env| {
+            assert_exception(
+                env,
+                super::init::<Missing>(env),
+                "java/lang/NoSuchFieldError",
+            );
+            assert_exception(
+                env,
+                super::to_java(env, Missing::Seconds),
+                "java/lang/NoSuchFieldError",
+            );
+        });
+    }
+}
@@ -2,6 +2,7 @@ use jni::{
     descriptors::Desc,
     errors::Error,
     objects::{JClass, JObject, JThrowable},
+    strings::JNIString,
     JNIEnv,
 };
 use std::{
@@ -242,11 +243,125 @@ pub fn throw_unwind<'a: 'b, 'b, R>(
     })
 }
 
+/// Throw a new exception of the given class and return
+/// [`Error::JavaException`], for reporting errors detected by Rust code.
+pub(crate) fn throw_error<'a, T>(
+    env: &JNIEnv<'a>,
+    class: impl Desc<'a, JClass<'a>>,
+    msg: impl Into<JNIString>,
+) -> Result<T, Error> {
+    env.throw_new(class, msg)?;
+    Err(Error::JavaException)
+}
+
+/// Trait for Rust errors which can be thrown as Java exceptions. This is used
+/// by closures which return a [`Result`], such as the ones passed to
+/// [`fn_callable()`](crate::ops::fn_callable) and
+/// [`try_fn_function()`](crate::ops::try_fn_function), to report their errors
+/// to Java code.
+///
+/// If a Java exception is already pending when a new exception is thrown, the
+/// pending exception is added to the new one as a suppressed exception.
+pub trait ThrowableError {
+    /// Throw this error as a Java exception.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    fn throw(self, env: &JNIEnv) -> Result<(), Error>;
+}
+
+/// [`Error::JavaException`] leaves the pending exception as it is, or throws a
+/// `java.lang.RuntimeException` if there isn't one. Any other error is thrown
+/// as a `java.lang.RuntimeException` with the error's message.
+impl ThrowableError for Error {
+    fn throw(self, env: &JNIEnv) -> Result<(), Error> {
+        if let Error::JavaException = self {
+            if env.exception_check()? {
+                return Ok(());
+            }
+        }
+        throw_new_suppressing(env, "java/lang/RuntimeException", &self.to_string())
+    }
+}
+
+/// Thrown as a `java.io.IOException` with the error's message.
+impl ThrowableError for std::io::Error {
+    fn throw(self, env: &JNIEnv) -> Result<(), Error> {
+        throw_new_suppressing(env, "java/io/IOException", &self.to_string())
+    }
+}
+
+/// Error which is thrown as a new Java exception of a given class.
+///
+/// The class must have a constructor which takes a single `String` message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThrowNew {
+    /// Internal name of the exception class, such as
+    /// `"java/lang/IllegalStateException"`.
+    pub class: String,
+    /// Message of the exception.
+    pub message: String,
+}
+
+impl ThrowNew {
+    /// Create a new [`ThrowNew`].
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - Internal name of the exception class.
+    /// * `message` - Message of the exception.
+    pub fn new(class: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            class: class.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ThrowNew {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.class.replace('/', "."), self.message)
+    }
+}
+
+impl std::error::Error for ThrowNew {}
+
+impl ThrowableError for ThrowNew {
+    fn throw(self, env: &JNIEnv) -> Result<(), Error> {
+        throw_new_suppressing(env, self.class.as_str(), &self.message)
+    }
+}
+
+fn throw_new_suppressing(env: &JNIEnv, class: &str, msg: &str) -> Result<(), Error> {
+    let old_ex = if env.exception_check()? {
+        let ex = env.exception_occurred()?;
+        env.exception_clear()?;
+        Some(ex)
+    } else {
+        None
+    };
+
+    let msg = env.auto_local(env.new_string(msg)?);
+    let ex: JThrowable = env
+        .new_object(class, "(Ljava/lang/String;)V", &[msg.as_obj().into()])?
+        .into();
+    if let Some(old_ex) = old_ex {
+        env.call_method(
+            ex,
+            "addSuppressed",
+            "(Ljava/lang/Throwable;)V",
+            &[old_ex.into()],
+        )?;
+    }
+    env.throw(ex)
+}
+
 #[cfg(test)]
 mod test {
     use jni::{errors::Error, objects::JThrowable, JNIEnv};
 
-    use super::try_block;
+    use super::{try_block, ThrowNew, ThrowableError};
     use crate::test_utils;
 
     fn test_catch<'a: 'b, 'b>(
@@ -645,4 +760,101 @@ mod test {
             ex.resume_unwind().unwrap();
         });
     }
+
+    fn take_message<'a: 'b, 'b>(env: &'b JNIEnv<'a>, class: &str) -> (JThrowable<'a>, String) {
+        assert!(env.exception_check().unwrap());
+        let ex = env.exception_occurred().unwrap();
+        env.exception_clear().unwrap();
+        assert!(env.is_instance_of(ex, class).unwrap());
+        let msg = env
+            .call_method(ex, "getMessage", "()Ljava/lang/String;", &[])
+            .unwrap()
+            .l()
+            .unwrap();
+        (ex, crate::strings::to_string(env, msg).unwrap())
+    }
+
+    #[test]
+    fn test_throw_new() {
+        test_utils::JVM_ENV.with(|env| {
+            let err = ThrowNew::new("java/lang/IllegalStateException", "Bad state");
+            assert_eq!(
+                err.to_string(),
+                "java.lang.IllegalStateException: Bad state"
+            );
+            err.throw(env).unwrap();
+            let (_, msg) = take_message(env, "java/lang/IllegalStateException");
+            assert_eq!(msg, "Bad state");
+        });
+    }
+
+    #[test]
+    fn test_throw_new_suppress() {
+        test_utils::JVM_ENV.with(|env| {
+            let old_ex: JThrowable = env
+                .new_object("java/lang/Exception", "()V", &[])
+                .unwrap()
+                .into();
+            env.throw(old_ex).unwrap();
+
+            ThrowNew::new("java/lang/IllegalStateException", "Bad state")
+                .throw(env)
+                .unwrap();
+            let (ex, _) = take_message(env, "java/lang/IllegalStateException");
+
+            let suppressed_list = env
+                .call_method(ex, "getSuppressed", "()[Ljava/lang/Throwable;", &[])
+                .unwrap()
+                .l()
+                .unwrap();
+            assert_eq!(
+                env.get_array_length(suppressed_list.into_inner()).unwrap(),
+                1
+            );
+            let suppressed_ex = env
+                .get_object_array_element(suppressed_list.into_inner(), 0)
+                .unwrap();
+            assert!(env.is_same_object(old_ex, suppressed_ex).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_throw_jni_error() {
+        test_utils::JVM_ENV.with(|env| {
+            Error::WrongJValueType("int", "long").throw(env).unwrap();
+            let (_, msg) = take_message(env, "java/lang/RuntimeException");
+            assert_eq!(msg, Error::WrongJValueType("int", "long").to_string());
+        });
+    }
+
+    #[test]
+    fn test_throw_jni_java_exception() {
+        test_utils::JVM_ENV.with(|env| {
+            let old_ex: JThrowable = env
+                .new_object("java/lang/IllegalArgumentException", "()V", &[])
+                .unwrap()
+                .into();
+            env.throw(old_ex).unwrap();
+
+            Error::JavaException.throw(env).unwrap();
+            assert!(env.exception_check().unwrap());
+            let ex = env.exception_occurred().unwrap();
+            env.exception_clear().unwrap();
+            assert!(env.is_same_object(old_ex, ex).unwrap());
+
+            Error::JavaException.throw(env).unwrap();
+            take_message(env, "java/lang/RuntimeException");
+        });
+    }
+
+    #[test]
+    fn test_throw_io_error() {
+        test_utils::JVM_ENV.with(|env| {
+            std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "Read failed")
+                .throw(env)
+                .unwrap();
+            let (_, msg) = take_message(env, "java/io/IOException");
+            assert_eq!(msg, "Read failed");
+        });
+    }
 }
@@ -10,7 +10,7 @@ use std::{
     convert::TryFrom,
     future::Future,
     pin::Pin,
-    task::{Context, Poll},
+    task::{Context, Poll, Waker},
 };
 
 /// Wrapper for [`JObject`]s that implement
@@ -40,11 +40,7 @@ impl<'a: 'b, 'b> JFuture<'a, 'b> {
     pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
         let class = env.auto_local(env.find_class("io/github/gedgygedgy/rust/future/Future")?);
 
-        let poll = env.get_method_id(
-            &class,
-            "poll",
-            "(Lio/github/gedgygedgy/rust/task/Waker;)Lio/github/gedgygedgy/rust/task/PollResult;",
-        )?;
+        let poll = env.get_method_id(&class, "poll", crate::sig!((Waker) -> JPollResult))?;
         Ok(Self {
             internal: obj,
             poll,
@@ -0,0 +1,1229 @@
+use crate::exceptions::try_block;
+use futures::io::{AsyncRead, AsyncWrite};
+use jni::{
+    errors::{Error, Result},
+    objects::{AutoLocal, GlobalRef, JMethodID, JObject, JString, JThrowable, JValue},
+    signature::{JavaType, Primitive},
+    sys::{jbyte, jint, jmethodID},
+    JNIEnv, JavaVM,
+};
+use static_assertions::assert_impl_all;
+use std::{
+    convert::TryFrom,
+    io::{self, ErrorKind, Read, Write},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+const BUFFER_SIZE: usize = 8192;
+
+/// Wrapper for [`JObject`]s that extend `java.io.InputStream`. Implements
+/// [`Read`] to allow Rust code to read from Java streams.
+///
+/// Data is read into a Java `byte[]` which is allocated once when the wrapper
+/// is created and reused for every call to [`Read::read`]. A single call will
+/// read at most 8 KiB, regardless of how large the destination buffer is.
+///
+/// `java.io.IOException`s thrown by the stream are caught and converted into
+/// [`io::Error`]s. Any other error, including other Java exceptions (which are
+/// left pending), is returned as an [`io::Error`] of kind
+/// [`ErrorKind::Other`] wrapping the original [`Error`].
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JInputStream<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    read: JMethodID<'a>,
+    close: JMethodID<'a>,
+    buffer: AutoLocal<'a, 'b>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JInputStream<'a, 'b> {
+    /// Create a [`JInputStream`] from the environment and an object. This
+    /// looks up the necessary class and method IDs to call all of the methods
+    /// on it so that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/io/InputStream")?);
+
+        let read = env.get_method_id(&class, "read", "([BII)I")?;
+        let close = env.get_method_id(&class, "close", "()V")?;
+        let buffer = env.auto_local(env.new_byte_array(BUFFER_SIZE as jint)?);
+        Ok(Self {
+            internal: obj,
+            read,
+            close,
+            buffer,
+            env,
+        })
+    }
+
+    /// Close the stream by calling `java.io.InputStream.close()`.
+    pub fn close(&self) -> io::Result<()> {
+        java_io_result(self.env, || {
+            self.env
+                .call_method_unchecked(
+                    self.internal,
+                    self.close,
+                    JavaType::Primitive(Primitive::Void),
+                    &[],
+                )?
+                .v()
+        })
+    }
+
+    fn read_internal(&self, buf: &mut [u8]) -> Result<usize> {
+        let len = buf.len().min(BUFFER_SIZE);
+        let result = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.read,
+                JavaType::Primitive(Primitive::Int),
+                &[self.buffer.as_obj().into(), 0.into(), (len as jint).into()],
+            )?
+            .i()?;
+
+        // A negative result means that the end of the stream has been reached.
+        if result < 0 {
+            return Ok(0);
+        }
+        let result = result as usize;
+        let slice = unsafe { &mut *(&mut buf[..result] as *mut [u8] as *mut [jbyte]) };
+        self.env
+            .get_byte_array_region(self.buffer.as_obj().into_inner(), 0, slice)?;
+        Ok(result)
+    }
+}
+
+impl<'a: 'b, 'b> Read for JInputStream<'a, 'b> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        java_io_result(self.env, || self.read_internal(buf))
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JInputStream<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JInputStream<'a, 'b>> for JObject<'a> {
+    fn from(other: JInputStream<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+/// Wrapper for [`JObject`]s that extend `java.io.OutputStream`. Implements
+/// [`Write`] to allow Rust code to write to Java streams.
+///
+/// Data is copied into a Java `byte[]` which is allocated once when the
+/// wrapper is created and reused for every call to [`Write::write`]. A single
+/// call will write at most 8 KiB, regardless of how large the source buffer
+/// is.
+///
+/// Errors are converted in the same way as for [`JInputStream`].
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JOutputStream<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    write: JMethodID<'a>,
+    flush: JMethodID<'a>,
+    close: JMethodID<'a>,
+    buffer: AutoLocal<'a, 'b>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JOutputStream<'a, 'b> {
+    /// Create a [`JOutputStream`] from the environment and an object. This
+    /// looks up the necessary class and method IDs to call all of the methods
+    /// on it so that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/io/OutputStream")?);
+
+        let write = env.get_method_id(&class, "write", "([BII)V")?;
+        let flush = env.get_method_id(&class, "flush", "()V")?;
+        let close = env.get_method_id(&class, "close", "()V")?;
+        let buffer = env.auto_local(env.new_byte_array(BUFFER_SIZE as jint)?);
+        Ok(Self {
+            internal: obj,
+            write,
+            flush,
+            close,
+            buffer,
+            env,
+        })
+    }
+
+    /// Close the stream by calling `java.io.OutputStream.close()`.
+    pub fn close(&self) -> io::Result<()> {
+        java_io_result(self.env, || self.call_void(self.close, &[]))
+    }
+
+    fn call_void(&self, method: JMethodID<'a>, args: &[JValue]) -> Result<()> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                method,
+                JavaType::Primitive(Primitive::Void),
+                args,
+            )?
+            .v()
+    }
+
+    fn write_internal(&self, buf: &[u8]) -> Result<usize> {
+        let len = buf.len().min(BUFFER_SIZE);
+        let slice = unsafe { &*(&buf[..len] as *const [u8] as *const [jbyte]) };
+        self.env
+            .set_byte_array_region(self.buffer.as_obj().into_inner(), 0, slice)?;
+        self.call_void(
+            self.write,
+            &[self.buffer.as_obj().into(), 0.into(), (len as jint).into()],
+        )?;
+        Ok(len)
+    }
+}
+
+impl<'a: 'b, 'b> Write for JOutputStream<'a, 'b> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        java_io_result(self.env, || self.write_internal(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        java_io_result(self.env, || self.call_void(self.flush, &[]))
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JOutputStream<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JOutputStream<'a, 'b>> for JObject<'a> {
+    fn from(other: JOutputStream<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+/// Wrap a [`Read`] in a Java `java.io.InputStream`. Each call to one of the
+/// stream's `read()` methods calls [`Read::read`] once, and any [`io::Error`]
+/// returned by the reader is thrown as a `java.io.IOException`.
+///
+/// Calling the stream's `close()` method drops the reader. The stream is
+/// implemented on top of an `io.github.gedgygedgy.rust.ops.FnBiFunction`, so
+/// no additional native methods are needed.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the stream.
+/// * `read` - Reader to wrap in a Java object.
+pub fn input_stream<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    mut read: impl Read + Send + 'static,
+) -> Result<JObject<'a>> {
+    let mut buffer = Vec::new();
+    let read_fn = env.auto_local(crate::ops::fn_mut_bi_function(
+        env,
+        move |env, _obj, array, len| {
+            let result = read_into_array(env, &mut read, &mut buffer, array, len);
+            throw_io_result(env, result)
+        },
+    )?);
+
+    let class = env.auto_local(env.find_class("io/github/gedgygedgy/rust/io/ReadInputStream")?);
+    env.new_object(
+        &class,
+        "(Lio/github/gedgygedgy/rust/ops/FnBiFunction;)V",
+        &[(&read_fn).into()],
+    )
+}
+
+/// Wrap a [`Write`] in a Java `java.io.OutputStream`. Each call to one of the
+/// stream's `write()` methods calls [`Write::write_all`] once, and each call
+/// to its `flush()` method calls [`Write::flush`]. Any [`io::Error`] returned
+/// by the writer is thrown as a `java.io.IOException`.
+///
+/// Calling the stream's `close()` method flushes and then drops the writer.
+/// The stream is implemented on top of
+/// `io.github.gedgygedgy.rust.ops.FnBiFunction` and
+/// `io.github.gedgygedgy.rust.ops.FnRunnable`, so no additional native methods
+/// are needed.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the stream.
+/// * `write` - Writer to wrap in a Java object.
+pub fn output_stream<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    write: impl Write + Send + 'static,
+) -> Result<JObject<'a>> {
+    let write = Arc::new(Mutex::new(write));
+    let write_clone = write.clone();
+
+    let write_fn = env.auto_local(crate::ops::fn_bi_function(
+        env,
+        move |env, _obj, array, len| {
+            let mut guard = write.lock().unwrap();
+            let result = write_from_array(env, &mut *guard, array, len);
+            throw_io_result(env, result)
+        },
+    )?);
+    let flush_fn = env.auto_local(crate::ops::fn_runnable(env, move |env, _obj| {
+        let result = write_clone.lock().unwrap().flush();
+        if let Err(err) = result {
+            let _ = throw_io_error(env, err);
+        }
+    })?);
+
+    let class = env.auto_local(env.find_class("io/github/gedgygedgy/rust/io/WriteOutputStream")?);
+    env.new_object(
+        &class,
+        "(Lio/github/gedgygedgy/rust/ops/FnBiFunction;Lio/github/gedgygedgy/rust/ops/FnRunnable;)V",
+        &[(&write_fn).into(), (&flush_fn).into()],
+    )
+}
+
+fn read_into_array<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    read: &mut impl Read,
+    buffer: &mut Vec<u8>,
+    array: JObject<'a>,
+    len: JObject<'a>,
+) -> Result<JObject<'a>> {
+    let len = env.call_method(len, "intValue", "()I", &[])?.i()? as usize;
+    if buffer.len() < len {
+        buffer.resize(len, 0);
+    }
+
+    let result = loop {
+        match read.read(&mut buffer[..len]) {
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            result => break result,
+        }
+    };
+    let count = match result {
+        Ok(count) => count,
+        Err(err) => {
+            throw_io_error(env, err)?;
+            return Err(Error::JavaException);
+        }
+    };
+
+    let slice = unsafe { &*(&buffer[..count] as *const [u8] as *const [jbyte]) };
+    env.set_byte_array_region(array.into_inner(), 0, slice)?;
+    env.call_static_method(
+        "java/lang/Integer",
+        "valueOf",
+        "(I)Ljava/lang/Integer;",
+        &[(count as jint).into()],
+    )?
+    .l()
+}
+
+fn write_from_array<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    write: &mut impl Write,
+    array: JObject<'a>,
+    len: JObject<'a>,
+) -> Result<JObject<'a>> {
+    let len = env.call_method(len, "intValue", "()I", &[])?.i()? as usize;
+    let mut buffer = vec![0; len];
+    env.get_byte_array_region(array.into_inner(), 0, &mut buffer)?;
+    let slice = unsafe { &*(&buffer[..] as *const [jbyte] as *const [u8]) };
+
+    if let Err(err) = write.write_all(slice) {
+        throw_io_error(env, err)?;
+        return Err(Error::JavaException);
+    }
+    Ok(JObject::null())
+}
+
+fn throw_io_error<'a: 'b, 'b>(env: &'b JNIEnv<'a>, err: io::Error) -> Result<()> {
+    env.throw_new("java/io/IOException", err.to_string())
+}
+
+// Convert the result of a closure backing a Java stream into the object it
+// should return, making sure that an exception is pending if it failed.
+fn throw_io_result<'a: 'b, 'b>(env: &'b JNIEnv<'a>, result: Result<JObject<'a>>) -> JObject<'a> {
+    result.unwrap_or_else(|err| {
+        if let Ok(false) = env.exception_check() {
+            let _ = env.throw_new("java/io/IOException", err.to_string());
+        }
+        JObject::null()
+    })
+}
+
+/// Wrapper for [`JObject`]s that implement
+/// `java.nio.channels.AsynchronousByteChannel`, such as
+/// `java.nio.channels.AsynchronousSocketChannel`. Implements [`AsyncRead`] and
+/// [`AsyncWrite`] to allow asynchronous Rust code to read from and write to
+/// Java channels.
+///
+/// Each read or write operation is started with an
+/// `io.github.gedgygedgy.rust.future.CompletionHandlerFuture` as its
+/// completion handler, which is then polled like a
+/// [`JFuture`](crate::future::JFuture). A single operation will transfer at
+/// most 8 KiB. Errors are converted in the same way as for [`JInputStream`],
+/// and exceptions that cause an operation to fail are converted into
+/// [`io::Error`]s regardless of their type.
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+///
+/// For a [`Send`] version of this, use [`JSendAsyncByteChannel`].
+pub struct JAsyncByteChannel<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    read: JMethodID<'a>,
+    write: JMethodID<'a>,
+    close: JMethodID<'a>,
+    env: &'b JNIEnv<'a>,
+    state: AsyncChannelState,
+}
+
+#[derive(Default)]
+struct AsyncChannelState {
+    read: Option<(GlobalRef, GlobalRef)>,
+    read_data: Vec<u8>,
+    read_pos: usize,
+    write: Option<GlobalRef>,
+}
+
+impl<'a: 'b, 'b> JAsyncByteChannel<'a, 'b> {
+    /// Create a [`JAsyncByteChannel`] from the environment and an object.
+    /// This looks up the necessary class and method IDs to call all of the
+    /// methods on it so that extra work doesn't need to be done on every
+    /// method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/nio/channels/AsynchronousByteChannel")?);
+
+        let read = env.get_method_id(
+            &class,
+            "read",
+            "(Ljava/nio/ByteBuffer;Ljava/lang/Object;Ljava/nio/channels/CompletionHandler;)V",
+        )?;
+        let write = env.get_method_id(
+            &class,
+            "write",
+            "(Ljava/nio/ByteBuffer;Ljava/lang/Object;Ljava/nio/channels/CompletionHandler;)V",
+        )?;
+        let close = env.get_method_id(&class, "close", "()V")?;
+        Ok(Self {
+            internal: obj,
+            read,
+            write,
+            close,
+            env,
+            state: Default::default(),
+        })
+    }
+
+    fn start_operation(
+        &self,
+        method: JMethodID<'a>,
+        byte_buffer: JObject<'a>,
+    ) -> io::Result<GlobalRef> {
+        java_io_result(self.env, || {
+            let future = self.env.auto_local(self.env.new_object(
+                "io/github/gedgygedgy/rust/future/CompletionHandlerFuture",
+                "()V",
+                &[],
+            )?);
+            self.env
+                .call_method_unchecked(
+                    self.internal,
+                    method,
+                    JavaType::Primitive(Primitive::Void),
+                    &[
+                        byte_buffer.into(),
+                        JObject::null().into(),
+                        future.as_obj().into(),
+                    ],
+                )?
+                .v()?;
+            self.env.new_global_ref(future.as_obj())
+        })
+    }
+
+    // Poll a pending operation and return the number of bytes transferred.
+    fn poll_operation(
+        &self,
+        context: &mut Context<'_>,
+        future: &GlobalRef,
+    ) -> io::Result<Poll<i32>> {
+        use crate::{future::JFuture, task::waker};
+
+        let env = self.env;
+        try_block(env, || {
+            let waker = env.auto_local(waker(env, context.waker().clone())?);
+            let future = JFuture::from_env(env, future.as_obj())?;
+            let result = future.poll(waker.as_obj())?;
+            let _auto_local = env.auto_local(*result);
+            if env.is_same_object(*result, JObject::null())? {
+                return Ok(Ok(Poll::Pending));
+            }
+            let count = env.auto_local(result.get()?);
+            Ok(Ok(Poll::Ready(
+                env.call_method(count.as_obj(), "intValue", "()I", &[])?
+                    .i()?,
+            )))
+        })
+        .catch("io/github/gedgygedgy/rust/future/FutureException", |ex| {
+            let cause = env
+                .call_method(ex, "getCause", "()Ljava/lang/Throwable;", &[])?
+                .l()?;
+            let _auto_local = env.auto_local(cause);
+            Ok(Err(io_error_from_exception(env, cause.into())?))
+        })
+        .result()
+        .unwrap_or_else(|err| Err(io::Error::other(err)))
+    }
+
+    fn poll_read_internal(
+        &self,
+        state: &mut AsyncChannelState,
+        context: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> io::Result<Poll<usize>> {
+        if state.read_pos < state.read_data.len() {
+            let count = buf.len().min(state.read_data.len() - state.read_pos);
+            buf[..count].copy_from_slice(&state.read_data[state.read_pos..state.read_pos + count]);
+            state.read_pos += count;
+            return Ok(Poll::Ready(count));
+        }
+        if buf.is_empty() {
+            return Ok(Poll::Ready(0));
+        }
+
+        let (future, byte_buffer) = match state.read.take() {
+            Some(read) => read,
+            None => {
+                let byte_buffer = java_io_result(self.env, || {
+                    let byte_buffer = self.env.auto_local(
+                        self.env
+                            .call_static_method(
+                                "java/nio/ByteBuffer",
+                                "allocate",
+                                "(I)Ljava/nio/ByteBuffer;",
+                                &[(buf.len().min(BUFFER_SIZE) as jint).into()],
+                            )?
+                            .l()?,
+                    );
+                    self.env.new_global_ref(byte_buffer.as_obj())
+                })?;
+                (
+                    self.start_operation(self.read, byte_buffer.as_obj())?,
+                    byte_buffer,
+                )
+            }
+        };
+
+        let count = match self.poll_operation(context, &future)? {
+            Poll::Ready(count) => count,
+            Poll::Pending => {
+                state.read = Some((future, byte_buffer));
+                return Ok(Poll::Pending);
+            }
+        };
+        // A negative result means that the end of the stream has been reached.
+        if count < 0 {
+            return Ok(Poll::Ready(0));
+        }
+
+        state.read_data.resize(count as usize, 0);
+        state.read_pos = 0;
+        java_io_result(self.env, || {
+            let array = self.env.auto_local(
+                self.env
+                    .call_method(byte_buffer.as_obj(), "array", "()[B", &[])?
+                    .l()?,
+            );
+            let slice = unsafe { &mut *(&mut state.read_data[..] as *mut [u8] as *mut [jbyte]) };
+            self.env
+                .get_byte_array_region(array.as_obj().into_inner(), 0, slice)
+        })?;
+        self.poll_read_internal(state, context, buf)
+    }
+
+    fn poll_write_internal(
+        &self,
+        state: &mut AsyncChannelState,
+        context: &mut Context<'_>,
+        buf: &[u8],
+    ) -> io::Result<Poll<usize>> {
+        let future = match state.write.take() {
+            Some(future) => future,
+            None => {
+                if buf.is_empty() {
+                    return Ok(Poll::Ready(0));
+                }
+                let byte_buffer = java_io_result(self.env, || {
+                    let array = self.env.auto_local(crate::arrays::slice_to_byte_array(
+                        self.env,
+                        &buf[..buf.len().min(BUFFER_SIZE)],
+                    )?);
+                    Ok(self.env.auto_local(
+                        self.env
+                            .call_static_method(
+                                "java/nio/ByteBuffer",
+                                "wrap",
+                                "([B)Ljava/nio/ByteBuffer;",
+                                &[array.as_obj().into()],
+                            )?
+                            .l()?,
+                    ))
+                })?;
+                self.start_operation(self.write, byte_buffer.as_obj())?
+            }
+        };
+
+        match self.poll_operation(context, &future)? {
+            Poll::Ready(count) => Ok(Poll::Ready(count as usize)),
+            Poll::Pending => {
+                state.write = Some(future);
+                Ok(Poll::Pending)
+            }
+        }
+    }
+
+    fn close_internal(&self) -> io::Result<()> {
+        java_io_result(self.env, || {
+            self.env
+                .call_method_unchecked(
+                    self.internal,
+                    self.close,
+                    JavaType::Primitive(Primitive::Void),
+                    &[],
+                )?
+                .v()
+        })
+    }
+}
+
+impl<'a: 'b, 'b> AsyncRead for JAsyncByteChannel<'a, 'b> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        context: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let mut state = std::mem::take(&mut this.state);
+        let result = this.poll_read_internal(&mut state, context, buf);
+        this.state = state;
+        transpose_poll(result)
+    }
+}
+
+impl<'a: 'b, 'b> AsyncWrite for JAsyncByteChannel<'a, 'b> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        context: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let mut state = std::mem::take(&mut this.state);
+        let result = this.poll_write_internal(&mut state, context, buf);
+        this.state = state;
+        transpose_poll(result)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _context: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _context: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.close_internal())
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JAsyncByteChannel<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JAsyncByteChannel<'a, 'b>> for JObject<'a> {
+    fn from(other: JAsyncByteChannel<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+/// [`Send`] version of [`JAsyncByteChannel`]. Instead of storing a
+/// [`JNIEnv`], it stores a [`JavaVM`] and calls [`JavaVM::get_env`] when it is
+/// polled. The method IDs are looked up once on creation, like
+/// [`JAsyncByteChannel`].
+pub struct JSendAsyncByteChannel {
+    internal: GlobalRef,
+    // Keeps the class loaded so that the method IDs remain valid.
+    _class: GlobalRef,
+    // Raw method IDs are stored as integers so that the channel is Send. Method
+    // IDs are valid on any thread.
+    read: usize,
+    write: usize,
+    close: usize,
+    vm: JavaVM,
+    state: AsyncChannelState,
+}
+
+impl<'a: 'b, 'b> TryFrom<JAsyncByteChannel<'a, 'b>> for JSendAsyncByteChannel {
+    type Error = Error;
+
+    fn try_from(channel: JAsyncByteChannel<'a, 'b>) -> Result<Self> {
+        let env = channel.env;
+        let class = env.auto_local(env.find_class("java/nio/channels/AsynchronousByteChannel")?);
+        Ok(Self {
+            internal: env.new_global_ref(channel.internal)?,
+            _class: env.new_global_ref(class.as_obj())?,
+            read: channel.read.into_inner() as usize,
+            write: channel.write.into_inner() as usize,
+            close: channel.close.into_inner() as usize,
+            vm: env.get_java_vm()?,
+            state: channel.state,
+        })
+    }
+}
+
+impl ::std::ops::Deref for JSendAsyncByteChannel {
+    type Target = GlobalRef;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl JSendAsyncByteChannel {
+    fn with_channel<T>(
+        &mut self,
+        f: impl for<'a, 'b> FnOnce(&JAsyncByteChannel<'a, 'b>, &mut AsyncChannelState) -> io::Result<T>,
+    ) -> io::Result<T> {
+        let env = self.vm.get_env().map_err(io::Error::other)?;
+        let channel = JAsyncByteChannel {
+            internal: self.internal.as_obj(),
+            read: JMethodID::from(self.read as jmethodID),
+            write: JMethodID::from(self.write as jmethodID),
+            close: JMethodID::from(self.close as jmethodID),
+            env: &env,
+            state: Default::default(),
+        };
+        f(&channel, &mut self.state)
+    }
+}
+
+impl AsyncRead for JSendAsyncByteChannel {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        context: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        transpose_poll(
+            self.get_mut()
+                .with_channel(|channel, state| channel.poll_read_internal(state, context, buf)),
+        )
+    }
+}
+
+impl AsyncWrite for JSendAsyncByteChannel {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        context: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        transpose_poll(
+            self.get_mut()
+                .with_channel(|channel, state| channel.poll_write_internal(state, context, buf)),
+        )
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _context: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _context: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(
+            self.get_mut()
+                .with_channel(|channel, _state| channel.close_internal()),
+        )
+    }
+}
+
+assert_impl_all!(JSendAsyncByteChannel: Send);
+
+fn transpose_poll<T>(result: io::Result<Poll<T>>) -> Poll<io::Result<T>> {
+    match result {
+        Ok(Poll::Ready(result)) => Poll::Ready(Ok(result)),
+        Ok(Poll::Pending) => Poll::Pending,
+        Err(err) => Poll::Ready(Err(err)),
+    }
+}
+
+// Run a block of JNI code, catching any `java.io.IOException` it throws and
+// converting it into an `io::Error`.
+fn java_io_result<'a: 'b, 'b, T>(
+    env: &'b JNIEnv<'a>,
+    block: impl FnOnce() -> Result<T>,
+) -> io::Result<T> {
+    try_block(env, || block().map(Ok))
+        .catch("java/io/IOException", |ex| {
+            Ok(Err(io_error_from_exception(env, ex)?))
+        })
+        .result()
+        .unwrap_or_else(|err| Err(io::Error::other(err)))
+}
+
+fn io_error_from_exception<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    ex: JThrowable<'a>,
+) -> Result<io::Error> {
+    let kind = if env.is_instance_of(ex, "java/io/FileNotFoundException")? {
+        ErrorKind::NotFound
+    } else if env.is_instance_of(ex, "java/io/EOFException")? {
+        ErrorKind::UnexpectedEof
+    } else if env.is_instance_of(ex, "java/io/InterruptedIOException")? {
+        ErrorKind::Interrupted
+    } else {
+        ErrorKind::Other
+    };
+
+    let msg: JString = env
+        .call_method(ex, "toString", "()Ljava/lang/String;", &[])?
+        .l()?
+        .into();
+    let _auto_local = env.auto_local(msg);
+    let msg: String = env.get_string(msg)?.into();
+    Ok(io::Error::new(kind, msg))
+}
+
+/// Extract the [`Error`] from an [`io::Error`] returned by [`JInputStream`] or
+/// [`JOutputStream`], if the error was caused by something other than a
+/// `java.io.IOException`.
+///
+/// # Arguments
+///
+/// * `err` - Error to inspect.
+pub fn jni_error(err: &io::Error) -> Option<&Error> {
+    err.get_ref().and_then(|e| e.downcast_ref::<Error>())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{JInputStream, JOutputStream};
+    use crate::{exceptions::try_block, test_utils};
+    use jni::{objects::JObject, JNIEnv};
+    use std::{
+        io::{self, Cursor, ErrorKind, Read, Write},
+        sync::{Arc, Mutex},
+    };
+
+    #[test]
+    fn test_input_stream_read() {
+        test_utils::JVM_ENV.with(|env| {
+            let data: Vec<u8> = (0..20000).map(|i| (i % 251) as u8).collect();
+            let array = crate::arrays::slice_to_byte_array(env, &data).unwrap();
+            let obj = env
+                .new_object("java/io/ByteArrayInputStream", "([B)V", &[array.into()])
+                .unwrap();
+            let mut stream = JInputStream::from_env(env, obj).unwrap();
+
+            let mut small = [0u8; 10];
+            assert_eq!(stream.read(&mut small).unwrap(), 10);
+            assert_eq!(small, data[..10]);
+
+            let mut rest = Vec::new();
+            stream.read_to_end(&mut rest).unwrap();
+            assert_eq!(rest, data[10..]);
+
+            assert_eq!(stream.read(&mut small).unwrap(), 0);
+            stream.close().unwrap();
+        });
+    }
+
+    #[test]
+    fn test_input_stream_io_exception() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = env
+                .new_object("java/io/PipedInputStream", "()V", &[])
+                .unwrap();
+            let mut stream = JInputStream::from_env(env, obj).unwrap();
+
+            let mut buf = [0u8; 10];
+            let err = stream.read(&mut buf).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::Other);
+            assert!(super::jni_error(&err).is_none());
+            assert!(err.to_string().starts_with("java.io.IOException"));
+            assert!(!env.exception_check().unwrap());
+        });
+    }
+
+    #[test]
+    fn test_output_stream_write() {
+        test_utils::JVM_ENV.with(|env| {
+            let data: Vec<u8> = (0..20000).map(|i| (i % 251) as u8).collect();
+            let obj = env
+                .new_object("java/io/ByteArrayOutputStream", "()V", &[])
+                .unwrap();
+            let mut stream = JOutputStream::from_env(env, obj).unwrap();
+
+            stream.write_all(&data).unwrap();
+            stream.flush().unwrap();
+            stream.close().unwrap();
+
+            let array = env
+                .call_method(obj, "toByteArray", "()[B", &[])
+                .unwrap()
+                .l()
+                .unwrap();
+            let actual = crate::arrays::byte_array_to_vec(env, array.into_inner()).unwrap();
+            assert_eq!(actual, data);
+        });
+    }
+
+    #[test]
+    fn test_output_stream_io_exception() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = env
+                .new_object("java/io/PipedOutputStream", "()V", &[])
+                .unwrap();
+            let mut stream = JOutputStream::from_env(env, obj).unwrap();
+
+            let err = stream.write(&[1, 2, 3]).unwrap_err();
+            assert!(super::jni_error(&err).is_none());
+            assert!(err.to_string().starts_with("java.io.IOException"));
+            assert!(!env.exception_check().unwrap());
+        });
+    }
+
+    #[test]
+    fn test_input_stream_roundtrip() {
+        test_utils::JVM_ENV.with(|env| {
+            let data: Vec<u8> = (0..20000).map(|i| (i % 251) as u8).collect();
+            let obj = super::input_stream(env, Cursor::new(data.clone())).unwrap();
+
+            let array = env.new_byte_array(10).unwrap();
+            let count = env
+                .call_method(obj, "read", "([BII)I", &[array.into(), 3.into(), 7.into()])
+                .unwrap()
+                .i()
+                .unwrap();
+            assert_eq!(count, 7);
+            let mut bytes = [0i8; 10];
+            env.get_byte_array_region(array, 0, &mut bytes).unwrap();
+            assert_eq!(bytes, [0, 0, 0, 0, 1, 2, 3, 4, 5, 6]);
+
+            let mut stream = JInputStream::from_env(env, obj).unwrap();
+            let mut rest = Vec::new();
+            stream.read_to_end(&mut rest).unwrap();
+            assert_eq!(rest, data[7..]);
+
+            let value = env
+                .call_method(obj, "read", "()I", &[])
+                .unwrap()
+                .i()
+                .unwrap();
+            assert_eq!(value, -1);
+        });
+    }
+
+    #[test]
+    fn test_input_stream_close() {
+        test_utils::JVM_ENV.with(|env| {
+            let data = Arc::new(Vec::new());
+            let obj = super::input_stream(env, Cursor::new(ArcBytes(data.clone()))).unwrap();
+            assert_eq!(Arc::strong_count(&data), 2);
+
+            env.call_method(obj, "close", "()V", &[]).unwrap();
+            assert_eq!(Arc::strong_count(&data), 1);
+
+            let result = try_block(env, || {
+                env.call_method(obj, "read", "()I", &[])?;
+                Ok(false)
+            })
+            .catch("java/io/IOException", |_ex| Ok(true))
+            .result()
+            .unwrap();
+            assert!(result);
+        });
+    }
+
+    #[test]
+    fn test_input_stream_error() {
+        struct ErrorRead;
+
+        impl Read for ErrorRead {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(ErrorKind::InvalidData, "This is an error"))
+            }
+        }
+
+        test_utils::JVM_ENV.with(|env| {
+            let obj = super::input_stream(env, ErrorRead).unwrap();
+            let mut stream = JInputStream::from_env(env, obj).unwrap();
+
+            let mut buf = [0u8; 10];
+            let err = stream.read(&mut buf).unwrap_err();
+            assert_eq!(err.to_string(), "java.io.IOException: This is an error");
+            assert!(!env.exception_check().unwrap());
+        });
+    }
+
+    #[test]
+    fn test_output_stream_roundtrip() {
+        test_utils::JVM_ENV.with(|env| {
+            let data: Vec<u8> = (0..20000).map(|i| (i % 251) as u8).collect();
+            let output = SharedWrite::default();
+            let obj = super::output_stream(env, output.clone()).unwrap();
+
+            let array = crate::arrays::slice_to_byte_array(env, &[9, 8, 7, 6, 5]).unwrap();
+            env.call_method(obj, "write", "([BII)V", &[array.into(), 2.into(), 3.into()])
+                .unwrap();
+            env.call_method(obj, "write", "(I)V", &[4.into()]).unwrap();
+
+            let mut stream = JOutputStream::from_env(env, obj).unwrap();
+            stream.write_all(&data).unwrap();
+            assert_eq!(output.flushes(), 0);
+            stream.flush().unwrap();
+            assert_eq!(output.flushes(), 1);
+
+            let mut expected = vec![7, 6, 5, 4];
+            expected.extend_from_slice(&data);
+            assert_eq!(output.data(), expected);
+
+            assert_eq!(Arc::strong_count(&output.0), 2);
+            stream.close().unwrap();
+            assert_eq!(output.flushes(), 2);
+            assert_eq!(Arc::strong_count(&output.0), 1);
+        });
+    }
+
+    #[test]
+    fn test_output_stream_error() {
+        struct ErrorWrite;
+
+        impl Write for ErrorWrite {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(ErrorKind::InvalidData, "This is an error"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    "This is a flush error",
+                ))
+            }
+        }
+
+        test_utils::JVM_ENV.with(|env| {
+            let obj = super::output_stream(env, ErrorWrite).unwrap();
+            let mut stream = JOutputStream::from_env(env, obj).unwrap();
+
+            let err = stream.write(&[1, 2, 3]).unwrap_err();
+            assert_eq!(err.to_string(), "java.io.IOException: This is an error");
+            let err = stream.flush().unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "java.io.IOException: This is a flush error"
+            );
+            assert!(!env.exception_check().unwrap());
+        });
+    }
+
+    fn socket_pair<'a: 'b, 'b>(env: &'b JNIEnv<'a>) -> (JObject<'a>, JObject<'a>) {
+        let server = env
+            .call_static_method(
+                "java/nio/channels/AsynchronousServerSocketChannel",
+                "open",
+                "()Ljava/nio/channels/AsynchronousServerSocketChannel;",
+                &[],
+            )
+            .unwrap()
+            .l()
+            .unwrap();
+        let host = env.new_string("127.0.0.1").unwrap();
+        let addr = env
+            .new_object(
+                "java/net/InetSocketAddress",
+                "(Ljava/lang/String;I)V",
+                &[host.into(), 0.into()],
+            )
+            .unwrap();
+        env.call_method(
+            server,
+            "bind",
+            "(Ljava/net/SocketAddress;)Ljava/nio/channels/AsynchronousServerSocketChannel;",
+            &[addr.into()],
+        )
+        .unwrap();
+        let addr = env
+            .call_method(server, "getLocalAddress", "()Ljava/net/SocketAddress;", &[])
+            .unwrap()
+            .l()
+            .unwrap();
+
+        let client = env
+            .call_static_method(
+                "java/nio/channels/AsynchronousSocketChannel",
+                "open",
+                "()Ljava/nio/channels/AsynchronousSocketChannel;",
+                &[],
+            )
+            .unwrap()
+            .l()
+            .unwrap();
+        let connect = env
+            .call_method(
+                client,
+                "connect",
+                "(Ljava/net/SocketAddress;)Ljava/util/concurrent/Future;",
+                &[addr.into()],
+            )
+            .unwrap()
+            .l()
+            .unwrap();
+        let accept = env
+            .call_method(server, "accept", "()Ljava/util/concurrent/Future;", &[])
+            .unwrap()
+            .l()
+            .unwrap();
+        let accepted = env
+            .call_method(accept, "get", "()Ljava/lang/Object;", &[])
+            .unwrap()
+            .l()
+            .unwrap();
+        env.call_method(connect, "get", "()Ljava/lang/Object;", &[])
+            .unwrap();
+        env.call_method(server, "close", "()V", &[]).unwrap();
+
+        (client, accepted)
+    }
+
+    #[test]
+    fn test_async_byte_channel() {
+        use super::JAsyncByteChannel;
+        use futures::{executor::block_on, join, AsyncReadExt, AsyncWriteExt};
+
+        test_utils::JVM_ENV.with(|env| {
+            let (client, accepted) = socket_pair(env);
+            let mut client = JAsyncByteChannel::from_env(env, client).unwrap();
+            let mut accepted = JAsyncByteChannel::from_env(env, accepted).unwrap();
+            let data: Vec<u8> = (0..20000).map(|i| (i % 251) as u8).collect();
+
+            block_on(async {
+                join!(
+                    async {
+                        client.write_all(&data).await.unwrap();
+                        client.close().await.unwrap();
+                    },
+                    async {
+                        let mut small = [0u8; 10];
+                        accepted.read_exact(&mut small).await.unwrap();
+                        assert_eq!(small, data[..10]);
+
+                        let mut rest = Vec::new();
+                        accepted.read_to_end(&mut rest).await.unwrap();
+                        assert_eq!(rest, data[10..]);
+                    }
+                );
+            });
+        });
+    }
+
+    #[test]
+    fn test_send_async_byte_channel() {
+        use super::{JAsyncByteChannel, JSendAsyncByteChannel};
+        use futures::{executor::block_on, AsyncReadExt, AsyncWriteExt};
+        use std::convert::TryInto;
+
+        test_utils::JVM_ENV.with(|env| {
+            let (client, accepted) = socket_pair(env);
+            let client = JAsyncByteChannel::from_env(env, client).unwrap();
+            let mut client: JSendAsyncByteChannel = client.try_into().unwrap();
+            let accepted = JAsyncByteChannel::from_env(env, accepted).unwrap();
+            let mut accepted: JSendAsyncByteChannel = accepted.try_into().unwrap();
+
+            let thread = std::thread::spawn(move || {
+                test_utils::JVM_ENV.with(|_env| {
+                    block_on(async {
+                        client.write_all(b"Hello from Rust").await.unwrap();
+                        client.close().await.unwrap();
+                    });
+                });
+            });
+
+            block_on(async {
+                let mut result = String::new();
+                accepted.read_to_string(&mut result).await.unwrap();
+                assert_eq!(result, "Hello from Rust");
+
+                accepted.close().await.unwrap();
+                let mut buf = [0u8; 10];
+                let err = accepted.read(&mut buf).await.unwrap_err();
+                assert!(err
+                    .to_string()
+                    .starts_with("java.nio.channels.ClosedChannelException"));
+            });
+            thread.join().unwrap();
+            assert!(!env.exception_check().unwrap());
+        });
+    }
+
+    struct ArcBytes(Arc<Vec<u8>>);
+
+    impl AsRef<[u8]> for ArcBytes {
+        fn as_ref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedWrite(Arc<Mutex<(Vec<u8>, u32)>>);
+
+    impl SharedWrite {
+        fn data(&self) -> Vec<u8> {
+            self.0.lock().unwrap().0.clone()
+        }
+
+        fn flushes(&self) -> u32 {
+            self.0.lock().unwrap().1
+        }
+    }
+
+    impl Write for SharedWrite {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().0.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.0.lock().unwrap().1 += 1;
+            Ok(())
+        }
+    }
+}
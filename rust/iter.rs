@@ -0,0 +1,339 @@
+use jni::{
+    errors::Result,
+    objects::{AutoLocal, JMethodID, JObject},
+    signature::{JavaType, Primitive},
+    JNIEnv,
+};
+
+/// Wrapper for [`JObject`]s that implement `java.util.Iterator`. Implements
+/// [`Iterator`] to allow Rust code to iterate over Java collections.
+///
+/// Each item is wrapped in an [`AutoLocal`] so that its local reference is
+/// deleted as soon as Rust code is done with it, even when iterating over very
+/// large collections. If calling `hasNext()` or `next()` returns an error, it
+/// is yielded as the next item, and the iterator is finished.
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JIterator<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    has_next: JMethodID<'a>,
+    next: JMethodID<'a>,
+    finished: bool,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JIterator<'a, 'b> {
+    /// Create a [`JIterator`] from the environment and an object. This looks
+    /// up the necessary class and method IDs to call all of the methods on it
+    /// so that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/util/Iterator")?);
+
+        let has_next = env.get_method_id(&class, "hasNext", "()Z")?;
+        let next = env.get_method_id(&class, "next", "()Ljava/lang/Object;")?;
+        Ok(Self {
+            internal: obj,
+            has_next,
+            next,
+            finished: false,
+            env,
+        })
+    }
+
+    /// Check whether there are more items by calling
+    /// `java.util.Iterator.hasNext()`.
+    pub fn has_next(&self) -> Result<bool> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.has_next,
+                JavaType::Primitive(Primitive::Boolean),
+                &[],
+            )?
+            .z()
+    }
+
+    /// Get the next item by calling `java.util.Iterator.next()`. Unlike
+    /// [`Iterator::next`], this does not check if there is a next item first,
+    /// and the resulting reference is not automatically deleted.
+    pub fn next_object(&self) -> Result<JObject<'a>> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.next,
+                JavaType::Object("java/lang/Object".into()),
+                &[],
+            )?
+            .l()
+    }
+
+    fn next_internal(&self) -> Result<Option<AutoLocal<'a, 'b>>> {
+        Ok(if self.has_next()? {
+            Some(self.env.auto_local(self.next_object()?))
+        } else {
+            None
+        })
+    }
+}
+
+impl<'a: 'b, 'b> Iterator for JIterator<'a, 'b> {
+    type Item = Result<AutoLocal<'a, 'b>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = self.next_internal().transpose();
+        if let None | Some(Err(_)) = result {
+            self.finished = true;
+        }
+        result
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JIterator<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JIterator<'a, 'b>> for JObject<'a> {
+    fn from(other: JIterator<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+/// Get a [`JIterator`] for a `java.lang.Iterable` by calling its `iterator()`
+/// method.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `iterable` - Object implementing `java.lang.Iterable`.
+pub fn iter<'a: 'b, 'b>(env: &'b JNIEnv<'a>, iterable: JObject<'a>) -> Result<JIterator<'a, 'b>> {
+    let iterator = env
+        .call_method(iterable, "iterator", "()Ljava/util/Iterator;", &[])?
+        .l()?;
+    JIterator::from_env(env, iterator)
+}
+
+/// Wrap an [`Iterator`] in a Java `java.util.Iterator`. Items are computed
+/// lazily as Java code calls `hasNext()` and `next()`, and each item is
+/// converted into a Java object with the given function.
+///
+/// The resulting object is an `io.github.gedgygedgy.rust.iter.FnIterator`.
+/// The Rust iterator is dropped as soon as it is exhausted, or earlier if Java
+/// code calls the object's `close()` method.
+///
+/// If the conversion function returns
+/// [`Error::JavaException`](jni::errors::Error::JavaException), the pending
+/// exception is thrown from `hasNext()`. Any other error is thrown as a
+/// `java.lang.RuntimeException`.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the object.
+/// * `iter` - Iterator to wrap in a Java object.
+/// * `f` - Function to convert each item into a Java object.
+pub fn iterator<'a: 'b, 'b, I: Iterator + Send + 'static>(
+    env: &'b JNIEnv<'a>,
+    iter: I,
+    f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, I::Item) -> Result<JObject<'c>> + Send + 'static,
+) -> Result<JObject<'a>> {
+    let next_fn = env.auto_local(crate::ops::fn_mut_function(env, iterator_next_fn(iter, f))?);
+    iterator_internal(env, next_fn)
+}
+
+/// Wrap an [`Iterator`] in a Java `java.util.Iterator` without checking if it
+/// is [`Send`]. Attempting to use the resulting object from a thread other
+/// than its origin thread will result in an
+/// `io.github.gedgygedgy.rust.thread.LocalThreadException` being thrown.
+///
+/// See [`iterator`] for more details.
+pub fn iterator_local<'a: 'b, 'b, I: Iterator + 'static>(
+    env: &'b JNIEnv<'a>,
+    iter: I,
+    f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, I::Item) -> Result<JObject<'c>> + 'static,
+) -> Result<JObject<'a>> {
+    let next_fn = env.auto_local(crate::ops::fn_mut_function_local(
+        env,
+        iterator_next_fn(iter, f),
+    )?);
+    iterator_internal(env, next_fn)
+}
+
+fn iterator_internal<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    next_fn: AutoLocal<'a, 'b>,
+) -> Result<JObject<'a>> {
+    let class = env.auto_local(env.find_class("io/github/gedgygedgy/rust/iter/FnIterator")?);
+    env.new_object(
+        &class,
+        "(Lio/github/gedgygedgy/rust/ops/FnFunction;)V",
+        &[(&next_fn).into()],
+    )
+}
+
+// The resulting closure is called with a one-element Object[]. If the
+// iterator has another item, it is stored in the array, which is then
+// returned. Otherwise, null is returned.
+fn iterator_next_fn<I: Iterator>(
+    mut iter: I,
+    f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, I::Item) -> Result<JObject<'c>>,
+) -> impl for<'c, 'd> FnMut(&'d JNIEnv<'c>, JObject<'c>, JObject<'c>) -> JObject<'c> {
+    move |env, _obj, slot| {
+        let item = match iter.next() {
+            Some(item) => item,
+            None => return JObject::null(),
+        };
+        let result = f(env, item).and_then(|obj| {
+            let _auto_local = env.auto_local(obj);
+            env.set_object_array_element(slot.into_inner(), 0, obj)
+        });
+        match result {
+            Ok(()) => slot,
+            Err(err) => {
+                if let Ok(false) = env.exception_check() {
+                    let _ = env.throw_new("java/lang/RuntimeException", err.to_string());
+                }
+                JObject::null()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::{self, assert_exception, int_value, integer};
+    use jni::{errors::Error, objects::JObject};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_iter() {
+        test_utils::JVM_ENV.with(|env| {
+            let list = env.new_object("java/util/ArrayList", "()V", &[]).unwrap();
+            let objs = [
+                env.new_object("java/lang/Object", "()V", &[]).unwrap(),
+                JObject::null(),
+                env.new_object("java/lang/Object", "()V", &[]).unwrap(),
+            ];
+            for obj in &objs {
+                env.call_method(list, "add", "(Ljava/lang/Object;)Z", &[(*obj).into()])
+                    .unwrap();
+            }
+
+            let items = super::iter(env, list)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(items.len(), objs.len());
+            for (item, obj) in items.iter().zip(objs.iter()) {
+                assert!(env.is_same_object(item.as_obj(), *obj).unwrap());
+            }
+        });
+    }
+
+    #[test]
+    fn test_iter_error() {
+        test_utils::JVM_ENV.with(|env| {
+            let list = env.new_object("java/util/ArrayList", "()V", &[]).unwrap();
+            let obj = env.new_object("java/lang/Object", "()V", &[]).unwrap();
+            env.call_method(list, "add", "(Ljava/lang/Object;)Z", &[obj.into()])
+                .unwrap();
+            env.call_method(list, "add", "(Ljava/lang/Object;)Z", &[obj.into()])
+                .unwrap();
+
+            let mut iter = super::iter(env, list).unwrap();
+            assert!(env
+                .is_same_object(iter.next().unwrap().unwrap().as_obj(), obj)
+                .unwrap());
+            env.call_method(list, "clear", "()V", &[]).unwrap();
+
+            assert_exception(
+                env,
+                iter.next().unwrap(),
+                "java/util/ConcurrentModificationException",
+            );
+            assert!(iter.next().is_none());
+        });
+    }
+
+    #[test]
+    fn test_iterator() {
+        test_utils::JVM_ENV.with(|env| {
+            let data = Arc::new(());
+            let data_clone = data.clone();
+            let obj = super::iterator(env, 0..5, move |env, i| {
+                let _ = &data_clone;
+                integer(env, i)
+            })
+            .unwrap();
+            assert_eq!(Arc::strong_count(&data), 2);
+
+            let items = super::JIterator::from_env(env, obj)
+                .unwrap()
+                .map(|item| int_value(env, item.unwrap().as_obj()).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(items, vec![0, 1, 2, 3, 4]);
+            assert_eq!(Arc::strong_count(&data), 1);
+
+            let result = env.call_method(obj, "hasNext", "()Z", &[]).unwrap();
+            assert!(!result.z().unwrap());
+        });
+    }
+
+    #[test]
+    fn test_iterator_close() {
+        test_utils::JVM_ENV.with(|env| {
+            let data = Arc::new(());
+            let data_clone = data.clone();
+            let obj = super::iterator_local(env, 0.., move |_env, _i| {
+                let _ = &data_clone;
+                Ok(JObject::null())
+            })
+            .unwrap();
+            assert_eq!(Arc::strong_count(&data), 2);
+
+            let mut iter = super::JIterator::from_env(env, obj).unwrap();
+            assert!(env
+                .is_same_object(iter.next().unwrap().unwrap().as_obj(), JObject::null())
+                .unwrap());
+            assert_eq!(Arc::strong_count(&data), 2);
+
+            env.call_method(obj, "close", "()V", &[]).unwrap();
+            assert_eq!(Arc::strong_count(&data), 1);
+            assert!(iter.next().is_none());
+        });
+    }
+
+    #[test]
+    fn test_iterator_error() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = super::iterator(env, 0..2, |env, i| {
+                if i == 0 {
+                    env.throw_new("java/lang/IllegalStateException", "Thrown")?;
+                    Err(Error::JavaException)
+                } else {
+                    Err(Error::NullPtr("Not thrown"))
+                }
+            })
+            .unwrap();
+
+            for class in &[
+                "java/lang/IllegalStateException",
+                "java/lang/RuntimeException",
+            ] {
+                let mut iter = super::JIterator::from_env(env, obj).unwrap();
+                assert_exception(env, iter.next().unwrap(), class);
+            }
+        });
+    }
+}
@@ -41,12 +41,34 @@
 
 use ::jni::{errors::Result, JNIEnv};
 
+// Allows the derive macros to refer to this crate by name from within it.
+extern crate self as jni_utils;
+
 pub mod arrays;
+pub mod class;
+pub mod convert;
+pub mod enums;
 pub mod exceptions;
 pub mod future;
+pub mod io;
+pub mod iter;
+pub mod list;
+pub mod map;
+pub mod math;
+#[cfg(feature = "url")]
+pub mod net;
 pub mod ops;
+pub mod optional;
+pub mod path;
+pub mod primitives;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod set;
+pub mod signature;
 pub mod stream;
+pub mod strings;
 pub mod task;
+pub mod time;
 pub mod uuid;
 
 /// Initialize [`jni-utils`](crate) by registering required native methods.
@@ -62,7 +84,11 @@ pub fn init(env: &JNIEnv) -> Result<()> {
 
 #[cfg(test)]
 pub(crate) mod test_utils {
-    use jni::{objects::GlobalRef, JNIEnv, JavaVM};
+    use jni::{
+        errors::{Error, Result},
+        objects::{GlobalRef, JObject},
+        JNIEnv, JavaVM,
+    };
     use lazy_static::lazy_static;
     use std::{
         sync::{Arc, Mutex},
@@ -100,6 +126,44 @@ pub(crate) mod test_utils {
         Waker::from(data.clone())
     }
 
+    pub fn integer<'a: 'b, 'b>(env: &'b JNIEnv<'a>, value: i32) -> Result<JObject<'a>> {
+        env.call_static_method(
+            "java/lang/Integer",
+            "valueOf",
+            "(I)Ljava/lang/Integer;",
+            &[value.into()],
+        )?
+        .l()
+    }
+
+    pub fn int_value<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<i32> {
+        env.call_method(obj, "intValue", "()I", &[])?.i()
+    }
+
+    pub fn string<'a: 'b, 'b>(env: &'b JNIEnv<'a>, value: &str) -> Result<JObject<'a>> {
+        Ok(env.new_string(value)?.into())
+    }
+
+    pub fn rust_string<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<String> {
+        Ok(env.get_string(obj.into())?.into())
+    }
+
+    pub fn to_string<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> String {
+        let s = env
+            .call_method(obj, "toString", "()Ljava/lang/String;", &[])
+            .unwrap()
+            .l()
+            .unwrap();
+        rust_string(env, s).unwrap()
+    }
+
+    pub fn assert_exception<T>(env: &JNIEnv, result: Result<T>, class: &str) {
+        assert!(matches!(result, Err(Error::JavaException)));
+        let ex = env.exception_occurred().unwrap();
+        env.exception_clear().unwrap();
+        assert!(env.is_instance_of(ex, class).unwrap());
+    }
+
     struct GlobalJVM {
         jvm: JavaVM,
         class_loader: GlobalRef,
@@ -0,0 +1,332 @@
+use crate::{exceptions::try_block, iter::JIterator};
+use jni::{
+    errors::Result,
+    objects::{JMethodID, JObject, JValue},
+    signature::{JavaType, Primitive},
+    sys::jint,
+    JNIEnv,
+};
+use std::convert::TryFrom;
+
+/// Wrapper for [`JObject`]s that implement `java.util.List`. Provides methods
+/// to get, add, replace and remove elements.
+///
+/// Methods which take an index return [`None`] if the index is out of range,
+/// rather than leaving a pending `java.lang.IndexOutOfBoundsException`.
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JList<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    size: JMethodID<'a>,
+    get: JMethodID<'a>,
+    add: JMethodID<'a>,
+    set: JMethodID<'a>,
+    remove: JMethodID<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JList<'a, 'b> {
+    /// Create a [`JList`] from the environment and an object. This looks up
+    /// the necessary class and method IDs to call all of the methods on it so
+    /// that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/util/List")?);
+
+        let size = env.get_method_id(&class, "size", "()I")?;
+        let get = env.get_method_id(&class, "get", "(I)Ljava/lang/Object;")?;
+        let add = env.get_method_id(&class, "add", "(Ljava/lang/Object;)Z")?;
+        let set = env.get_method_id(&class, "set", "(ILjava/lang/Object;)Ljava/lang/Object;")?;
+        let remove = env.get_method_id(&class, "remove", "(I)Ljava/lang/Object;")?;
+        Ok(Self {
+            internal: obj,
+            size,
+            get,
+            add,
+            set,
+            remove,
+            env,
+        })
+    }
+
+    /// Create a [`JList`] which wraps a new, empty `java.util.ArrayList`.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    pub fn new(env: &'b JNIEnv<'a>) -> Result<Self> {
+        Self::with_capacity(env, 0)
+    }
+
+    /// Create a [`JList`] which wraps a new, empty `java.util.ArrayList` with
+    /// room for at least the given number of elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `capacity` - Initial capacity of the list.
+    pub fn with_capacity(env: &'b JNIEnv<'a>, capacity: usize) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/util/ArrayList")?);
+        let capacity = jint::try_from(capacity).unwrap_or(jint::MAX);
+        let obj = env.new_object(&class, "(I)V", &[capacity.into()])?;
+        Self::from_env(env, obj)
+    }
+
+    /// Get the number of elements in the list by calling
+    /// `java.util.List.size()`.
+    pub fn size(&self) -> Result<usize> {
+        let size = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.size,
+                JavaType::Primitive(Primitive::Int),
+                &[],
+            )?
+            .i()?;
+        Ok(size as usize)
+    }
+
+    /// Get the element at the given index by calling
+    /// `java.util.List.get()`, or [`None`] if the index is out of range.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the element to get.
+    pub fn get(&self, index: usize) -> Result<Option<JObject<'a>>> {
+        self.call_with_index(self.get, index, None)
+    }
+
+    /// Append an element to the end of the list by calling
+    /// `java.util.List.add()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `obj` - Element to add.
+    pub fn add(&self, obj: JObject<'a>) -> Result<()> {
+        self.env.call_method_unchecked(
+            self.internal,
+            self.add,
+            JavaType::Primitive(Primitive::Boolean),
+            &[obj.into()],
+        )?;
+        Ok(())
+    }
+
+    /// Replace the element at the given index by calling
+    /// `java.util.List.set()`. Returns the element previously at that index,
+    /// or [`None`] if the index is out of range.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the element to replace.
+    /// * `obj` - New element.
+    pub fn set(&self, index: usize, obj: JObject<'a>) -> Result<Option<JObject<'a>>> {
+        self.call_with_index(self.set, index, Some(obj))
+    }
+
+    /// Remove the element at the given index by calling
+    /// `java.util.List.remove()`. Returns the removed element, or [`None`] if
+    /// the index is out of range.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the element to remove.
+    pub fn remove(&self, index: usize) -> Result<Option<JObject<'a>>> {
+        self.call_with_index(self.remove, index, None)
+    }
+
+    /// Get a [`JIterator`] over the elements of the list.
+    pub fn iter(&self) -> Result<JIterator<'a, 'b>> {
+        crate::iter::iter(self.env, self.internal)
+    }
+
+    fn call_with_index(
+        &self,
+        method: JMethodID<'a>,
+        index: usize,
+        obj: Option<JObject<'a>>,
+    ) -> Result<Option<JObject<'a>>> {
+        let index = match jint::try_from(index) {
+            Ok(index) => index,
+            Err(_) => return Ok(None),
+        };
+        let mut args = vec![JValue::from(index)];
+        args.extend(obj.map(JValue::from));
+
+        try_block(self.env, || {
+            Ok(Some(
+                self.env
+                    .call_method_unchecked(
+                        self.internal,
+                        method,
+                        JavaType::Object("java/lang/Object".into()),
+                        &args,
+                    )?
+                    .l()?,
+            ))
+        })
+        .catch("java/lang/IndexOutOfBoundsException", |ex| {
+            self.env.delete_local_ref(*ex)?;
+            Ok(None)
+        })
+        .result()
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JList<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JList<'a, 'b>> for JObject<'a> {
+    fn from(other: JList<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+/// Create a new `java.util.ArrayList` from the given slice, converting each
+/// element into a Java object with the given function. The local reference
+/// returned by the function is deleted once the object has been added to the
+/// list.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new list.
+/// * `slice` - Slice to convert into a list.
+/// * `f` - Function to convert each element into a Java object.
+pub fn slice_to_list<'a: 'b, 'b, T>(
+    env: &'b JNIEnv<'a>,
+    slice: &[T],
+    mut f: impl FnMut(&'b JNIEnv<'a>, &T) -> Result<JObject<'a>>,
+) -> Result<JList<'a, 'b>> {
+    let list = JList::with_capacity(env, slice.len())?;
+    for item in slice {
+        let obj = env.auto_local(f(env, item)?);
+        list.add(obj.as_obj())?;
+    }
+    Ok(list)
+}
+
+/// Get a [`Vec`] from the given `java.util.List`, converting each element
+/// into a Rust value with the given function. The local reference passed to
+/// the function is deleted after it returns.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `list` - List to convert into a [`Vec`].
+/// * `f` - Function to convert each element into a Rust value.
+pub fn list_to_vec<'a: 'b, 'b, T>(
+    env: &'b JNIEnv<'a>,
+    list: JObject<'a>,
+    mut f: impl FnMut(&'b JNIEnv<'a>, JObject<'a>) -> Result<T>,
+) -> Result<Vec<T>> {
+    let list = JList::from_env(env, list)?;
+    let mut result = Vec::with_capacity(list.size()?);
+    for item in list.iter()? {
+        result.push(f(env, item?.as_obj())?);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::JList;
+    use crate::test_utils::{self, int_value, integer};
+    use jni::objects::JObject;
+
+    #[test]
+    fn test_list_methods() {
+        test_utils::JVM_ENV.with(|env| {
+            let list = JList::new(env).unwrap();
+            assert_eq!(list.size().unwrap(), 0);
+
+            list.add(integer(env, 1).unwrap()).unwrap();
+            list.add(integer(env, 2).unwrap()).unwrap();
+            list.add(JObject::null()).unwrap();
+            assert_eq!(list.size().unwrap(), 3);
+
+            let item = list.get(1).unwrap().unwrap();
+            assert_eq!(int_value(env, item).unwrap(), 2);
+            let item = list.get(2).unwrap().unwrap();
+            assert!(env.is_same_object(item, JObject::null()).unwrap());
+
+            let old = list.set(0, integer(env, 3).unwrap()).unwrap().unwrap();
+            assert_eq!(int_value(env, old).unwrap(), 1);
+            let item = list.get(0).unwrap().unwrap();
+            assert_eq!(int_value(env, item).unwrap(), 3);
+
+            let removed = list.remove(1).unwrap().unwrap();
+            assert_eq!(int_value(env, removed).unwrap(), 2);
+            assert_eq!(list.size().unwrap(), 2);
+
+            let obj: JObject = list.into();
+            assert!(env.is_instance_of(obj, "java/util/ArrayList").unwrap());
+        });
+    }
+
+    #[test]
+    fn test_list_out_of_range() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = env.new_object("java/util/LinkedList", "()V", &[]).unwrap();
+            let list = JList::from_env(env, obj).unwrap();
+            list.add(integer(env, 1).unwrap()).unwrap();
+
+            assert!(list.get(1).unwrap().is_none());
+            assert!(list.get(usize::MAX).unwrap().is_none());
+            assert!(list.set(5, integer(env, 2).unwrap()).unwrap().is_none());
+            assert!(list.remove(1).unwrap().is_none());
+            assert!(!env.exception_check().unwrap());
+            assert_eq!(list.size().unwrap(), 1);
+        });
+    }
+
+    #[test]
+    fn test_slice_to_list() {
+        test_utils::JVM_ENV.with(|env| {
+            let list = super::slice_to_list(env, &[1, 2, 3], |env, i| integer(env, *i)).unwrap();
+            assert_eq!(list.size().unwrap(), 3);
+            for i in 0..3 {
+                let item = list.get(i).unwrap().unwrap();
+                assert_eq!(int_value(env, item).unwrap(), i as i32 + 1);
+            }
+        });
+    }
+
+    #[test]
+    fn test_list_to_vec() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = env.new_object("java/util/LinkedList", "()V", &[]).unwrap();
+            let list = JList::from_env(env, obj).unwrap();
+            for i in &[4, 5, 6] {
+                list.add(integer(env, *i).unwrap()).unwrap();
+            }
+
+            let vec = super::list_to_vec(env, obj, int_value).unwrap();
+            assert_eq!(vec, vec![4, 5, 6]);
+        });
+    }
+
+    #[test]
+    fn test_list_to_vec_error() {
+        test_utils::JVM_ENV.with(|env| {
+            let list = JList::new(env).unwrap();
+            list.add(integer(env, 1).unwrap()).unwrap();
+            list.add(env.new_object("java/lang/Object", "()V", &[]).unwrap())
+                .unwrap();
+
+            let result = super::list_to_vec(env, *list, int_value);
+            assert!(result.is_err());
+            env.exception_clear().unwrap();
+        });
+    }
+}
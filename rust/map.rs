@@ -0,0 +1,561 @@
+use crate::iter::JIterator;
+use jni::{
+    errors::Result,
+    objects::{AutoLocal, JMethodID, JObject},
+    signature::{JavaType, Primitive},
+    JNIEnv,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+};
+
+/// Wrapper for [`JObject`]s that implement `java.util.Map`. Provides methods
+/// to get, put and remove entries, and to iterate over them.
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JMap<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    size: JMethodID<'a>,
+    get: JMethodID<'a>,
+    put: JMethodID<'a>,
+    remove: JMethodID<'a>,
+    contains_key: JMethodID<'a>,
+    entry_set: JMethodID<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JMap<'a, 'b> {
+    /// Create a [`JMap`] from the environment and an object. This looks up
+    /// the necessary class and method IDs to call all of the methods on it so
+    /// that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/util/Map")?);
+
+        let size = env.get_method_id(&class, "size", "()I")?;
+        let get = env.get_method_id(&class, "get", "(Ljava/lang/Object;)Ljava/lang/Object;")?;
+        let put = env.get_method_id(
+            &class,
+            "put",
+            "(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;",
+        )?;
+        let remove =
+            env.get_method_id(&class, "remove", "(Ljava/lang/Object;)Ljava/lang/Object;")?;
+        let contains_key = env.get_method_id(&class, "containsKey", "(Ljava/lang/Object;)Z")?;
+        let entry_set = env.get_method_id(&class, "entrySet", "()Ljava/util/Set;")?;
+        Ok(Self {
+            internal: obj,
+            size,
+            get,
+            put,
+            remove,
+            contains_key,
+            entry_set,
+            env,
+        })
+    }
+
+    /// Create a [`JMap`] which wraps a new, empty `java.util.HashMap`.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    pub fn new(env: &'b JNIEnv<'a>) -> Result<Self> {
+        Self::new_impl(env, "java/util/HashMap")
+    }
+
+    /// Create a [`JMap`] which wraps a new, empty `java.util.LinkedHashMap`.
+    /// Unlike [`new`](JMap::new), the resulting map iterates over its entries
+    /// in the order in which they were inserted.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    pub fn new_linked(env: &'b JNIEnv<'a>) -> Result<Self> {
+        Self::new_impl(env, "java/util/LinkedHashMap")
+    }
+
+    /// Get the number of entries in the map by calling
+    /// `java.util.Map.size()`.
+    pub fn size(&self) -> Result<usize> {
+        let size = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.size,
+                JavaType::Primitive(Primitive::Int),
+                &[],
+            )?
+            .i()?;
+        Ok(size as usize)
+    }
+
+    /// Get the value for a key by calling `java.util.Map.get()`. Returns
+    /// `null` if the map does not contain the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up.
+    pub fn get(&self, key: JObject<'a>) -> Result<JObject<'a>> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.get,
+                JavaType::Object("java/lang/Object".into()),
+                &[key.into()],
+            )?
+            .l()
+    }
+
+    /// Set the value for a key by calling `java.util.Map.put()`. Returns the
+    /// previous value, or `null` if the map did not contain the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to set.
+    /// * `value` - New value.
+    pub fn put(&self, key: JObject<'a>, value: JObject<'a>) -> Result<JObject<'a>> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.put,
+                JavaType::Object("java/lang/Object".into()),
+                &[key.into(), value.into()],
+            )?
+            .l()
+    }
+
+    /// Remove a key by calling `java.util.Map.remove()`. Returns the previous
+    /// value, or `null` if the map did not contain the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to remove.
+    pub fn remove(&self, key: JObject<'a>) -> Result<JObject<'a>> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.remove,
+                JavaType::Object("java/lang/Object".into()),
+                &[key.into()],
+            )?
+            .l()
+    }
+
+    /// Check whether the map contains a key by calling
+    /// `java.util.Map.containsKey()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to look up.
+    pub fn contains_key(&self, key: JObject<'a>) -> Result<bool> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.contains_key,
+                JavaType::Primitive(Primitive::Boolean),
+                &[key.into()],
+            )?
+            .z()
+    }
+
+    /// Get a [`JMapEntries`] which iterates over the entries of the map, in
+    /// the map's own iteration order.
+    pub fn entries(&self) -> Result<JMapEntries<'a, 'b>> {
+        let entry_set = self.env.auto_local(
+            self.env
+                .call_method_unchecked(
+                    self.internal,
+                    self.entry_set,
+                    JavaType::Object("java/util/Set".into()),
+                    &[],
+                )?
+                .l()?,
+        );
+        JMapEntries::from_env(self.env, crate::iter::iter(self.env, entry_set.as_obj())?)
+    }
+
+    fn new_impl(env: &'b JNIEnv<'a>, class: &str) -> Result<Self> {
+        let class = env.auto_local(env.find_class(class)?);
+        let obj = env.new_object(&class, "()V", &[])?;
+        Self::from_env(env, obj)
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JMap<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JMap<'a, 'b>> for JObject<'a> {
+    fn from(other: JMap<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+/// Iterator over the entries of a [`JMap`]. Each item is a key-value pair
+/// taken from a `java.util.Map.Entry`, with both references wrapped in an
+/// [`AutoLocal`].
+///
+/// Looks up the class and method IDs on creation rather than for every
+/// entry.
+pub struct JMapEntries<'a: 'b, 'b> {
+    iter: JIterator<'a, 'b>,
+    get_key: JMethodID<'a>,
+    get_value: JMethodID<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JMapEntries<'a, 'b> {
+    /// Create a [`JMapEntries`] from the environment and an iterator over
+    /// `java.util.Map.Entry` objects.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `iter` - Iterator over the entries.
+    pub fn from_env(env: &'b JNIEnv<'a>, iter: JIterator<'a, 'b>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/util/Map$Entry")?);
+
+        let get_key = env.get_method_id(&class, "getKey", "()Ljava/lang/Object;")?;
+        let get_value = env.get_method_id(&class, "getValue", "()Ljava/lang/Object;")?;
+        Ok(Self {
+            iter,
+            get_key,
+            get_value,
+            env,
+        })
+    }
+
+    fn split_entry(
+        &self,
+        entry: AutoLocal<'a, 'b>,
+    ) -> Result<(AutoLocal<'a, 'b>, AutoLocal<'a, 'b>)> {
+        let key = self.env.auto_local(
+            self.env
+                .call_method_unchecked(
+                    entry.as_obj(),
+                    self.get_key,
+                    JavaType::Object("java/lang/Object".into()),
+                    &[],
+                )?
+                .l()?,
+        );
+        let value = self.env.auto_local(
+            self.env
+                .call_method_unchecked(
+                    entry.as_obj(),
+                    self.get_value,
+                    JavaType::Object("java/lang/Object".into()),
+                    &[],
+                )?
+                .l()?,
+        );
+        Ok((key, value))
+    }
+}
+
+impl<'a: 'b, 'b> Iterator for JMapEntries<'a, 'b> {
+    type Item = Result<(AutoLocal<'a, 'b>, AutoLocal<'a, 'b>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.iter.next()?;
+        Some(entry.and_then(|entry| self.split_entry(entry)))
+    }
+}
+
+/// Create a new `java.util.HashMap` from the given [`HashMap`], converting
+/// each key and value into a Java object with the given functions. The local
+/// references returned by the functions are deleted once the entry has been
+/// added to the map.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new map.
+/// * `map` - Map to convert into a Java map.
+/// * `fk` - Function to convert each key into a Java object.
+/// * `fv` - Function to convert each value into a Java object.
+pub fn hash_map_to_map<'a: 'b, 'b, K, V, S>(
+    env: &'b JNIEnv<'a>,
+    map: &HashMap<K, V, S>,
+    fk: impl FnMut(&'b JNIEnv<'a>, &K) -> Result<JObject<'a>>,
+    fv: impl FnMut(&'b JNIEnv<'a>, &V) -> Result<JObject<'a>>,
+) -> Result<JMap<'a, 'b>> {
+    fill_map(JMap::new(env)?, map, fk, fv)
+}
+
+/// Create a new `java.util.LinkedHashMap` from the given [`BTreeMap`],
+/// converting each key and value into a Java object with the given functions.
+/// The resulting map iterates over its entries in the same order as the
+/// original [`BTreeMap`]. The local references returned by the functions are
+/// deleted once the entry has been added to the map.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new map.
+/// * `map` - Map to convert into a Java map.
+/// * `fk` - Function to convert each key into a Java object.
+/// * `fv` - Function to convert each value into a Java object.
+pub fn btree_map_to_map<'a: 'b, 'b, K, V>(
+    env: &'b JNIEnv<'a>,
+    map: &BTreeMap<K, V>,
+    fk: impl FnMut(&'b JNIEnv<'a>, &K) -> Result<JObject<'a>>,
+    fv: impl FnMut(&'b JNIEnv<'a>, &V) -> Result<JObject<'a>>,
+) -> Result<JMap<'a, 'b>> {
+    fill_map(JMap::new_linked(env)?, map, fk, fv)
+}
+
+/// Get a [`HashMap`] from the given `java.util.Map`, converting each key and
+/// value into a Rust value with the given functions. The local references
+/// passed to the functions are deleted after they return.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `map` - Map to convert into a [`HashMap`].
+/// * `fk` - Function to convert each key into a Rust value.
+/// * `fv` - Function to convert each value into a Rust value.
+pub fn map_to_hash_map<'a: 'b, 'b, K: Eq + Hash, V>(
+    env: &'b JNIEnv<'a>,
+    map: JObject<'a>,
+    fk: impl FnMut(&'b JNIEnv<'a>, JObject<'a>) -> Result<K>,
+    fv: impl FnMut(&'b JNIEnv<'a>, JObject<'a>) -> Result<V>,
+) -> Result<HashMap<K, V>> {
+    map_to_entries(env, map, fk, fv)
+}
+
+/// Get a [`BTreeMap`] from the given `java.util.Map`, converting each key and
+/// value into a Rust value with the given functions. The local references
+/// passed to the functions are deleted after they return.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `map` - Map to convert into a [`BTreeMap`].
+/// * `fk` - Function to convert each key into a Rust value.
+/// * `fv` - Function to convert each value into a Rust value.
+pub fn map_to_btree_map<'a: 'b, 'b, K: Ord, V>(
+    env: &'b JNIEnv<'a>,
+    map: JObject<'a>,
+    fk: impl FnMut(&'b JNIEnv<'a>, JObject<'a>) -> Result<K>,
+    fv: impl FnMut(&'b JNIEnv<'a>, JObject<'a>) -> Result<V>,
+) -> Result<BTreeMap<K, V>> {
+    map_to_entries(env, map, fk, fv)
+}
+
+/// Get a [`Vec`] of key-value pairs from the given `java.util.Map`,
+/// converting each key and value into a Rust value with the given functions.
+/// The pairs are in the map's own iteration order, so the ordering of sources
+/// such as `java.util.LinkedHashMap` and `java.util.TreeMap` is preserved.
+/// The local references passed to the functions are deleted after they
+/// return.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `map` - Map to convert into a [`Vec`].
+/// * `fk` - Function to convert each key into a Rust value.
+/// * `fv` - Function to convert each value into a Rust value.
+pub fn map_to_vec<'a: 'b, 'b, K, V>(
+    env: &'b JNIEnv<'a>,
+    map: JObject<'a>,
+    fk: impl FnMut(&'b JNIEnv<'a>, JObject<'a>) -> Result<K>,
+    fv: impl FnMut(&'b JNIEnv<'a>, JObject<'a>) -> Result<V>,
+) -> Result<Vec<(K, V)>> {
+    map_to_entries(env, map, fk, fv)
+}
+
+fn fill_map<'a: 'b, 'b, 'c, K: 'c, V: 'c>(
+    map: JMap<'a, 'b>,
+    entries: impl IntoIterator<Item = (&'c K, &'c V)>,
+    mut fk: impl FnMut(&'b JNIEnv<'a>, &K) -> Result<JObject<'a>>,
+    mut fv: impl FnMut(&'b JNIEnv<'a>, &V) -> Result<JObject<'a>>,
+) -> Result<JMap<'a, 'b>> {
+    let env = map.env;
+    for (key, value) in entries {
+        let key = env.auto_local(fk(env, key)?);
+        let value = env.auto_local(fv(env, value)?);
+        let _old = env.auto_local(map.put(key.as_obj(), value.as_obj())?);
+    }
+    Ok(map)
+}
+
+fn map_to_entries<'a: 'b, 'b, K, V, C: Default + Extend<(K, V)>>(
+    env: &'b JNIEnv<'a>,
+    map: JObject<'a>,
+    mut fk: impl FnMut(&'b JNIEnv<'a>, JObject<'a>) -> Result<K>,
+    mut fv: impl FnMut(&'b JNIEnv<'a>, JObject<'a>) -> Result<V>,
+) -> Result<C> {
+    let map = JMap::from_env(env, map)?;
+    let mut result = C::default();
+    for entry in map.entries()? {
+        let (key, value) = entry?;
+        let key = fk(env, key.as_obj())?;
+        let value = fv(env, value.as_obj())?;
+        result.extend(Some((key, value)));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::JMap;
+    use crate::test_utils::{self, int_value, integer, rust_string, string};
+    use jni::objects::JObject;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[test]
+    fn test_map_methods() {
+        test_utils::JVM_ENV.with(|env| {
+            let map = JMap::new(env).unwrap();
+            assert_eq!(map.size().unwrap(), 0);
+
+            let key = string(env, "one").unwrap();
+            let old = map.put(key, integer(env, 1).unwrap()).unwrap();
+            assert!(env.is_same_object(old, JObject::null()).unwrap());
+            assert_eq!(map.size().unwrap(), 1);
+
+            let other_key = string(env, "one").unwrap();
+            assert!(map.contains_key(other_key).unwrap());
+            let value = map.get(other_key).unwrap();
+            assert_eq!(int_value(env, value).unwrap(), 1);
+
+            let old = map.put(other_key, integer(env, 2).unwrap()).unwrap();
+            assert_eq!(int_value(env, old).unwrap(), 1);
+            assert_eq!(map.size().unwrap(), 1);
+
+            let missing = string(env, "two").unwrap();
+            assert!(!map.contains_key(missing).unwrap());
+            let value = map.get(missing).unwrap();
+            assert!(env.is_same_object(value, JObject::null()).unwrap());
+
+            let removed = map.remove(key).unwrap();
+            assert_eq!(int_value(env, removed).unwrap(), 2);
+            assert_eq!(map.size().unwrap(), 0);
+        });
+    }
+
+    #[test]
+    fn test_map_entries() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = env.new_object("java/util/TreeMap", "()V", &[]).unwrap();
+            let map = JMap::from_env(env, obj).unwrap();
+            for (key, value) in &[("c", 3), ("a", 1), ("b", 2)] {
+                map.put(string(env, key).unwrap(), integer(env, *value).unwrap())
+                    .unwrap();
+            }
+
+            let entries = map
+                .entries()
+                .unwrap()
+                .map(|entry| {
+                    let (key, value) = entry.unwrap();
+                    (
+                        rust_string(env, key.as_obj()).unwrap(),
+                        int_value(env, value.as_obj()).unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                entries,
+                vec![
+                    ("a".to_string(), 1),
+                    ("b".to_string(), 2),
+                    ("c".to_string(), 3)
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_hash_map_to_map() {
+        test_utils::JVM_ENV.with(|env| {
+            let mut map = HashMap::new();
+            map.insert("one".to_string(), 1);
+            map.insert("two".to_string(), 2);
+
+            let jmap = super::hash_map_to_map(
+                env,
+                &map,
+                |env, k| string(env, k),
+                |env, v| integer(env, *v),
+            )
+            .unwrap();
+            assert_eq!(jmap.size().unwrap(), 2);
+            let value = jmap.get(string(env, "two").unwrap()).unwrap();
+            assert_eq!(int_value(env, value).unwrap(), 2);
+
+            let obj: JObject = jmap.into();
+            assert!(env.is_instance_of(obj, "java/util/HashMap").unwrap());
+        });
+    }
+
+    #[test]
+    fn test_btree_map_to_map() {
+        test_utils::JVM_ENV.with(|env| {
+            let mut map = BTreeMap::new();
+            map.insert(3, "c".to_string());
+            map.insert(1, "a".to_string());
+            map.insert(2, "b".to_string());
+
+            let jmap = super::btree_map_to_map(
+                env,
+                &map,
+                |env, k| integer(env, *k),
+                |env, v| string(env, v),
+            )
+            .unwrap();
+            let entries = super::map_to_vec(env, *jmap, int_value, rust_string).unwrap();
+            assert_eq!(
+                entries,
+                vec![
+                    (1, "a".to_string()),
+                    (2, "b".to_string()),
+                    (3, "c".to_string())
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_map_to_rust_maps() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = env
+                .new_object("java/util/LinkedHashMap", "()V", &[])
+                .unwrap();
+            let map = JMap::from_env(env, obj).unwrap();
+            for (key, value) in &[("z", 26), ("a", 1), ("m", 13)] {
+                map.put(string(env, key).unwrap(), integer(env, *value).unwrap())
+                    .unwrap();
+            }
+
+            let entries = super::map_to_vec(env, obj, rust_string, int_value).unwrap();
+            assert_eq!(
+                entries,
+                vec![
+                    ("z".to_string(), 26),
+                    ("a".to_string(), 1),
+                    ("m".to_string(), 13)
+                ]
+            );
+
+            let hash_map = super::map_to_hash_map(env, obj, rust_string, int_value).unwrap();
+            assert_eq!(hash_map, entries.iter().cloned().collect::<HashMap<_, _>>());
+
+            let btree_map = super::map_to_btree_map(env, obj, rust_string, int_value).unwrap();
+            assert_eq!(
+                btree_map,
+                entries.iter().cloned().collect::<BTreeMap<_, _>>()
+            );
+        });
+    }
+}
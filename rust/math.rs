@@ -0,0 +1,588 @@
+use crate::{
+    arrays::{byte_array_to_vec, slice_to_byte_array},
+    exceptions::throw_error,
+};
+use jni::{
+    errors::Result,
+    objects::{AutoLocal, JMethodID, JObject},
+    signature::{JavaType, Primitive},
+    JNIEnv,
+};
+use std::convert::TryInto;
+
+/// Wrapper for [`JObject`]s that contain `java.math.BigInteger`. Provides
+/// methods to convert to and from [`i128`] and [`u128`], and, with the
+/// `num-bigint` feature, `num_bigint::BigInt`.
+///
+/// Conversions go through the two's-complement byte representation returned
+/// by `toByteArray()` rather than through strings. If a value does not fit in
+/// the requested Rust type, a `java.lang.ArithmeticException` is thrown.
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JBigInteger<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    to_byte_array: JMethodID<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JBigInteger<'a, 'b> {
+    /// Create a [`JBigInteger`] from the environment and an object. This looks
+    /// up the necessary class and method IDs to call all of the methods on it
+    /// so that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/math/BigInteger")?);
+        Self::from_env_impl(env, obj, class)
+    }
+
+    /// Create a [`JBigInteger`] which wraps a new `java.math.BigInteger`
+    /// created from a given [`i128`].
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `value` - Value to convert into a `java.math.BigInteger`.
+    pub fn from_i128(env: &'b JNIEnv<'a>, value: i128) -> Result<Self> {
+        Self::from_bytes(env, &value.to_be_bytes())
+    }
+
+    /// Create a [`JBigInteger`] which wraps a new `java.math.BigInteger`
+    /// created from a given [`u128`].
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `value` - Value to convert into a `java.math.BigInteger`.
+    pub fn from_u128(env: &'b JNIEnv<'a>, value: u128) -> Result<Self> {
+        // Prefix a zero byte so that the value is never interpreted as
+        // negative.
+        let mut bytes = [0u8; 17];
+        bytes[1..].copy_from_slice(&value.to_be_bytes());
+        Self::from_bytes(env, &bytes)
+    }
+
+    /// Convert the `java.math.BigInteger` into an [`i128`]. If the value is
+    /// out of range, a `java.lang.ArithmeticException` is thrown.
+    pub fn as_i128(&self) -> Result<i128> {
+        let bytes = self.to_bytes()?;
+        match sign_extend::<16>(&bytes) {
+            Some(bytes) => Ok(i128::from_be_bytes(bytes)),
+            None => throw_error(
+                self.env,
+                "java/lang/ArithmeticException",
+                "BigInteger out of i128 range",
+            ),
+        }
+    }
+
+    /// Convert the `java.math.BigInteger` into a [`u128`]. If the value is
+    /// negative or out of range, a `java.lang.ArithmeticException` is thrown.
+    pub fn as_u128(&self) -> Result<u128> {
+        let bytes = self.to_bytes()?;
+        match sign_extend::<17>(&bytes) {
+            Some(bytes) if bytes[0] == 0 => Ok(u128::from_be_bytes(bytes[1..].try_into().unwrap())),
+            _ => throw_error(
+                self.env,
+                "java/lang/ArithmeticException",
+                "BigInteger out of u128 range",
+            ),
+        }
+    }
+
+    /// Get the big-endian two's-complement representation of the value by
+    /// calling `java.math.BigInteger.toByteArray()`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let array = self.env.auto_local(
+            self.env
+                .call_method_unchecked(
+                    self.internal,
+                    self.to_byte_array,
+                    JavaType::Array(Box::new(JavaType::Primitive(Primitive::Byte))),
+                    &[],
+                )?
+                .l()?,
+        );
+        byte_array_to_vec(self.env, array.as_obj().into_inner())
+    }
+
+    /// Create a [`JBigInteger`] which wraps a new `java.math.BigInteger`
+    /// created from a big-endian two's-complement representation.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `bytes` - Big-endian two's-complement bytes. Must not be empty.
+    pub fn from_bytes(env: &'b JNIEnv<'a>, bytes: &[u8]) -> Result<Self> {
+        let array = env.auto_local(JObject::from(slice_to_byte_array(env, bytes)?));
+        let class = env.auto_local(env.find_class("java/math/BigInteger")?);
+        let obj = env.new_object(&class, "([B)V", &[array.as_obj().into()])?;
+        Self::from_env_impl(env, obj, class)
+    }
+
+    fn from_env_impl(
+        env: &'b JNIEnv<'a>,
+        obj: JObject<'a>,
+        class: AutoLocal<'a, 'b>,
+    ) -> Result<Self> {
+        let to_byte_array = env.get_method_id(&class, "toByteArray", "()[B")?;
+        Ok(Self {
+            internal: obj,
+            to_byte_array,
+            env,
+        })
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JBigInteger<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JBigInteger<'a, 'b>> for JObject<'a> {
+    fn from(other: JBigInteger<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `java.math.BigDecimal`. Provides
+/// methods to get the unscaled value and scale, and, with the
+/// `rust_decimal` and `bigdecimal` features, to convert to and from
+/// `rust_decimal::Decimal` and `bigdecimal::BigDecimal`.
+///
+/// The value of a `java.math.BigDecimal` is `unscaled * 10^-scale`.
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JBigDecimal<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    unscaled_value: JMethodID<'a>,
+    scale: JMethodID<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JBigDecimal<'a, 'b> {
+    /// Create a [`JBigDecimal`] from the environment and an object. This looks
+    /// up the necessary class and method IDs to call all of the methods on it
+    /// so that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/math/BigDecimal")?);
+        Self::from_env_impl(env, obj, class)
+    }
+
+    /// Create a [`JBigDecimal`] which wraps a new `java.math.BigDecimal`
+    /// created from an unscaled value and a scale.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `unscaled` - Unscaled value.
+    /// * `scale` - Number of digits to the right of the decimal point.
+    pub fn new(env: &'b JNIEnv<'a>, unscaled: &JBigInteger<'a, 'b>, scale: i32) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/math/BigDecimal")?);
+        let obj = env.new_object(
+            &class,
+            "(Ljava/math/BigInteger;I)V",
+            &[unscaled.internal.into(), scale.into()],
+        )?;
+        Self::from_env_impl(env, obj, class)
+    }
+
+    /// Get the unscaled value by calling
+    /// `java.math.BigDecimal.unscaledValue()`.
+    pub fn unscaled_value(&self) -> Result<JBigInteger<'a, 'b>> {
+        let obj = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.unscaled_value,
+                JavaType::Object("java/math/BigInteger".into()),
+                &[],
+            )?
+            .l()?;
+        JBigInteger::from_env(self.env, obj)
+    }
+
+    /// Get the scale by calling `java.math.BigDecimal.scale()`.
+    pub fn scale(&self) -> Result<i32> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.scale,
+                JavaType::Primitive(Primitive::Int),
+                &[],
+            )?
+            .i()
+    }
+
+    fn from_env_impl(
+        env: &'b JNIEnv<'a>,
+        obj: JObject<'a>,
+        class: AutoLocal<'a, 'b>,
+    ) -> Result<Self> {
+        let unscaled_value =
+            env.get_method_id(&class, "unscaledValue", "()Ljava/math/BigInteger;")?;
+        let scale = env.get_method_id(&class, "scale", "()I")?;
+        Ok(Self {
+            internal: obj,
+            unscaled_value,
+            scale,
+            env,
+        })
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JBigDecimal<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JBigDecimal<'a, 'b>> for JObject<'a> {
+    fn from(other: JBigDecimal<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+// Sign-extends a big-endian two's-complement number to N bytes, or returns
+// None if it doesn't fit. Redundant sign bytes are stripped first, so
+// non-minimal encodings are accepted.
+pub(crate) fn sign_extend<const N: usize>(bytes: &[u8]) -> Option<[u8; N]> {
+    let fill = match bytes.first() {
+        Some(b) if *b & 0x80 != 0 => 0xff,
+        _ => 0x00,
+    };
+    let mut bytes = bytes;
+    while bytes.len() > 1 && bytes[0] == fill && (bytes[1] & 0x80 != 0) == (fill == 0xff) {
+        bytes = &bytes[1..];
+    }
+    if bytes.len() > N {
+        return None;
+    }
+    let mut result = [fill; N];
+    result[N - bytes.len()..].copy_from_slice(bytes);
+    Some(result)
+}
+
+#[cfg(feature = "num-bigint")]
+mod num_bigint_support {
+    use super::JBigInteger;
+    use jni::{errors::Result, JNIEnv};
+    use num_bigint::BigInt;
+
+    impl<'a: 'b, 'b> JBigInteger<'a, 'b> {
+        /// Create a [`JBigInteger`] which wraps a new `java.math.BigInteger`
+        /// created from a given [`BigInt`].
+        ///
+        /// # Arguments
+        ///
+        /// * `env` - Java environment to use.
+        /// * `value` - Value to convert into a `java.math.BigInteger`.
+        pub fn from_big_int(env: &'b JNIEnv<'a>, value: &BigInt) -> Result<Self> {
+            Self::from_bytes(env, &value.to_signed_bytes_be())
+        }
+
+        /// Convert the `java.math.BigInteger` into a [`BigInt`].
+        pub fn as_big_int(&self) -> Result<BigInt> {
+            Ok(BigInt::from_signed_bytes_be(&self.to_bytes()?))
+        }
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal_support {
+    use super::{JBigDecimal, JBigInteger};
+    use crate::exceptions::throw_error;
+    use jni::{errors::Result, JNIEnv};
+    use rust_decimal::Decimal;
+    use std::convert::TryFrom;
+
+    impl<'a: 'b, 'b> JBigDecimal<'a, 'b> {
+        /// Create a [`JBigDecimal`] which wraps a new `java.math.BigDecimal`
+        /// created from a given [`Decimal`].
+        ///
+        /// # Arguments
+        ///
+        /// * `env` - Java environment to use.
+        /// * `value` - Value to convert into a `java.math.BigDecimal`.
+        pub fn from_decimal(env: &'b JNIEnv<'a>, value: Decimal) -> Result<Self> {
+            let unscaled = JBigInteger::from_i128(env, value.mantissa())?;
+            let _auto_local = env.auto_local(unscaled.internal);
+            Self::new(env, &unscaled, value.scale() as i32)
+        }
+
+        /// Convert the `java.math.BigDecimal` into a [`Decimal`]. Trailing
+        /// zeros are removed if necessary to fit the value into a
+        /// [`Decimal`]. If the value is out of range or has too many
+        /// significant digits after the decimal point, a
+        /// `java.lang.ArithmeticException` is thrown rather than rounding.
+        pub fn as_decimal(&self) -> Result<Decimal> {
+            let unscaled = self.unscaled_value()?;
+            let _auto_local = self.env.auto_local(unscaled.internal);
+            let mut mantissa = unscaled.as_i128()?;
+            if mantissa == 0 {
+                return Ok(Decimal::ZERO);
+            }
+            let mut scale = self.scale()? as i64;
+
+            if scale < 0 {
+                let factor = u32::try_from(-scale)
+                    .ok()
+                    .and_then(|exp| 10i128.checked_pow(exp));
+                mantissa = match factor.and_then(|factor| mantissa.checked_mul(factor)) {
+                    Some(mantissa) => mantissa,
+                    None => return self.out_of_range(),
+                };
+                scale = 0;
+            }
+            // A non-zero i128 has at most 38 trailing zeros, so this stops long
+            // before the scale does.
+            while scale > Decimal::MAX_SCALE as i64 && mantissa % 10 == 0 {
+                mantissa /= 10;
+                scale -= 1;
+            }
+
+            let scale = match u32::try_from(scale) {
+                Ok(scale) => scale,
+                Err(_) => return self.out_of_range(),
+            };
+            match Decimal::try_from_i128_with_scale(mantissa, scale) {
+                Ok(value) => Ok(value),
+                Err(_) => self.out_of_range(),
+            }
+        }
+
+        fn out_of_range<T>(&self) -> Result<T> {
+            throw_error(
+                self.env,
+                "java/lang/ArithmeticException",
+                "BigDecimal cannot be represented as Decimal without loss of precision",
+            )
+        }
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+mod bigdecimal_support {
+    use super::{JBigDecimal, JBigInteger};
+    use crate::exceptions::throw_error;
+    use bigdecimal::{num_bigint::BigInt, BigDecimal};
+    use jni::{errors::Result, JNIEnv};
+    use std::convert::TryFrom;
+
+    impl<'a: 'b, 'b> JBigDecimal<'a, 'b> {
+        /// Create a [`JBigDecimal`] which wraps a new `java.math.BigDecimal`
+        /// created from a given [`BigDecimal`]. If the scale does not fit in
+        /// a Java `int`, a `java.lang.ArithmeticException` is thrown.
+        ///
+        /// # Arguments
+        ///
+        /// * `env` - Java environment to use.
+        /// * `value` - Value to convert into a `java.math.BigDecimal`.
+        pub fn from_big_decimal(env: &'b JNIEnv<'a>, value: &BigDecimal) -> Result<Self> {
+            let (unscaled, scale) = value.as_bigint_and_exponent();
+            let scale = match i32::try_from(scale) {
+                Ok(scale) => scale,
+                Err(_) => {
+                    return throw_error(
+                        env,
+                        "java/lang/ArithmeticException",
+                        "BigDecimal scale out of range",
+                    )
+                }
+            };
+            let unscaled = JBigInteger::from_bytes(env, &unscaled.to_signed_bytes_be())?;
+            let _auto_local = env.auto_local(unscaled.internal);
+            Self::new(env, &unscaled, scale)
+        }
+
+        /// Convert the `java.math.BigDecimal` into a [`BigDecimal`].
+        pub fn as_big_decimal(&self) -> Result<BigDecimal> {
+            let unscaled = self.unscaled_value()?;
+            let _auto_local = self.env.auto_local(unscaled.internal);
+            let unscaled = BigInt::from_signed_bytes_be(&unscaled.to_bytes()?);
+            Ok(BigDecimal::new(unscaled, self.scale()? as i64))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{sign_extend, JBigDecimal, JBigInteger};
+    use crate::test_utils::{self, assert_exception, to_string};
+    use jni::JNIEnv;
+
+    fn big_integer<'a: 'b, 'b>(env: &'b JNIEnv<'a>, s: &str) -> JBigInteger<'a, 'b> {
+        let s = env.new_string(s).unwrap();
+        let obj = env
+            .new_object("java/math/BigInteger", "(Ljava/lang/String;)V", &[s.into()])
+            .unwrap();
+        JBigInteger::from_env(env, obj).unwrap()
+    }
+
+    #[test]
+    fn test_i128() {
+        test_utils::JVM_ENV.with(|env| {
+            for value in &[
+                0,
+                1,
+                -1,
+                127,
+                128,
+                -128,
+                -129,
+                i64::MIN as i128,
+                i128::MIN,
+                i128::MAX,
+            ] {
+                let obj = JBigInteger::from_i128(env, *value).unwrap();
+                assert_eq!(to_string(env, *obj), value.to_string());
+                assert_eq!(obj.as_i128().unwrap(), *value);
+            }
+
+            let obj = big_integer(env, "170141183460469231731687303715884105728");
+            assert_exception(env, obj.as_i128(), "java/lang/ArithmeticException");
+            let obj = big_integer(env, "-170141183460469231731687303715884105729");
+            assert_exception(env, obj.as_i128(), "java/lang/ArithmeticException");
+        });
+    }
+
+    #[test]
+    fn test_sign_extend() {
+        assert_eq!(sign_extend::<2>(&[]), Some([0x00, 0x00]));
+        assert_eq!(sign_extend::<2>(&[0x80]), Some([0xff, 0x80]));
+        assert_eq!(sign_extend::<1>(&[0x00, 0x7f]), Some([0x7f]));
+        assert_eq!(sign_extend::<1>(&[0xff, 0xff, 0x80]), Some([0x80]));
+        assert_eq!(sign_extend::<2>(&[0x00, 0x00, 0x80]), Some([0x00, 0x80]));
+        assert_eq!(sign_extend::<1>(&[0x00, 0x80]), None);
+        assert_eq!(sign_extend::<1>(&[0xff, 0x7f]), None);
+        assert_eq!(sign_extend::<1>(&[0x01, 0x00]), None);
+    }
+
+    #[test]
+    fn test_u128() {
+        test_utils::JVM_ENV.with(|env| {
+            for value in &[0, 1, 255, 1 << 127, u128::MAX] {
+                let obj = JBigInteger::from_u128(env, *value).unwrap();
+                assert_eq!(to_string(env, *obj), value.to_string());
+                assert_eq!(obj.as_u128().unwrap(), *value);
+            }
+
+            let obj = big_integer(env, "340282366920938463463374607431768211456");
+            assert_exception(env, obj.as_u128(), "java/lang/ArithmeticException");
+            let obj = big_integer(env, "-1");
+            assert_exception(env, obj.as_u128(), "java/lang/ArithmeticException");
+        });
+    }
+
+    #[test]
+    fn test_big_decimal() {
+        test_utils::JVM_ENV.with(|env| {
+            let unscaled = JBigInteger::from_i128(env, -12345).unwrap();
+            let obj = JBigDecimal::new(env, &unscaled, 3).unwrap();
+            assert_eq!(to_string(env, *obj), "-12.345");
+            assert_eq!(obj.scale().unwrap(), 3);
+            assert_eq!(obj.unscaled_value().unwrap().as_i128().unwrap(), -12345);
+        });
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn test_big_int() {
+        use num_bigint::BigInt;
+
+        test_utils::JVM_ENV.with(|env| {
+            for s in &[
+                "0",
+                "-1",
+                "123456789012345678901234567890123456789012345678901234567890",
+            ] {
+                let value: BigInt = s.parse().unwrap();
+                let obj = JBigInteger::from_big_int(env, &value).unwrap();
+                assert_eq!(to_string(env, *obj), *s);
+                assert_eq!(obj.as_big_int().unwrap(), value);
+            }
+        });
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_decimal() {
+        use rust_decimal::Decimal;
+
+        test_utils::JVM_ENV.with(|env| {
+            for s in &["0", "-12.345", "79228162514264337593543950335", "0.0001"] {
+                let value: Decimal = s.parse().unwrap();
+                let obj = JBigDecimal::from_decimal(env, value).unwrap();
+                assert_eq!(to_string(env, *obj), *s);
+                assert_eq!(obj.as_decimal().unwrap(), value);
+            }
+
+            let unscaled = JBigInteger::from_i128(env, 15).unwrap();
+            let obj = JBigDecimal::new(env, &unscaled, -2).unwrap();
+            assert_eq!(obj.as_decimal().unwrap(), Decimal::from(1500));
+
+            let unscaled = JBigInteger::from_i128(env, 1000).unwrap();
+            let obj = JBigDecimal::new(env, &unscaled, 30).unwrap();
+            assert_eq!(
+                obj.as_decimal().unwrap(),
+                "0.000000000000000000000000001".parse().unwrap()
+            );
+
+            let unscaled = JBigInteger::from_i128(env, 1).unwrap();
+            let obj = JBigDecimal::new(env, &unscaled, 29).unwrap();
+            assert_exception(env, obj.as_decimal(), "java/lang/ArithmeticException");
+
+            let unscaled = JBigInteger::from_i128(env, 1).unwrap();
+            let obj = JBigDecimal::new(env, &unscaled, -29).unwrap();
+            assert_exception(env, obj.as_decimal(), "java/lang/ArithmeticException");
+
+            let unscaled = JBigInteger::from_i128(env, 1).unwrap();
+            let obj = JBigDecimal::new(env, &unscaled, i32::MIN).unwrap();
+            assert_exception(env, obj.as_decimal(), "java/lang/ArithmeticException");
+
+            let unscaled = JBigInteger::from_i128(env, 10).unwrap();
+            let obj = JBigDecimal::new(env, &unscaled, i32::MAX).unwrap();
+            assert_exception(env, obj.as_decimal(), "java/lang/ArithmeticException");
+
+            // Zero is in range whatever the scale.
+            let unscaled = JBigInteger::from_i128(env, 0).unwrap();
+            for scale in [i32::MIN, i32::MAX] {
+                let obj = JBigDecimal::new(env, &unscaled, scale).unwrap();
+                assert_eq!(obj.as_decimal().unwrap(), Decimal::ZERO);
+            }
+        });
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_bigdecimal() {
+        use bigdecimal::BigDecimal;
+
+        test_utils::JVM_ENV.with(|env| {
+            for s in &[
+                "0",
+                "-12.345",
+                "1.23456789012345678901234567890123456789E+50",
+            ] {
+                let value: BigDecimal = s.parse().unwrap();
+                let obj = JBigDecimal::from_big_decimal(env, &value).unwrap();
+                assert_eq!(obj.as_big_decimal().unwrap(), value);
+            }
+        });
+    }
+}
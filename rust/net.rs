@@ -0,0 +1,151 @@
+use crate::{
+    exceptions::throw_error,
+    primitives::check_instance_of,
+    strings::{new_string, to_string},
+};
+use jni::{errors::Result, objects::JObject, JNIEnv};
+use url::Url;
+
+/// Convert a `java.net.URI` into a [`Url`].
+///
+/// The URI is converted with `toASCIIString()` and parsed with
+/// [`Url::parse()`]. Relative URIs, and any other URIs which
+/// [`Url::parse()`] rejects, cause a `java.lang.IllegalArgumentException` to
+/// be thrown. If the object is not a `java.net.URI`, a
+/// `java.lang.ClassCastException` is thrown.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - `java.net.URI` to convert.
+pub fn uri_to_url<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Url> {
+    check_instance_of(env, obj, "java/net/URI")?;
+    parse_url(env, obj, "toASCIIString")
+}
+
+/// Create a new `java.net.URI` from a [`Url`] using
+/// `java.net.URI.create()`.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new object.
+/// * `url` - URL to convert.
+pub fn url_to_uri<'a: 'b, 'b>(env: &'b JNIEnv<'a>, url: &Url) -> Result<JObject<'a>> {
+    let s = env.auto_local(JObject::from(new_string(env, url.as_str())?));
+    env.call_static_method(
+        "java/net/URI",
+        "create",
+        "(Ljava/lang/String;)Ljava/net/URI;",
+        &[s.as_obj().into()],
+    )?
+    .l()
+}
+
+/// Convert a `java.net.URL` into a [`Url`].
+///
+/// The URL is converted with `toExternalForm()` and parsed with
+/// [`Url::parse()`]. If parsing fails, a `java.lang.IllegalArgumentException`
+/// is thrown. If the object is not a `java.net.URL`, a
+/// `java.lang.ClassCastException` is thrown.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - `java.net.URL` to convert.
+pub fn java_url_to_url<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Url> {
+    check_instance_of(env, obj, "java/net/URL")?;
+    parse_url(env, obj, "toExternalForm")
+}
+
+/// Create a new `java.net.URL` from a [`Url`].
+///
+/// If Java does not have a protocol handler for the URL's scheme, the
+/// `java.net.MalformedURLException` thrown by the constructor is left pending
+/// and [`Error::JavaException`](jni::errors::Error::JavaException) is returned.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new object.
+/// * `url` - URL to convert.
+pub fn url_to_java_url<'a: 'b, 'b>(env: &'b JNIEnv<'a>, url: &Url) -> Result<JObject<'a>> {
+    let s = env.auto_local(JObject::from(new_string(env, url.as_str())?));
+    env.new_object(
+        "java/net/URL",
+        "(Ljava/lang/String;)V",
+        &[s.as_obj().into()],
+    )
+}
+
+fn parse_url<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>, getter: &str) -> Result<Url> {
+    let s = env.auto_local(
+        env.call_method(obj, getter, "()Ljava/lang/String;", &[])?
+            .l()?,
+    );
+    let s = to_string(env, s.as_obj())?;
+    match Url::parse(&s) {
+        Ok(url) => Ok(url),
+        Err(err) => throw_error(
+            env,
+            "java/lang/IllegalArgumentException",
+            format!("Invalid URL {}: {}", s, err),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::{self, assert_exception};
+    use url::Url;
+
+    const TESTS: &[&str] = &[
+        "https://example.com/",
+        "https://user@example.com:8443/a%20b/c?q=1&r=%C3%BC#frag",
+        "file:///tmp/file.txt",
+    ];
+
+    #[test]
+    fn test_uri() {
+        test_utils::JVM_ENV.with(|env| {
+            for test in TESTS {
+                let url = Url::parse(test).unwrap();
+                let obj = super::url_to_uri(env, &url).unwrap();
+                assert!(env.is_instance_of(obj, "java/net/URI").unwrap());
+                assert_eq!(super::uri_to_url(env, obj).unwrap(), url);
+            }
+        });
+    }
+
+    #[test]
+    fn test_java_url() {
+        test_utils::JVM_ENV.with(|env| {
+            for test in TESTS {
+                let url = Url::parse(test).unwrap();
+                let obj = super::url_to_java_url(env, &url).unwrap();
+                assert!(env.is_instance_of(obj, "java/net/URL").unwrap());
+                assert_eq!(super::java_url_to_url(env, obj).unwrap(), url);
+            }
+        });
+    }
+
+    #[test]
+    fn test_relative_uri() {
+        test_utils::JVM_ENV.with(|env| {
+            let s = env.new_string("relative/path").unwrap();
+            let obj = env
+                .call_static_method(
+                    "java/net/URI",
+                    "create",
+                    "(Ljava/lang/String;)Ljava/net/URI;",
+                    &[s.into()],
+                )
+                .unwrap()
+                .l()
+                .unwrap();
+            assert_exception(
+                env,
+                super::uri_to_url(env, obj),
+                "java/lang/IllegalArgumentException",
+            );
+        });
+    }
+}
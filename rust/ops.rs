@@ -1,7 +1,26 @@
-use ::jni::{errors::Result, objects::JObject, JNIEnv};
-use std::sync::{Arc, Mutex};
+use crate::{
+    exceptions::ThrowableError,
+    primitives::{box_primitive, JavaPrimitive},
+    signature::JavaSignature,
+};
+use ::jni::{
+    errors::Result,
+    objects::{JClass, JObject},
+    sys::{jdouble, jint, jlong, jsize},
+    JNIEnv,
+};
+use std::{
+    cmp::Ordering,
+    sync::{Arc, Mutex},
+};
 
 macro_rules! define_fn_adapter {
+    (@adapter [$adapter:ident] $default:ident) => {
+        $adapter
+    };
+    (@adapter [] $default:ident) => {
+        $default
+    };
     (
         fn_once: $fo:ident,
         fn_once_local: $fol:ident,
@@ -18,19 +37,24 @@ macro_rules! define_fn_adapter {
         doc_fn_once: $dfo:literal,
         doc_fn: $df:literal,
         doc_noop: $dnoop:literal,
+        $(doc_error: $derr:literal,)?
+        $(generics: <$($gen:ident: $bound:path),*>,)?
+        $(adapters: $ao:ident, $am:ident, $af:ident,)?
         signature: $closure_name:ident: impl for<'c, 'd> Fn$args:tt -> $ret:ty,
         closure: $closure:expr,
     ) => {
-        fn $foi<'a: 'b, 'b>(
+        fn $foi<'a: 'b, 'b $($(, $gen: $bound)*)?>(
             env: &'b JNIEnv<'a>,
             $closure_name: impl for<'c, 'd> FnOnce$args -> $ret + 'static,
             local: bool,
         ) -> Result<JObject<'a>> {
-            let adapter = env.auto_local(fn_once_adapter(env, $closure, local)?);
+            let adapter = env.auto_local(define_fn_adapter!(@adapter [$($ao)?] fn_once_adapter)(
+                env, $closure, local,
+            )?);
             let class = env.auto_local(env.find_class($ic)?);
             env.new_object(
                 &class,
-                "(Lio/github/gedgygedgy/rust/ops/FnAdapter;)V",
+                crate::sig!((FnAdapter) -> ()),
                 &[(&adapter).into()],
             )
         }
@@ -45,13 +69,14 @@ macro_rules! define_fn_adapter {
         #[doc = "\n\n"]
         #[doc = "If the closure panics, the unwind will be caught and thrown "]
         #[doc = "as an `io.github.gedgygedgy.rust.panic.PanicException`."]
+        $(#[doc = "\n\n"] #[doc = $derr])?
         #[doc = "\n\n"]
         #[doc = "It is safe to call the object's `"]
         #[doc = $dm]
         #[doc = "` method recursively, but the second call will "]
         #[doc = $dnoop]
         #[doc = "."]
-        pub fn $fo<'a: 'b, 'b>(
+        pub fn $fo<'a: 'b, 'b $($(, $gen: $bound)*)?>(
             env: &'b JNIEnv<'a>,
             f: impl for<'c, 'd> FnOnce$args -> $ret + Send + 'static,
         ) -> Result<JObject<'a>> {
@@ -67,23 +92,25 @@ macro_rules! define_fn_adapter {
         #[doc = "than its origin thread will result in an "]
         #[doc = "`io.github.gedgygedgy.rust.thread.LocalThreadException` "]
         #[doc = "being thrown."]
-        pub fn $fol<'a: 'b, 'b>(
+        pub fn $fol<'a: 'b, 'b $($(, $gen: $bound)*)?>(
             env: &'b JNIEnv<'a>,
             f: impl for<'c, 'd> FnOnce$args -> $ret + 'static,
         ) -> Result<JObject<'a>> {
             $foi(env, f, true)
         }
 
-        fn $fmi<'a: 'b, 'b>(
+        fn $fmi<'a: 'b, 'b $($(, $gen: $bound)*)?>(
             env: &'b JNIEnv<'a>,
             mut $closure_name: impl for<'c, 'd> FnMut$args -> $ret + 'static,
             local: bool,
         ) -> Result<JObject<'a>> {
-            let adapter = env.auto_local(fn_mut_adapter(env, $closure, local)?);
+            let adapter = env.auto_local(define_fn_adapter!(@adapter [$($am)?] fn_mut_adapter)(
+                env, $closure, local,
+            )?);
             let class = env.auto_local(env.find_class($ic)?);
             env.new_object(
                 &class,
-                "(Lio/github/gedgygedgy/rust/ops/FnAdapter;)V",
+                crate::sig!((FnAdapter) -> ()),
                 &[(&adapter).into()],
             )
         }
@@ -98,6 +125,7 @@ macro_rules! define_fn_adapter {
         #[doc = "\n\n"]
         #[doc = "If the closure panics, the unwind will be caught and thrown "]
         #[doc = "as an `io.github.gedgygedgy.rust.panic.PanicException`."]
+        $(#[doc = "\n\n"] #[doc = $derr])?
         #[doc = "\n\n"]
         #[doc = "Unlike [`"]
         #[doc = $df]
@@ -109,7 +137,7 @@ macro_rules! define_fn_adapter {
         #[doc = "internal [`Mutex`], so calling `"]
         #[doc = $dm]
         #[doc = "` recursively will result in a deadlock."]
-        pub fn $fm<'a: 'b, 'b>(
+        pub fn $fm<'a: 'b, 'b $($(, $gen: $bound)*)?>(
             env: &'b JNIEnv<'a>,
             f: impl for<'c, 'd> FnMut$args -> $ret + Send + 'static,
         ) -> Result<JObject<'a>> {
@@ -125,23 +153,25 @@ macro_rules! define_fn_adapter {
         #[doc = "than its origin thread will result in an "]
         #[doc = "`io.github.gedgygedgy.rust.thread.LocalThreadException` "]
         #[doc = "being thrown."]
-        pub fn $fml<'a: 'b, 'b>(
+        pub fn $fml<'a: 'b, 'b $($(, $gen: $bound)*)?>(
             env: &'b JNIEnv<'a>,
             f: impl for<'c, 'd> FnMut$args -> $ret + 'static,
         ) -> Result<JObject<'a>> {
             $fmi(env, f, true)
         }
 
-        fn $fi<'a: 'b, 'b>(
+        fn $fi<'a: 'b, 'b $($(, $gen: $bound)*)?>(
             env: &'b JNIEnv<'a>,
             $closure_name: impl for<'c, 'd> Fn$args -> $ret + 'static,
             local: bool,
         ) -> Result<JObject<'a>> {
-            let adapter = env.auto_local(fn_adapter(env, $closure, local)?);
+            let adapter = env.auto_local(define_fn_adapter!(@adapter [$($af)?] fn_adapter)(
+                env, $closure, local,
+            )?);
             let class = env.auto_local(env.find_class($ic)?);
             env.new_object(
                 &class,
-                "(Lio/github/gedgygedgy/rust/ops/FnAdapter;)V",
+                crate::sig!((FnAdapter) -> ()),
                 &[(&adapter).into()],
             )
         }
@@ -156,11 +186,12 @@ macro_rules! define_fn_adapter {
         #[doc = "\n\n"]
         #[doc = "If the closure panics, the unwind will be caught and thrown "]
         #[doc = "as an `io.github.gedgygedgy.rust.panic.PanicException`."]
+        $(#[doc = "\n\n"] #[doc = $derr])?
         #[doc = "\n\n"]
         #[doc = "It is safe to call the object's `"]
         #[doc = $dm]
         #[doc = "` method recursively."]
-        pub fn $f<'a: 'b, 'b>(
+        pub fn $f<'a: 'b, 'b $($(, $gen: $bound)*)?>(
             env: &'b JNIEnv<'a>,
             f: impl for<'c, 'd> Fn$args -> $ret + Send + Sync + 'static,
         ) -> Result<JObject<'a>> {
@@ -176,7 +207,7 @@ macro_rules! define_fn_adapter {
         #[doc = "than its origin thread will result in an "]
         #[doc = "`io.github.gedgygedgy.rust.thread.LocalThreadException` "]
         #[doc = "being thrown."]
-        pub fn $fl<'a: 'b, 'b>(
+        pub fn $fl<'a: 'b, 'b $($(, $gen: $bound)*)?>(
             env: &'b JNIEnv<'a>,
             f: impl for<'c, 'd> Fn$args -> $ret + 'static,
         ) -> Result<JObject<'a>> {
@@ -252,23 +283,561 @@ define_fn_adapter! {
     },
 }
 
+define_fn_adapter! {
+    fn_once: fn_once_supplier,
+    fn_once_local: fn_once_supplier_local,
+    fn_once_internal: fn_once_supplier_internal,
+    fn_mut: fn_mut_supplier,
+    fn_mut_local: fn_mut_supplier_local,
+    fn_mut_internal: fn_mut_supplier_internal,
+    fn: fn_supplier,
+    fn_local: fn_supplier_local,
+    fn_internal: fn_supplier_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnSupplierImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnSupplier",
+    doc_method: "get()",
+    doc_fn_once: "fn_once_supplier",
+    doc_fn: "fn_supplier",
+    doc_noop: "return `null`",
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>) -> JObject<'c>,
+    closure: move |env, _obj1, obj2, _arg1, _arg2| {
+        f(env, obj2)
+    },
+}
+
+define_fn_adapter! {
+    fn_once: fn_once_consumer,
+    fn_once_local: fn_once_consumer_local,
+    fn_once_internal: fn_once_consumer_internal,
+    fn_mut: fn_mut_consumer,
+    fn_mut_local: fn_mut_consumer_local,
+    fn_mut_internal: fn_mut_consumer_internal,
+    fn: fn_consumer,
+    fn_local: fn_consumer_local,
+    fn_internal: fn_consumer_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnConsumerImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnConsumer",
+    doc_method: "accept()",
+    doc_fn_once: "fn_once_consumer",
+    doc_fn: "fn_consumer",
+    doc_noop: "be a no-op",
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, JObject<'c>) -> (),
+    closure: move |env, _obj1, obj2, arg1, _arg2| {
+        f(env, obj2, arg1);
+        JObject::null()
+    },
+}
+
+define_fn_adapter! {
+    fn_once: fn_once_bi_consumer,
+    fn_once_local: fn_once_bi_consumer_local,
+    fn_once_internal: fn_once_bi_consumer_internal,
+    fn_mut: fn_mut_bi_consumer,
+    fn_mut_local: fn_mut_bi_consumer_local,
+    fn_mut_internal: fn_mut_bi_consumer_internal,
+    fn: fn_bi_consumer,
+    fn_local: fn_bi_consumer_local,
+    fn_internal: fn_bi_consumer_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnBiConsumerImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnBiConsumer",
+    doc_method: "accept()",
+    doc_fn_once: "fn_once_bi_consumer",
+    doc_fn: "fn_bi_consumer",
+    doc_noop: "be a no-op",
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, JObject<'c>, JObject<'c>) -> (),
+    closure: move |env, _obj1, obj2, arg1, arg2| {
+        f(env, obj2, arg1, arg2);
+        JObject::null()
+    },
+}
+
+define_fn_adapter! {
+    fn_once: fn_once_predicate,
+    fn_once_local: fn_once_predicate_local,
+    fn_once_internal: fn_once_predicate_internal,
+    fn_mut: fn_mut_predicate,
+    fn_mut_local: fn_mut_predicate_local,
+    fn_mut_internal: fn_mut_predicate_internal,
+    fn: fn_predicate,
+    fn_local: fn_predicate_local,
+    fn_internal: fn_predicate_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnPredicateImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnPredicate",
+    doc_method: "test()",
+    doc_fn_once: "fn_once_predicate",
+    doc_fn: "fn_predicate",
+    doc_noop: "return `false`",
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, JObject<'c>) -> bool,
+    closure: move |env, _obj1, obj2, arg1, _arg2| {
+        box_result(env, f(env, obj2, arg1))
+    },
+}
+
+define_fn_adapter! {
+    fn_once: fn_once_comparator,
+    fn_once_local: fn_once_comparator_local,
+    fn_once_internal: fn_once_comparator_internal,
+    fn_mut: fn_mut_comparator,
+    fn_mut_local: fn_mut_comparator_local,
+    fn_mut_internal: fn_mut_comparator_internal,
+    fn: fn_comparator,
+    fn_local: fn_comparator_local,
+    fn_internal: fn_comparator_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnComparatorImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnComparator",
+    doc_method: "compare()",
+    doc_fn_once: "fn_once_comparator",
+    doc_fn: "fn_comparator",
+    doc_noop: "return `0`",
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, JObject<'c>, JObject<'c>) -> Ordering,
+    closure: move |env, _obj1, obj2, arg1, arg2| {
+        box_result(env, f(env, obj2, arg1, arg2) as i32)
+    },
+}
+
+define_fn_adapter! {
+    fn_once: fn_once_int_unary_operator,
+    fn_once_local: fn_once_int_unary_operator_local,
+    fn_once_internal: fn_once_int_unary_operator_internal,
+    fn_mut: fn_mut_int_unary_operator,
+    fn_mut_local: fn_mut_int_unary_operator_local,
+    fn_mut_internal: fn_mut_int_unary_operator_internal,
+    fn: fn_int_unary_operator,
+    fn_local: fn_int_unary_operator_local,
+    fn_internal: fn_int_unary_operator_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnIntUnaryOperatorImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnIntUnaryOperator",
+    doc_method: "applyAsInt()",
+    doc_fn_once: "fn_once_int_unary_operator",
+    doc_fn: "fn_int_unary_operator",
+    doc_noop: "return `0`",
+    adapters: fn_once_int_adapter, fn_mut_int_adapter, fn_int_adapter,
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, i32) -> i32,
+    closure: move |env, obj2, operand| f(env, obj2, operand),
+}
+
+define_fn_adapter! {
+    fn_once: fn_once_to_long_function,
+    fn_once_local: fn_once_to_long_function_local,
+    fn_once_internal: fn_once_to_long_function_internal,
+    fn_mut: fn_mut_to_long_function,
+    fn_mut_local: fn_mut_to_long_function_local,
+    fn_mut_internal: fn_mut_to_long_function_internal,
+    fn: fn_to_long_function,
+    fn_local: fn_to_long_function_local,
+    fn_internal: fn_to_long_function_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnToLongFunctionImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnToLongFunction",
+    doc_method: "applyAsLong()",
+    doc_fn_once: "fn_once_to_long_function",
+    doc_fn: "fn_to_long_function",
+    doc_noop: "return `0`",
+    adapters: fn_once_long_adapter, fn_mut_long_adapter, fn_long_adapter,
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, JObject<'c>) -> i64,
+    closure: move |env, obj2, value| f(env, obj2, value),
+}
+
+define_fn_adapter! {
+    fn_once: fn_once_double_binary_operator,
+    fn_once_local: fn_once_double_binary_operator_local,
+    fn_once_internal: fn_once_double_binary_operator_internal,
+    fn_mut: fn_mut_double_binary_operator,
+    fn_mut_local: fn_mut_double_binary_operator_local,
+    fn_mut_internal: fn_mut_double_binary_operator_internal,
+    fn: fn_double_binary_operator,
+    fn_local: fn_double_binary_operator_local,
+    fn_internal: fn_double_binary_operator_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnDoubleBinaryOperatorImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnDoubleBinaryOperator",
+    doc_method: "applyAsDouble()",
+    doc_fn_once: "fn_once_double_binary_operator",
+    doc_fn: "fn_double_binary_operator",
+    doc_noop: "return `0.0`",
+    adapters: fn_once_double_adapter, fn_mut_double_adapter, fn_double_adapter,
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, f64, f64) -> f64,
+    closure: move |env, obj2, left, right| f(env, obj2, left, right),
+}
+
+define_fn_adapter! {
+    fn_once: fn_once_callable,
+    fn_once_local: fn_once_callable_local,
+    fn_once_internal: fn_once_callable_internal,
+    fn_mut: fn_mut_callable,
+    fn_mut_local: fn_mut_callable_local,
+    fn_mut_internal: fn_mut_callable_internal,
+    fn: fn_callable,
+    fn_local: fn_callable_local,
+    fn_internal: fn_callable_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnCallableImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnCallable",
+    doc_method: "call()",
+    doc_fn_once: "fn_once_callable",
+    doc_fn: "fn_callable",
+    doc_noop: "return `null`",
+    doc_error: "If the closure returns an error, it is thrown from `call()` with [`ThrowableError::throw()`].",
+    generics: <E: ThrowableError>,
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>) -> std::result::Result<JObject<'c>, E>,
+    closure: move |env, _obj1, obj2, _arg1, _arg2| {
+        throw_result(env, f(env, obj2))
+    },
+}
+
+define_fn_adapter! {
+    fn_once: try_fn_once_runnable,
+    fn_once_local: try_fn_once_runnable_local,
+    fn_once_internal: try_fn_once_runnable_internal,
+    fn_mut: try_fn_mut_runnable,
+    fn_mut_local: try_fn_mut_runnable_local,
+    fn_mut_internal: try_fn_mut_runnable_internal,
+    fn: try_fn_runnable,
+    fn_local: try_fn_runnable_local,
+    fn_internal: try_fn_runnable_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnRunnableImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnRunnable",
+    doc_method: "run()",
+    doc_fn_once: "try_fn_once_runnable",
+    doc_fn: "try_fn_runnable",
+    doc_noop: "be a no-op",
+    doc_error: "If the closure returns an error, it is thrown from `run()` with [`ThrowableError::throw()`].",
+    generics: <E: ThrowableError>,
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>) -> std::result::Result<(), E>,
+    closure: move |env, _obj1, obj2, _arg1, _arg2| {
+        throw_result(env, f(env, obj2).map(|()| JObject::null()))
+    },
+}
+
+define_fn_adapter! {
+    fn_once: try_fn_once_bi_function,
+    fn_once_local: try_fn_once_bi_function_local,
+    fn_once_internal: try_fn_once_bi_function_internal,
+    fn_mut: try_fn_mut_bi_function,
+    fn_mut_local: try_fn_mut_bi_function_local,
+    fn_mut_internal: try_fn_mut_bi_function_internal,
+    fn: try_fn_bi_function,
+    fn_local: try_fn_bi_function_local,
+    fn_internal: try_fn_bi_function_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnBiFunctionImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnBiFunction",
+    doc_method: "apply()",
+    doc_fn_once: "try_fn_once_bi_function",
+    doc_fn: "try_fn_bi_function",
+    doc_noop: "return `null`",
+    doc_error: "If the closure returns an error, it is thrown from `apply()` with [`ThrowableError::throw()`].",
+    generics: <E: ThrowableError>,
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, JObject<'c>, JObject<'c>) -> std::result::Result<JObject<'c>, E>,
+    closure: move |env, _obj1, obj2, arg1, arg2| {
+        throw_result(env, f(env, obj2, arg1, arg2))
+    },
+}
+
+define_fn_adapter! {
+    fn_once: try_fn_once_function,
+    fn_once_local: try_fn_once_function_local,
+    fn_once_internal: try_fn_once_function_internal,
+    fn_mut: try_fn_mut_function,
+    fn_mut_local: try_fn_mut_function_local,
+    fn_mut_internal: try_fn_mut_function_internal,
+    fn: try_fn_function,
+    fn_local: try_fn_function_local,
+    fn_internal: try_fn_function_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnFunctionImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnFunction",
+    doc_method: "apply()",
+    doc_fn_once: "try_fn_once_function",
+    doc_fn: "try_fn_function",
+    doc_noop: "return `null`",
+    doc_error: "If the closure returns an error, it is thrown from `apply()` with [`ThrowableError::throw()`].",
+    generics: <E: ThrowableError>,
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, JObject<'c>) -> std::result::Result<JObject<'c>, E>,
+    closure: move |env, _obj1, obj2, arg1, _arg2| {
+        throw_result(env, f(env, obj2, arg1))
+    },
+}
+
+define_fn_adapter! {
+    fn_once: try_fn_once_supplier,
+    fn_once_local: try_fn_once_supplier_local,
+    fn_once_internal: try_fn_once_supplier_internal,
+    fn_mut: try_fn_mut_supplier,
+    fn_mut_local: try_fn_mut_supplier_local,
+    fn_mut_internal: try_fn_mut_supplier_internal,
+    fn: try_fn_supplier,
+    fn_local: try_fn_supplier_local,
+    fn_internal: try_fn_supplier_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnSupplierImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnSupplier",
+    doc_method: "get()",
+    doc_fn_once: "try_fn_once_supplier",
+    doc_fn: "try_fn_supplier",
+    doc_noop: "return `null`",
+    doc_error: "If the closure returns an error, it is thrown from `get()` with [`ThrowableError::throw()`].",
+    generics: <E: ThrowableError>,
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>) -> std::result::Result<JObject<'c>, E>,
+    closure: move |env, _obj1, obj2, _arg1, _arg2| {
+        throw_result(env, f(env, obj2))
+    },
+}
+
+define_fn_adapter! {
+    fn_once: try_fn_once_consumer,
+    fn_once_local: try_fn_once_consumer_local,
+    fn_once_internal: try_fn_once_consumer_internal,
+    fn_mut: try_fn_mut_consumer,
+    fn_mut_local: try_fn_mut_consumer_local,
+    fn_mut_internal: try_fn_mut_consumer_internal,
+    fn: try_fn_consumer,
+    fn_local: try_fn_consumer_local,
+    fn_internal: try_fn_consumer_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnConsumerImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnConsumer",
+    doc_method: "accept()",
+    doc_fn_once: "try_fn_once_consumer",
+    doc_fn: "try_fn_consumer",
+    doc_noop: "be a no-op",
+    doc_error: "If the closure returns an error, it is thrown from `accept()` with [`ThrowableError::throw()`].",
+    generics: <E: ThrowableError>,
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, JObject<'c>) -> std::result::Result<(), E>,
+    closure: move |env, _obj1, obj2, arg1, _arg2| {
+        throw_result(env, f(env, obj2, arg1).map(|()| JObject::null()))
+    },
+}
+
+define_fn_adapter! {
+    fn_once: try_fn_once_bi_consumer,
+    fn_once_local: try_fn_once_bi_consumer_local,
+    fn_once_internal: try_fn_once_bi_consumer_internal,
+    fn_mut: try_fn_mut_bi_consumer,
+    fn_mut_local: try_fn_mut_bi_consumer_local,
+    fn_mut_internal: try_fn_mut_bi_consumer_internal,
+    fn: try_fn_bi_consumer,
+    fn_local: try_fn_bi_consumer_local,
+    fn_internal: try_fn_bi_consumer_internal,
+    impl_class: "io/github/gedgygedgy/rust/ops/FnBiConsumerImpl",
+    doc_class: "io.github.gedgygedgy.rust.ops.FnBiConsumer",
+    doc_method: "accept()",
+    doc_fn_once: "try_fn_once_bi_consumer",
+    doc_fn: "try_fn_bi_consumer",
+    doc_noop: "be a no-op",
+    doc_error: "If the closure returns an error, it is thrown from `accept()` with [`ThrowableError::throw()`].",
+    generics: <E: ThrowableError>,
+    signature: f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, JObject<'c>, JObject<'c>) -> std::result::Result<(), E>,
+    closure: move |env, _obj1, obj2, arg1, arg2| {
+        throw_result(env, f(env, obj2, arg1, arg2).map(|()| JObject::null()))
+    },
+}
+
+// Unwraps the return value of a closure for FnAdapter. If the closure returned
+// an error, it is thrown and null is returned.
+fn throw_result<'a: 'b, 'b, E: ThrowableError>(
+    env: &'b JNIEnv<'a>,
+    result: std::result::Result<JObject<'a>, E>,
+) -> JObject<'a> {
+    result.unwrap_or_else(|e| {
+        let _ = e.throw(env);
+        JObject::null()
+    })
+}
+
+// Boxes the return value of a closure for FnAdapter. If boxing fails, the
+// exception is left pending and null is returned.
+fn box_result<'a: 'b, 'b>(env: &'b JNIEnv<'a>, value: impl JavaPrimitive) -> JObject<'a> {
+    box_primitive(env, value).unwrap_or_else(|_| JObject::null())
+}
+
+fn proxy<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    interfaces: &[&str],
+    adapter: JObject<'a>,
+) -> Result<JObject<'a>> {
+    let adapter = env.auto_local(adapter);
+    let array = env.new_object_array(
+        interfaces.len() as jsize,
+        "java/lang/Class",
+        JObject::null(),
+    )?;
+    let array = env.auto_local(array);
+    for (i, interface) in interfaces.iter().enumerate() {
+        let class = env.auto_local(env.find_class(*interface)?);
+        env.set_object_array_element(array.as_obj().into_inner(), i as jsize, &class)?;
+    }
+
+    let class =
+        env.auto_local(env.find_class("io/github/gedgygedgy/rust/ops/FnInvocationHandler")?);
+    env.call_static_method(
+        &class,
+        "newProxy",
+        crate::sig!((Vec<JClass>, FnAdapter) -> JObject),
+        &[array.as_obj().into(), adapter.as_obj().into()],
+    )?
+    .l()
+}
+
+// The Java side passes the method name and descriptor as one string, such as
+// `apply(Ljava/lang/Object;)Ljava/lang/Object;`. Returns the string and the
+// position of the descriptor.
+fn proxy_method<'a: 'b, 'b>(env: &'b JNIEnv<'a>, method: JObject<'a>) -> Result<(String, usize)> {
+    let method = crate::strings::to_string(env, method)?;
+    let pos = method.find('(').unwrap_or(method.len());
+    Ok((method, pos))
+}
+
+fn fn_mut_proxy_internal<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    interfaces: &[&str],
+    mut f: impl for<'c, 'd> FnMut(&'d JNIEnv<'c>, JObject<'c>, &str, &str, JObject<'c>) -> JObject<'c>
+        + 'static,
+    local: bool,
+) -> Result<JObject<'a>> {
+    let adapter = fn_mut_adapter(
+        env,
+        move |env, _obj1, obj2, arg1, arg2| match proxy_method(env, arg1) {
+            Ok((method, pos)) => f(env, obj2, &method[..pos], &method[pos..], arg2),
+            Err(_) => JObject::null(),
+        },
+        local,
+    )?;
+    proxy(env, interfaces, adapter)
+}
+
+/// Create a `java.lang.reflect.Proxy` which implements the given interfaces
+/// by calling a given [`FnMut`]. The closure receives the proxy object, the
+/// name and JNI descriptor of the interface method that was called, such as
+/// `"propertyChange"` and `"(Ljava/beans/PropertyChangeEvent;)V"`, and an
+/// `Object[]` of the arguments. Primitive arguments are boxed. The closure
+/// must return `null` for `void` methods, and a boxed value for methods that
+/// return a primitive.
+///
+/// The proxy also implements `java.io.Closeable`. Calling its `close()` method
+/// frees the closure instead of passing the call to it, and afterwards every
+/// method returns `null`. `equals()`, `hashCode()` and `toString()` use the
+/// identity of the proxy and are not passed to the closure either.
+///
+/// If the closure panics, the unwind will be caught and thrown as an
+/// `io.github.gedgygedgy.rust.panic.PanicException`.
+///
+/// It is not safe to call the proxy's methods recursively. The [`FnMut`] is
+/// managed with an internal [`Mutex`], so calling a method recursively will
+/// result in a deadlock.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `interfaces` - Internal names of the interfaces to implement, such as
+///   `"java/beans/PropertyChangeListener"`.
+/// * `f` - Closure to call.
+pub fn fn_mut_proxy<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    interfaces: &[&str],
+    f: impl for<'c, 'd> FnMut(&'d JNIEnv<'c>, JObject<'c>, &str, &str, JObject<'c>) -> JObject<'c>
+        + Send
+        + 'static,
+) -> Result<JObject<'a>> {
+    fn_mut_proxy_internal(env, interfaces, f, false)
+}
+
+/// Create a `java.lang.reflect.Proxy` from a given [`FnMut`] without checking
+/// if it is [`Send`]. Attempting to call any of the proxy's methods from a
+/// thread other than its origin thread will result in an
+/// `io.github.gedgygedgy.rust.thread.LocalThreadException` being thrown. See
+/// [`fn_mut_proxy()`] for details.
+pub fn fn_mut_proxy_local<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    interfaces: &[&str],
+    f: impl for<'c, 'd> FnMut(&'d JNIEnv<'c>, JObject<'c>, &str, &str, JObject<'c>) -> JObject<'c>
+        + 'static,
+) -> Result<JObject<'a>> {
+    fn_mut_proxy_internal(env, interfaces, f, true)
+}
+
+fn fn_proxy_internal<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    interfaces: &[&str],
+    f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, &str, &str, JObject<'c>) -> JObject<'c>
+        + 'static,
+    local: bool,
+) -> Result<JObject<'a>> {
+    let adapter = fn_adapter(
+        env,
+        move |env, _obj1, obj2, arg1, arg2| match proxy_method(env, arg1) {
+            Ok((method, pos)) => f(env, obj2, &method[..pos], &method[pos..], arg2),
+            Err(_) => JObject::null(),
+        },
+        local,
+    )?;
+    proxy(env, interfaces, adapter)
+}
+
+/// Create a `java.lang.reflect.Proxy` which implements the given interfaces
+/// by calling a given [`Fn`]. This works the same way as [`fn_mut_proxy()`],
+/// except that it is safe to call the proxy's methods recursively.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `interfaces` - Internal names of the interfaces to implement, such as
+///   `"java/beans/PropertyChangeListener"`.
+/// * `f` - Closure to call.
+pub fn fn_proxy<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    interfaces: &[&str],
+    f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, &str, &str, JObject<'c>) -> JObject<'c>
+        + Send
+        + Sync
+        + 'static,
+) -> Result<JObject<'a>> {
+    fn_proxy_internal(env, interfaces, f, false)
+}
+
+/// Create a `java.lang.reflect.Proxy` from a given [`Fn`] without checking if
+/// it is [`Send`]. Attempting to call any of the proxy's methods from a thread
+/// other than its origin thread will result in an
+/// `io.github.gedgygedgy.rust.thread.LocalThreadException` being thrown. See
+/// [`fn_proxy()`] for details.
+pub fn fn_proxy_local<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    interfaces: &[&str],
+    f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, &str, &str, JObject<'c>) -> JObject<'c>
+        + 'static,
+) -> Result<JObject<'a>> {
+    fn_proxy_internal(env, interfaces, f, true)
+}
+
+// Stands in for `io.github.gedgygedgy.rust.ops.FnAdapter` in signatures.
+struct FnAdapter;
+
+impl JavaSignature for FnAdapter {
+    const SIGNATURE: &'static str = "Lio/github/gedgygedgy/rust/ops/FnAdapter;";
+}
+
 struct SendSyncWrapper<T>(T);
 
 unsafe impl<T> Send for SendSyncWrapper<T> {}
 unsafe impl<T> Sync for SendSyncWrapper<T> {}
 
-type FnWrapper = SendSyncWrapper<
-    Arc<
-        dyn for<'a, 'b> Fn(
-                &'b JNIEnv<'a>,
-                JObject<'a>,
-                JObject<'a>,
-                JObject<'a>,
-                JObject<'a>,
-            ) -> JObject<'a>
-            + 'static,
-    >,
->;
+// Closure called by an FnAdapter. The variant determines which of the adapter's
+// native methods can call it: primitive closures take and return their values
+// directly instead of boxing them.
+enum AdapterFn {
+    Object(Arc<ObjectFn>),
+    Int(Arc<IntFn>),
+    Long(Arc<LongFn>),
+    Double(Arc<DoubleFn>),
+}
+
+type ObjectFn = dyn for<'a, 'b> Fn(
+        &'b JNIEnv<'a>,
+        JObject<'a>,
+        JObject<'a>,
+        JObject<'a>,
+        JObject<'a>,
+    ) -> JObject<'a>
+    + 'static;
+type IntFn = dyn for<'a, 'b> Fn(&'b JNIEnv<'a>, JObject<'a>, jint) -> jint + 'static;
+type LongFn = dyn for<'a, 'b> Fn(&'b JNIEnv<'a>, JObject<'a>, JObject<'a>) -> jlong + 'static;
+type DoubleFn =
+    dyn for<'a, 'b> Fn(&'b JNIEnv<'a>, JObject<'a>, jdouble, jdouble) -> jdouble + 'static;
+
+type FnWrapper = SendSyncWrapper<AdapterFn>;
 
 fn fn_once_adapter<'a: 'b, 'b>(
     env: &'b JNIEnv<'a>,
@@ -335,26 +904,107 @@ fn fn_adapter<'a: 'b, 'b>(
         + 'static,
     local: bool,
 ) -> Result<JObject<'a>> {
-    let arc: Arc<
-        dyn for<'c, 'd> Fn(
-            &'d JNIEnv<'c>,
-            JObject<'c>,
-            JObject<'c>,
-            JObject<'c>,
-            JObject<'c>,
-        ) -> JObject<'c>,
-    > = Arc::from(f);
+    new_adapter(env, AdapterFn::Object(Arc::from(f)), local)
+}
 
+fn new_adapter<'a: 'b, 'b>(env: &'b JNIEnv<'a>, f: AdapterFn, local: bool) -> Result<JObject<'a>> {
     let class = env.auto_local(env.find_class("io/github/gedgygedgy/rust/ops/FnAdapter")?);
 
-    let obj = env.new_object(&class, "(Z)V", &[local.into()])?;
-    env.set_rust_field::<_, _, FnWrapper>(obj, "data", SendSyncWrapper(arc))?;
+    let obj = env.new_object(&class, crate::sig!((bool) -> ()), &[local.into()])?;
+    env.set_rust_field::<_, _, FnWrapper>(obj, "data", SendSyncWrapper(f))?;
     Ok(obj)
 }
 
+macro_rules! define_primitive_adapter {
+    (
+        variant: $variant:ident,
+        fn_once: $fo:ident,
+        fn_mut: $fm:ident,
+        fn: $f:ident,
+        args: ($($arg:ident: $ty:ty),*) -> $ret:ty,
+    ) => {
+        fn $fo<'a: 'b, 'b>(
+            env: &'b JNIEnv<'a>,
+            f: impl for<'c, 'd> FnOnce(&'d JNIEnv<'c>, JObject<'c>, $($ty),*) -> $ret + 'static,
+            local: bool,
+        ) -> Result<JObject<'a>> {
+            let mutex = Mutex::new(Some(f));
+            $f(
+                env,
+                move |env, obj, $($arg),*| {
+                    let f = {
+                        let mut guard = mutex.lock().unwrap();
+                        if let Some(f) = guard.take() {
+                            f
+                        } else {
+                            return Default::default();
+                        }
+                    };
+                    f(env, obj, $($arg),*)
+                },
+                local,
+            )
+        }
+
+        fn $fm<'a: 'b, 'b>(
+            env: &'b JNIEnv<'a>,
+            f: impl for<'c, 'd> FnMut(&'d JNIEnv<'c>, JObject<'c>, $($ty),*) -> $ret + 'static,
+            local: bool,
+        ) -> Result<JObject<'a>> {
+            let mutex = Mutex::new(f);
+            $f(
+                env,
+                move |env, obj, $($arg),*| {
+                    let mut guard = mutex.lock().unwrap();
+                    guard(env, obj, $($arg),*)
+                },
+                local,
+            )
+        }
+
+        fn $f<'a: 'b, 'b>(
+            env: &'b JNIEnv<'a>,
+            f: impl for<'c, 'd> Fn(&'d JNIEnv<'c>, JObject<'c>, $($ty),*) -> $ret + 'static,
+            local: bool,
+        ) -> Result<JObject<'a>> {
+            new_adapter(env, AdapterFn::$variant(Arc::new(f)), local)
+        }
+    };
+}
+
+define_primitive_adapter! {
+    variant: Int,
+    fn_once: fn_once_int_adapter,
+    fn_mut: fn_mut_int_adapter,
+    fn: fn_int_adapter,
+    args: (arg: jint) -> jint,
+}
+
+define_primitive_adapter! {
+    variant: Long,
+    fn_once: fn_once_long_adapter,
+    fn_mut: fn_mut_long_adapter,
+    fn: fn_long_adapter,
+    args: (arg: JObject<'c>) -> jlong,
+}
+
+define_primitive_adapter! {
+    variant: Double,
+    fn_once: fn_once_double_adapter,
+    fn_mut: fn_mut_double_adapter,
+    fn: fn_double_adapter,
+    args: (arg1: jdouble, arg2: jdouble) -> jdouble,
+}
+
 pub(crate) mod jni {
-    use super::FnWrapper;
-    use jni::{errors::Result, objects::JObject, JNIEnv, NativeMethod};
+    use super::{AdapterFn, FnWrapper};
+    use jni::{
+        errors::Result,
+        objects::JObject,
+        sys::{jdouble, jint, jlong},
+        JNIEnv, NativeMethod,
+    };
+    use std::panic::AssertUnwindSafe;
 
     extern "C" fn fn_adapter_call_internal<'a>(
         env: JNIEnv<'a>,
@@ -363,17 +1013,66 @@ pub(crate) mod jni {
         arg1: JObject<'a>,
         arg2: JObject<'a>,
     ) -> JObject<'a> {
-        use std::panic::AssertUnwindSafe;
-
-        let arc = if let Ok(f) = env.get_rust_field::<_, _, FnWrapper>(obj1, "data") {
-            AssertUnwindSafe(f.0.clone())
-        } else {
-            return JObject::null();
+        let arc = match env.get_rust_field::<_, _, FnWrapper>(obj1, "data") {
+            Ok(f) => match &f.0 {
+                AdapterFn::Object(f) => AssertUnwindSafe(f.clone()),
+                _ => return JObject::null(),
+            },
+            Err(_) => return JObject::null(),
         };
         crate::exceptions::throw_unwind(&env, || arc(&env, obj1, obj2, arg1, arg2))
             .unwrap_or_else(|_| JObject::null())
     }
 
+    extern "C" fn fn_adapter_call_int_internal<'a>(
+        env: JNIEnv<'a>,
+        obj1: JObject<'a>,
+        obj2: JObject<'a>,
+        arg: jint,
+    ) -> jint {
+        let arc = match env.get_rust_field::<_, _, FnWrapper>(obj1, "data") {
+            Ok(f) => match &f.0 {
+                AdapterFn::Int(f) => AssertUnwindSafe(f.clone()),
+                _ => return 0,
+            },
+            Err(_) => return 0,
+        };
+        crate::exceptions::throw_unwind(&env, || arc(&env, obj2, arg)).unwrap_or(0)
+    }
+
+    extern "C" fn fn_adapter_call_long_internal<'a>(
+        env: JNIEnv<'a>,
+        obj1: JObject<'a>,
+        obj2: JObject<'a>,
+        arg: JObject<'a>,
+    ) -> jlong {
+        let arc = match env.get_rust_field::<_, _, FnWrapper>(obj1, "data") {
+            Ok(f) => match &f.0 {
+                AdapterFn::Long(f) => AssertUnwindSafe(f.clone()),
+                _ => return 0,
+            },
+            Err(_) => return 0,
+        };
+        crate::exceptions::throw_unwind(&env, || arc(&env, obj2, arg)).unwrap_or(0)
+    }
+
+    extern "C" fn fn_adapter_call_double_internal<'a>(
+        env: JNIEnv<'a>,
+        obj1: JObject<'a>,
+        obj2: JObject<'a>,
+        arg1: jdouble,
+        arg2: jdouble,
+    ) -> jdouble {
+        let arc = match env.get_rust_field::<_, _, FnWrapper>(obj1, "data") {
+            Ok(f) => match &f.0 {
+                AdapterFn::Double(f) => AssertUnwindSafe(f.clone()),
+                _ => return 0.0,
+            },
+            Err(_) => return 0.0,
+        };
+        crate::exceptions::throw_unwind(&env, || arc(&env, obj2, arg1, arg2)).unwrap_or(0.0)
+    }
+
     extern "C" fn fn_adapter_close_internal(env: JNIEnv, obj: JObject) {
         let _ = crate::exceptions::throw_unwind(&env, || {
             let _ = env.take_rust_field::<_, _, FnWrapper>(obj, "data");
@@ -389,14 +1088,27 @@ pub(crate) mod jni {
             &[
                 NativeMethod {
                     name: "callInternal".into(),
-                    sig:
-                        "(Ljava/lang/Object;Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;"
-                            .into(),
+                    sig: crate::sig!((JObject, JObject, JObject) -> JObject).into(),
                     fn_ptr: fn_adapter_call_internal as *mut c_void,
                 },
+                NativeMethod {
+                    name: "callIntInternal".into(),
+                    sig: crate::sig!((JObject, jint) -> jint).into(),
+                    fn_ptr: fn_adapter_call_int_internal as *mut c_void,
+                },
+                NativeMethod {
+                    name: "callLongInternal".into(),
+                    sig: crate::sig!((JObject, JObject) -> jlong).into(),
+                    fn_ptr: fn_adapter_call_long_internal as *mut c_void,
+                },
+                NativeMethod {
+                    name: "callDoubleInternal".into(),
+                    sig: crate::sig!((JObject, jdouble, jdouble) -> jdouble).into(),
+                    fn_ptr: fn_adapter_call_double_internal as *mut c_void,
+                },
                 NativeMethod {
                     name: "closeInternal".into(),
-                    sig: "()V".into(),
+                    sig: crate::sig!(() -> ()).into(),
                     fn_ptr: fn_adapter_close_internal as *mut c_void,
                 },
             ],
@@ -408,7 +1120,10 @@ pub(crate) mod jni {
 
 #[cfg(test)]
 mod test {
-    use crate::{exceptions::try_block, test_utils};
+    use crate::{
+        exceptions::{try_block, ThrowNew},
+        test_utils,
+    };
     use jni::{objects::JObject, JNIEnv};
     use std::{
         cell::RefCell,
@@ -1337,4 +2052,666 @@ mod test {
             assert!(env.is_same_object(ret.as_obj(), actual_ret).unwrap());
         });
     }
+
+    #[test]
+    fn test_fn_supplier_object() {
+        test_utils::JVM_ENV.with(|env| {
+            let ret = env
+                .new_global_ref(env.new_object("java/lang/Object", "()V", &[]).unwrap())
+                .unwrap();
+            let ret_clone = ret.clone();
+
+            let obj_ref = Arc::new(Mutex::new(env.new_global_ref(JObject::null()).unwrap()));
+            let obj_ref_2 = obj_ref.clone();
+            let supplier = super::fn_supplier(env, move |e, o| {
+                let guard = obj_ref_2.lock().unwrap();
+                assert!(e.is_same_object(guard.as_obj(), o).unwrap());
+                ret_clone.as_obj().into_inner().into()
+            })
+            .unwrap();
+
+            {
+                let mut guard = obj_ref.lock().unwrap();
+                *guard = env.new_global_ref(supplier).unwrap();
+            }
+
+            let actual_ret = env
+                .call_method(supplier, "get", "()Ljava/lang/Object;", &[])
+                .unwrap()
+                .l()
+                .unwrap();
+            assert!(env.is_same_object(ret.as_obj(), actual_ret).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_fn_consumer_object() {
+        test_utils::JVM_ENV.with(|env| {
+            let arg = env
+                .new_global_ref(env.new_object("java/lang/Object", "()V", &[]).unwrap())
+                .unwrap();
+            let arg_clone = arg.clone();
+            let calls = Arc::new(Mutex::new(0));
+            let calls_2 = calls.clone();
+
+            let obj_ref = Arc::new(Mutex::new(env.new_global_ref(JObject::null()).unwrap()));
+            let obj_ref_2 = obj_ref.clone();
+            let consumer = super::fn_consumer(env, move |e, o, a| {
+                let guard = obj_ref_2.lock().unwrap();
+                assert!(e.is_same_object(guard.as_obj(), o).unwrap());
+                assert!(e.is_same_object(arg_clone.as_obj(), a).unwrap());
+                *calls_2.lock().unwrap() += 1;
+            })
+            .unwrap();
+
+            {
+                let mut guard = obj_ref.lock().unwrap();
+                *guard = env.new_global_ref(consumer).unwrap();
+            }
+
+            env.call_method(
+                consumer,
+                "accept",
+                "(Ljava/lang/Object;)V",
+                &[arg.as_obj().into()],
+            )
+            .unwrap();
+            test_data(&calls, 1, 2);
+        });
+    }
+
+    #[test]
+    fn test_fn_bi_consumer_object() {
+        test_utils::JVM_ENV.with(|env| {
+            let arg1 = env
+                .new_global_ref(env.new_object("java/lang/Object", "()V", &[]).unwrap())
+                .unwrap();
+            let arg2 = env
+                .new_global_ref(env.new_object("java/lang/Object", "()V", &[]).unwrap())
+                .unwrap();
+            let arg1_clone = arg1.clone();
+            let arg2_clone = arg2.clone();
+            let calls = Arc::new(Mutex::new(0));
+            let calls_2 = calls.clone();
+
+            let obj_ref = Arc::new(Mutex::new(env.new_global_ref(JObject::null()).unwrap()));
+            let obj_ref_2 = obj_ref.clone();
+            let bi_consumer = super::fn_bi_consumer(env, move |e, o, a1, a2| {
+                let guard = obj_ref_2.lock().unwrap();
+                assert!(e.is_same_object(guard.as_obj(), o).unwrap());
+                assert!(e.is_same_object(arg1_clone.as_obj(), a1).unwrap());
+                assert!(e.is_same_object(arg2_clone.as_obj(), a2).unwrap());
+                *calls_2.lock().unwrap() += 1;
+            })
+            .unwrap();
+
+            {
+                let mut guard = obj_ref.lock().unwrap();
+                *guard = env.new_global_ref(bi_consumer).unwrap();
+            }
+
+            env.call_method(
+                bi_consumer,
+                "accept",
+                "(Ljava/lang/Object;Ljava/lang/Object;)V",
+                &[arg1.as_obj().into(), arg2.as_obj().into()],
+            )
+            .unwrap();
+            test_data(&calls, 1, 2);
+        });
+    }
+
+    #[test]
+    fn test_fn_predicate() {
+        test_utils::JVM_ENV.with(|env| {
+            let predicate = super::fn_predicate(env, |e, _o, t| {
+                crate::strings::to_string(e, t).unwrap() == "yes"
+            })
+            .unwrap();
+
+            for (value, expected) in [("yes", true), ("no", false)] {
+                let value = env.new_string(value).unwrap();
+                let result = env
+                    .call_method(predicate, "test", "(Ljava/lang/Object;)Z", &[value.into()])
+                    .unwrap()
+                    .z()
+                    .unwrap();
+                assert_eq!(result, expected);
+            }
+
+            env.call_method(predicate, "close", "()V", &[]).unwrap();
+            let value = env.new_string("yes").unwrap();
+            let result = env
+                .call_method(predicate, "test", "(Ljava/lang/Object;)Z", &[value.into()])
+                .unwrap()
+                .z()
+                .unwrap();
+            assert!(!result);
+        });
+    }
+
+    #[test]
+    fn test_fn_comparator() {
+        test_utils::JVM_ENV.with(|env| {
+            let comparator = super::fn_comparator(env, |e, _o, o1, o2| {
+                let o1: i32 = crate::primitives::unbox_primitive(e, o1).unwrap();
+                let o2: i32 = crate::primitives::unbox_primitive(e, o2).unwrap();
+                o2.cmp(&o1)
+            })
+            .unwrap();
+
+            let list = env.new_object("java/util/ArrayList", "()V", &[]).unwrap();
+            for i in [3, 1, 2] {
+                let i = crate::primitives::box_primitive(env, i).unwrap();
+                env.call_method(list, "add", "(Ljava/lang/Object;)Z", &[i.into()])
+                    .unwrap();
+            }
+            env.call_static_method(
+                "java/util/Collections",
+                "sort",
+                "(Ljava/util/List;Ljava/util/Comparator;)V",
+                &[list.into(), comparator.into()],
+            )
+            .unwrap();
+
+            let mut actual = Vec::new();
+            for i in 0..3 {
+                let obj = env
+                    .call_method(list, "get", "(I)Ljava/lang/Object;", &[i.into()])
+                    .unwrap()
+                    .l()
+                    .unwrap();
+                actual.push(crate::primitives::unbox_primitive::<i32>(env, obj).unwrap());
+            }
+            assert_eq!(actual, [3, 2, 1]);
+        });
+    }
+
+    #[test]
+    fn test_fn_int_unary_operator() {
+        test_utils::JVM_ENV.with(|env| {
+            let operator = super::fn_int_unary_operator(env, |_e, _o, i| i * 2).unwrap();
+            let result = env
+                .call_method(operator, "applyAsInt", "(I)I", &[21.into()])
+                .unwrap()
+                .i()
+                .unwrap();
+            assert_eq!(result, 42);
+        });
+    }
+
+    #[test]
+    fn test_fn_to_long_function() {
+        test_utils::JVM_ENV.with(|env| {
+            let function = super::fn_once_to_long_function(env, |e, _o, t| {
+                crate::strings::to_string(e, t).unwrap().len() as i64
+            })
+            .unwrap();
+            let value = env.new_string("hello").unwrap();
+            let result = env
+                .call_method(
+                    function,
+                    "applyAsLong",
+                    "(Ljava/lang/Object;)J",
+                    &[value.into()],
+                )
+                .unwrap()
+                .j()
+                .unwrap();
+            assert_eq!(result, 5);
+
+            let result = env
+                .call_method(
+                    function,
+                    "applyAsLong",
+                    "(Ljava/lang/Object;)J",
+                    &[value.into()],
+                )
+                .unwrap()
+                .j()
+                .unwrap();
+            assert_eq!(result, 0);
+        });
+    }
+
+    #[test]
+    fn test_fn_double_binary_operator() {
+        test_utils::JVM_ENV.with(|env| {
+            let mut calls = 0;
+            let operator = super::fn_mut_double_binary_operator(env, move |_e, _o, l, r| {
+                calls += 1;
+                l * r + calls as f64
+            })
+            .unwrap();
+            for expected in [7.0, 8.0] {
+                let result = env
+                    .call_method(
+                        operator,
+                        "applyAsDouble",
+                        "(DD)D",
+                        &[1.5.into(), 4.0.into()],
+                    )
+                    .unwrap()
+                    .d()
+                    .unwrap();
+                assert_eq!(result, expected);
+            }
+        });
+    }
+
+    #[test]
+    fn test_fn_primitive_operators_unboxed() {
+        fn adapter<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> JObject<'a> {
+            env.get_field(obj, "adapter", "Lio/github/gedgygedgy/rust/ops/FnAdapter;")
+                .unwrap()
+                .l()
+                .unwrap()
+        }
+
+        test_utils::JVM_ENV.with(|env| {
+            let calls = Arc::new(Mutex::new(0));
+            let calls2 = calls.clone();
+            let calls3 = calls.clone();
+            let calls4 = calls.clone();
+            let operators = [
+                super::fn_int_unary_operator(env, move |_e, _o, i| {
+                    *calls2.lock().unwrap() += 1;
+                    i
+                })
+                .unwrap(),
+                super::fn_to_long_function(env, move |_e, _o, _t| {
+                    *calls3.lock().unwrap() += 1;
+                    0
+                })
+                .unwrap(),
+                super::fn_double_binary_operator(env, move |_e, _o, l, _r| {
+                    *calls4.lock().unwrap() += 1;
+                    l
+                })
+                .unwrap(),
+            ];
+
+            for operator in operators {
+                let adapter = adapter(env, operator);
+                {
+                    let data = env
+                        .get_rust_field::<_, _, super::FnWrapper>(adapter, "data")
+                        .unwrap();
+                    assert!(!matches!(data.0, super::AdapterFn::Object(_)));
+                }
+
+                // The boxed entry point can't reach a primitive closure.
+                let result = env
+                    .call_method(
+                        adapter,
+                        "call",
+                        "(Ljava/lang/Object;Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;",
+                        &[operator.into(), JObject::null().into(), JObject::null().into()],
+                    )
+                    .unwrap()
+                    .l()
+                    .unwrap();
+                assert!(result.is_null());
+            }
+            assert_eq!(*calls.lock().unwrap(), 0);
+
+            let result = env
+                .call_method(operators[0], "applyAsInt", "(I)I", &[3.into()])
+                .unwrap()
+                .i()
+                .unwrap();
+            assert_eq!(result, 3);
+            assert_eq!(*calls.lock().unwrap(), 1);
+        });
+    }
+
+    #[test]
+    fn test_fn_proxy() {
+        test_utils::JVM_ENV.with(|env| {
+            let data = Arc::new(Mutex::new(0));
+            let data2 = data.clone();
+            let proxy = super::fn_proxy(
+                env,
+                &["java/lang/Runnable", "java/util/function/IntBinaryOperator"],
+                move |e, _o, name, descriptor, args| match name {
+                    "run" => {
+                        assert_eq!(descriptor, "()V");
+                        assert_eq!(e.get_array_length(args.into_inner()).unwrap(), 0);
+                        *data2.lock().unwrap() += 1;
+                        JObject::null()
+                    }
+                    "applyAsInt" => {
+                        assert_eq!(descriptor, "(II)I");
+                        let args = args.into_inner();
+                        let left = e.get_object_array_element(args, 0).unwrap();
+                        let right = e.get_object_array_element(args, 1).unwrap();
+                        let left: i32 = crate::primitives::unbox_primitive(e, left).unwrap();
+                        let right: i32 = crate::primitives::unbox_primitive(e, right).unwrap();
+                        crate::primitives::box_primitive(e, left + right).unwrap()
+                    }
+                    _ => panic!("Unexpected method {}", name),
+                },
+            )
+            .unwrap();
+
+            assert!(env.is_instance_of(proxy, "java/io/Closeable").unwrap());
+            env.call_method(proxy, "run", "()V", &[]).unwrap();
+            assert_eq!(*data.lock().unwrap(), 1);
+            let sum = env
+                .call_method(proxy, "applyAsInt", "(II)I", &[3.into(), 4.into()])
+                .unwrap()
+                .i()
+                .unwrap();
+            assert_eq!(sum, 7);
+
+            let equals = env
+                .call_method(proxy, "equals", "(Ljava/lang/Object;)Z", &[proxy.into()])
+                .unwrap()
+                .z()
+                .unwrap();
+            assert!(equals);
+            env.call_method(proxy, "hashCode", "()I", &[]).unwrap();
+            env.call_method(proxy, "toString", "()Ljava/lang/String;", &[])
+                .unwrap();
+
+            assert_eq!(Arc::strong_count(&data), 2);
+            env.call_method(proxy, "close", "()V", &[]).unwrap();
+            assert_eq!(Arc::strong_count(&data), 1);
+            env.call_method(proxy, "run", "()V", &[]).unwrap();
+            assert_eq!(*data.lock().unwrap(), 1);
+        });
+    }
+
+    #[test]
+    fn test_fn_proxy_mixed_class_loaders() {
+        test_utils::JVM_ENV.with(|env| {
+            let proxy = super::fn_proxy(
+                env,
+                &[
+                    "java/lang/Runnable",
+                    "io/github/gedgygedgy/rust/ops/FnSupplier",
+                ],
+                |e, _o, name, _descriptor, _args| match name {
+                    "run" => JObject::null(),
+                    "get" => e.new_string("Hello").unwrap().into(),
+                    _ => panic!("Unexpected method {}", name),
+                },
+            )
+            .unwrap();
+
+            assert!(env.is_instance_of(proxy, "java/lang/Runnable").unwrap());
+            env.call_method(proxy, "run", "()V", &[]).unwrap();
+            let value = env
+                .call_method(proxy, "get", "()Ljava/lang/Object;", &[])
+                .unwrap()
+                .l()
+                .unwrap();
+            assert_eq!(crate::strings::to_string(env, value).unwrap(), "Hello");
+        });
+    }
+
+    #[test]
+    fn test_fn_mut_proxy_local_thread() {
+        test_utils::JVM_ENV.with(|env| {
+            let (data, f) = create_test_fn_local();
+            let proxy = super::fn_mut_proxy_local(
+                env,
+                &["java/lang/Runnable"],
+                move |e, o, _name, _descriptor, _args| {
+                    f(e, o);
+                    JObject::null()
+                },
+            )
+            .unwrap();
+            env.call_method(proxy, "run", "()V", &[]).unwrap();
+            test_data_local(&data, 1, 2);
+
+            let proxy = env.new_global_ref(proxy).unwrap();
+            let thread = std::thread::spawn(move || {
+                test_utils::JVM_ENV.with(|env| {
+                    let value = crate::exceptions::try_block(env, || {
+                        env.call_method(proxy.as_obj(), "run", "()V", &[])?;
+                        Ok(false)
+                    })
+                    .catch(
+                        "io/github/gedgygedgy/rust/thread/LocalThreadException",
+                        |_ex| Ok(true),
+                    )
+                    .result()
+                    .unwrap();
+                    assert!(value);
+                });
+            });
+            thread.join().unwrap();
+            test_data_local(&data, 1, 2);
+        });
+    }
+
+    #[test]
+    fn test_fn_proxy_not_interface() {
+        test_utils::JVM_ENV.with(|env| {
+            let result = super::fn_proxy(env, &["java/lang/Object"], |_e, _o, _n, _d, _a| {
+                JObject::null()
+            });
+            assert!(result.is_err());
+            let ex = env.exception_occurred().unwrap();
+            env.exception_clear().unwrap();
+            assert!(env
+                .is_instance_of(ex, "java/lang/IllegalArgumentException")
+                .unwrap());
+        });
+    }
+
+    #[test]
+    fn test_fn_callable() {
+        test_utils::JVM_ENV.with(|env| {
+            let callable = super::fn_callable(env, |e, _o| -> jni::errors::Result<_> {
+                Ok(e.new_string("Hello")?.into())
+            })
+            .unwrap();
+
+            let result = env
+                .call_method(callable, "call", "()Ljava/lang/Object;", &[])
+                .unwrap()
+                .l()
+                .unwrap();
+            assert_eq!(crate::strings::to_string(env, result).unwrap(), "Hello");
+        });
+    }
+
+    #[test]
+    fn test_fn_callable_error() {
+        test_utils::JVM_ENV.with(|env| {
+            let callable = super::fn_callable(env, |_e, _o| {
+                Err(ThrowNew::new(
+                    "java/util/NoSuchElementException",
+                    "No value",
+                ))
+            })
+            .unwrap();
+
+            let value = try_block(env, || {
+                env.call_method(callable, "call", "()Ljava/lang/Object;", &[])?;
+                Ok(String::new())
+            })
+            .catch("java/util/NoSuchElementException", |ex| {
+                let msg = env
+                    .call_method(ex, "getMessage", "()Ljava/lang/String;", &[])?
+                    .l()?;
+                crate::strings::to_string(env, msg)
+            })
+            .result()
+            .unwrap();
+            assert_eq!(value, "No value");
+        });
+    }
+
+    #[test]
+    fn test_fn_callable_executor() {
+        test_utils::JVM_ENV.with(|env| {
+            let callable = super::fn_callable(env, |_e, _o| -> std::io::Result<JObject> {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "Task failed",
+                ))
+            })
+            .unwrap();
+
+            let executor = env
+                .call_static_method(
+                    "java/util/concurrent/Executors",
+                    "newSingleThreadExecutor",
+                    "()Ljava/util/concurrent/ExecutorService;",
+                    &[],
+                )
+                .unwrap()
+                .l()
+                .unwrap();
+            let future = env
+                .call_method(
+                    executor,
+                    "submit",
+                    "(Ljava/util/concurrent/Callable;)Ljava/util/concurrent/Future;",
+                    &[callable.into()],
+                )
+                .unwrap()
+                .l()
+                .unwrap();
+
+            let cause = try_block(env, || {
+                env.call_method(future, "get", "()Ljava/lang/Object;", &[])?;
+                Ok(JObject::null())
+            })
+            .catch("java/util/concurrent/ExecutionException", |ex| {
+                env.call_method(ex, "getCause", "()Ljava/lang/Throwable;", &[])?
+                    .l()
+            })
+            .result()
+            .unwrap();
+            env.call_method(executor, "shutdown", "()V", &[]).unwrap();
+
+            assert!(env.is_instance_of(cause, "java/io/IOException").unwrap());
+            let msg = env
+                .call_method(cause, "getMessage", "()Ljava/lang/String;", &[])
+                .unwrap()
+                .l()
+                .unwrap();
+            assert_eq!(crate::strings::to_string(env, msg).unwrap(), "Task failed");
+        });
+    }
+
+    #[test]
+    fn test_try_fn_function() {
+        test_utils::JVM_ENV.with(|env| {
+            let function = super::try_fn_function(env, |e, _o, arg| -> jni::errors::Result<_> {
+                let s = crate::strings::to_string(e, arg)?;
+                Ok(e.new_string(s.to_uppercase())?.into())
+            })
+            .unwrap();
+
+            let arg = env.new_string("hello").unwrap();
+            let result = env
+                .call_method(
+                    function,
+                    "apply",
+                    "(Ljava/lang/Object;)Ljava/lang/Object;",
+                    &[arg.into()],
+                )
+                .unwrap()
+                .l()
+                .unwrap();
+            assert_eq!(crate::strings::to_string(env, result).unwrap(), "HELLO");
+        });
+    }
+
+    #[test]
+    fn test_try_fn_function_java_exception() {
+        test_utils::JVM_ENV.with(|env| {
+            let function = super::try_fn_function(env, |e, _o, arg| {
+                e.call_static_method(
+                    "java/lang/Integer",
+                    "valueOf",
+                    "(Ljava/lang/String;)Ljava/lang/Integer;",
+                    &[arg.into()],
+                )?
+                .l()
+            })
+            .unwrap();
+
+            let arg = env.new_string("abc").unwrap();
+            let value = try_block(env, || {
+                env.call_method(
+                    function,
+                    "apply",
+                    "(Ljava/lang/Object;)Ljava/lang/Object;",
+                    &[arg.into()],
+                )?;
+                Ok(false)
+            })
+            .catch("java/lang/NumberFormatException", |_ex| Ok(true))
+            .result()
+            .unwrap();
+            assert!(value);
+        });
+    }
+
+    #[test]
+    fn test_try_fn_mut_runnable_error() {
+        test_utils::JVM_ENV.with(|env| {
+            let mut count = 0;
+            let runnable = super::try_fn_mut_runnable(env, move |_e, _o| {
+                count += 1;
+                if count > 1 {
+                    Err(ThrowNew::new(
+                        "java/lang/IllegalStateException",
+                        "Ran twice",
+                    ))
+                } else {
+                    Ok(())
+                }
+            })
+            .unwrap();
+
+            env.call_method(runnable, "run", "()V", &[]).unwrap();
+            assert!(!env.exception_check().unwrap());
+
+            let value = try_block(env, || {
+                env.call_method(runnable, "run", "()V", &[])?;
+                Ok(String::new())
+            })
+            .catch("java/lang/IllegalStateException", |ex| {
+                let msg = env
+                    .call_method(ex, "getMessage", "()Ljava/lang/String;", &[])?
+                    .l()?;
+                crate::strings::to_string(env, msg)
+            })
+            .result()
+            .unwrap();
+            assert_eq!(value, "Ran twice");
+        });
+    }
+
+    #[test]
+    fn test_try_fn_once_bi_consumer_error() {
+        test_utils::JVM_ENV.with(|env| {
+            let consumer = super::try_fn_once_bi_consumer(env, |_e, _o, _arg1, _arg2| {
+                Err(jni::errors::Error::NullPtr("Consumer argument"))
+            })
+            .unwrap();
+
+            let value = try_block(env, || {
+                env.call_method(
+                    consumer,
+                    "accept",
+                    "(Ljava/lang/Object;Ljava/lang/Object;)V",
+                    &[JObject::null().into(), JObject::null().into()],
+                )?;
+                Ok(false)
+            })
+            .catch("java/lang/RuntimeException", |_ex| Ok(true))
+            .result()
+            .unwrap();
+            assert!(value);
+        });
+    }
 }
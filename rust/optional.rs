@@ -0,0 +1,325 @@
+use crate::primitives::{check_instance_of, JavaPrimitive};
+use jni::{
+    errors::Result,
+    objects::{JObject, JValue},
+    sys::{jdouble, jint, jlong},
+    JNIEnv,
+};
+
+/// Get an [`Option`] from a `java.util.Optional`, converting the contained
+/// value into a Rust value with the given function. The local reference passed
+/// to the function is deleted after it returns.
+///
+/// A `null` reference is treated the same as an empty `java.util.Optional`.
+/// If the object is not a `java.util.Optional`, a
+/// `java.lang.ClassCastException` is thrown.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - `java.util.Optional` to convert.
+/// * `f` - Function to convert the contained value into a Rust value.
+pub fn optional_to_option<'a: 'b, 'b, T>(
+    env: &'b JNIEnv<'a>,
+    obj: JObject<'a>,
+    f: impl FnOnce(&'b JNIEnv<'a>, JObject<'a>) -> Result<T>,
+) -> Result<Option<T>> {
+    let value = match get_value(
+        env,
+        obj,
+        "java/util/Optional",
+        "get",
+        "()Ljava/lang/Object;",
+    )? {
+        Some(value) => env.auto_local(value.l()?),
+        None => return Ok(None),
+    };
+    f(env, value.as_obj()).map(Some)
+}
+
+/// Create a new `java.util.Optional` from an [`Option`], converting the
+/// contained value into a Java object with the given function. The local
+/// reference returned by the function is deleted once the `java.util.Optional`
+/// has been created.
+///
+/// [`None`] is converted into `Optional.empty()`. If the function returns
+/// `null`, the result is also empty.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new object.
+/// * `option` - [`Option`] to convert.
+/// * `f` - Function to convert the contained value into a Java object.
+pub fn option_to_optional<'a: 'b, 'b, T>(
+    env: &'b JNIEnv<'a>,
+    option: Option<T>,
+    f: impl FnOnce(&'b JNIEnv<'a>, T) -> Result<JObject<'a>>,
+) -> Result<JObject<'a>> {
+    match option {
+        Some(value) => {
+            let value = env.auto_local(f(env, value)?);
+            env.call_static_method(
+                "java/util/Optional",
+                "ofNullable",
+                "(Ljava/lang/Object;)Ljava/util/Optional;",
+                &[value.as_obj().into()],
+            )?
+            .l()
+        }
+        None => empty(env, "java/util/Optional"),
+    }
+}
+
+/// Get an [`Option`] from a `java.util.OptionalInt`. A `null` reference is
+/// treated as empty.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - `java.util.OptionalInt` to convert.
+pub fn optional_int_to_option<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    obj: JObject<'a>,
+) -> Result<Option<jint>> {
+    get_primitive(env, obj, "java/util/OptionalInt", "getAsInt")
+}
+
+/// Create a new `java.util.OptionalInt` from an [`Option`].
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new object.
+/// * `option` - [`Option`] to convert.
+pub fn option_to_optional_int<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    option: Option<jint>,
+) -> Result<JObject<'a>> {
+    new_primitive(env, option, "java/util/OptionalInt")
+}
+
+/// Get an [`Option`] from a `java.util.OptionalLong`. A `null` reference is
+/// treated as empty.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - `java.util.OptionalLong` to convert.
+pub fn optional_long_to_option<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    obj: JObject<'a>,
+) -> Result<Option<jlong>> {
+    get_primitive(env, obj, "java/util/OptionalLong", "getAsLong")
+}
+
+/// Create a new `java.util.OptionalLong` from an [`Option`].
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new object.
+/// * `option` - [`Option`] to convert.
+pub fn option_to_optional_long<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    option: Option<jlong>,
+) -> Result<JObject<'a>> {
+    new_primitive(env, option, "java/util/OptionalLong")
+}
+
+/// Get an [`Option`] from a `java.util.OptionalDouble`. A `null` reference is
+/// treated as empty.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - `java.util.OptionalDouble` to convert.
+pub fn optional_double_to_option<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    obj: JObject<'a>,
+) -> Result<Option<jdouble>> {
+    get_primitive(env, obj, "java/util/OptionalDouble", "getAsDouble")
+}
+
+/// Create a new `java.util.OptionalDouble` from an [`Option`].
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new object.
+/// * `option` - [`Option`] to convert.
+pub fn option_to_optional_double<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    option: Option<jdouble>,
+) -> Result<JObject<'a>> {
+    new_primitive(env, option, "java/util/OptionalDouble")
+}
+
+fn get_value<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    obj: JObject<'a>,
+    class: &str,
+    getter: &str,
+    sig: &str,
+) -> Result<Option<JValue<'a>>> {
+    if env.is_same_object(obj, JObject::null())? {
+        return Ok(None);
+    }
+    check_instance_of(env, obj, class)?;
+
+    if env.call_method(obj, "isPresent", "()Z", &[])?.z()? {
+        Ok(Some(env.call_method(obj, getter, sig, &[])?))
+    } else {
+        Ok(None)
+    }
+}
+
+fn get_primitive<'a: 'b, 'b, T: JavaPrimitive>(
+    env: &'b JNIEnv<'a>,
+    obj: JObject<'a>,
+    class: &str,
+    getter: &str,
+) -> Result<Option<T>> {
+    get_value(env, obj, class, getter, T::UNBOX_SIG)?
+        .map(T::from_jvalue)
+        .transpose()
+}
+
+fn new_primitive<'a: 'b, 'b, T: JavaPrimitive>(
+    env: &'b JNIEnv<'a>,
+    option: Option<T>,
+    class: &str,
+) -> Result<JObject<'a>> {
+    match option {
+        Some(value) => {
+            let sig = format!("({})L{};", T::SIG, class);
+            env.call_static_method(class, "of", sig, &[value.into_jvalue()])?
+                .l()
+        }
+        None => empty(env, class),
+    }
+}
+
+fn empty<'a: 'b, 'b>(env: &'b JNIEnv<'a>, class: &str) -> Result<JObject<'a>> {
+    env.call_static_method(class, "empty", format!("()L{};", class), &[])?
+        .l()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::{self, assert_exception};
+    use jni::objects::JObject;
+
+    #[test]
+    fn test_optional_to_option() {
+        test_utils::JVM_ENV.with(|env| {
+            let s = env.new_string("hello").unwrap();
+            let obj = env
+                .call_static_method(
+                    "java/util/Optional",
+                    "of",
+                    "(Ljava/lang/Object;)Ljava/util/Optional;",
+                    &[s.into()],
+                )
+                .unwrap()
+                .l()
+                .unwrap();
+            let result = super::optional_to_option(env, obj, |env, value| {
+                Ok(String::from(env.get_string(value.into())?))
+            })
+            .unwrap();
+            assert_eq!(result, Some("hello".to_string()));
+
+            let obj = env
+                .call_static_method("java/util/Optional", "empty", "()Ljava/util/Optional;", &[])
+                .unwrap()
+                .l()
+                .unwrap();
+            let result = super::optional_to_option(env, obj, |_env, _value| -> Result<(), _> {
+                panic!("Should not be called")
+            })
+            .unwrap();
+            assert_eq!(result, None);
+
+            let result =
+                super::optional_to_option(env, JObject::null(), |_env, _value| Ok(())).unwrap();
+            assert_eq!(result, None);
+        });
+    }
+
+    #[test]
+    fn test_option_to_optional() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = super::option_to_optional(env, Some("world"), |env, value| {
+                Ok(env.new_string(value)?.into())
+            })
+            .unwrap();
+            assert!(env
+                .call_method(obj, "isPresent", "()Z", &[])
+                .unwrap()
+                .z()
+                .unwrap());
+            let value = env
+                .call_method(obj, "get", "()Ljava/lang/Object;", &[])
+                .unwrap()
+                .l()
+                .unwrap();
+            let value: String = env.get_string(value.into()).unwrap().into();
+            assert_eq!(value, "world");
+
+            let obj = super::option_to_optional(env, None::<()>, |_env, _value| {
+                panic!("Should not be called")
+            })
+            .unwrap();
+            assert!(!env
+                .call_method(obj, "isPresent", "()Z", &[])
+                .unwrap()
+                .z()
+                .unwrap());
+
+            let obj = super::option_to_optional(env, Some(()), |_env, _value| Ok(JObject::null()))
+                .unwrap();
+            assert!(!env
+                .call_method(obj, "isPresent", "()Z", &[])
+                .unwrap()
+                .z()
+                .unwrap());
+        });
+    }
+
+    #[test]
+    fn test_primitive_optionals() {
+        test_utils::JVM_ENV.with(|env| {
+            for value in &[Some(-7), None] {
+                let obj = super::option_to_optional_int(env, *value).unwrap();
+                assert!(env.is_instance_of(obj, "java/util/OptionalInt").unwrap());
+                assert_eq!(super::optional_int_to_option(env, obj).unwrap(), *value);
+            }
+
+            for value in &[Some(i64::MIN), None] {
+                let obj = super::option_to_optional_long(env, *value).unwrap();
+                assert!(env.is_instance_of(obj, "java/util/OptionalLong").unwrap());
+                assert_eq!(super::optional_long_to_option(env, obj).unwrap(), *value);
+            }
+
+            for value in &[Some(0.125), None] {
+                let obj = super::option_to_optional_double(env, *value).unwrap();
+                assert!(env.is_instance_of(obj, "java/util/OptionalDouble").unwrap());
+                assert_eq!(super::optional_double_to_option(env, obj).unwrap(), *value);
+            }
+
+            assert_eq!(
+                super::optional_long_to_option(env, JObject::null()).unwrap(),
+                None
+            );
+        });
+    }
+
+    #[test]
+    fn test_wrong_type() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = super::option_to_optional_int(env, Some(1)).unwrap();
+            assert_exception(
+                env,
+                super::optional_long_to_option(env, obj),
+                "java/lang/ClassCastException",
+            );
+        });
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    exceptions::throw_error,
+    primitives::check_instance_of,
+    strings::{new_string, to_string},
+};
+use jni::{errors::Result, objects::JObject, JNIEnv};
+use std::path::{Path, PathBuf};
+
+/// Convert a `java.nio.file.Path` into a Rust [`PathBuf`].
+///
+/// The path is converted with `toString()`, so it is only meaningful for paths
+/// in the default file system. If the path contains an unpaired surrogate, a
+/// `java.lang.IllegalArgumentException` is thrown. If the object is not a
+/// `java.nio.file.Path`, a `java.lang.ClassCastException` is thrown.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - `java.nio.file.Path` to convert.
+pub fn path_to_path_buf<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<PathBuf> {
+    check_instance_of(env, obj, "java/nio/file/Path")?;
+    get_path(env, obj, "toString")
+}
+
+/// Create a new `java.nio.file.Path` from a Rust [`Path`] using
+/// `java.nio.file.Paths.get()`.
+///
+/// Java paths are made of UTF-16 strings, so Rust paths which are not valid
+/// Unicode cannot be represented. For such paths, a
+/// `java.lang.IllegalArgumentException` is thrown rather than converting the
+/// path lossily.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new object.
+/// * `path` - Path to convert.
+pub fn path_buf_to_path<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    path: impl AsRef<Path>,
+) -> Result<JObject<'a>> {
+    let s = env.auto_local(path_to_string(env, path.as_ref())?);
+    let more = env.auto_local(env.new_object_array(0, "java/lang/String", JObject::null())?);
+    env.call_static_method(
+        "java/nio/file/Paths",
+        "get",
+        "(Ljava/lang/String;[Ljava/lang/String;)Ljava/nio/file/Path;",
+        &[s.as_obj().into(), more.as_obj().into()],
+    )?
+    .l()
+}
+
+/// Convert a `java.io.File` into a Rust [`PathBuf`].
+///
+/// The path is converted with `getPath()`. If the path contains an unpaired
+/// surrogate, a `java.lang.IllegalArgumentException` is thrown. If the object
+/// is not a `java.io.File`, a `java.lang.ClassCastException` is thrown.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - `java.io.File` to convert.
+pub fn file_to_path_buf<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<PathBuf> {
+    check_instance_of(env, obj, "java/io/File")?;
+    get_path(env, obj, "getPath")
+}
+
+/// Create a new `java.io.File` from a Rust [`Path`].
+///
+/// As with [`path_buf_to_path`], a `java.lang.IllegalArgumentException` is
+/// thrown if the path is not valid Unicode.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new object.
+/// * `path` - Path to convert.
+pub fn path_buf_to_file<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    path: impl AsRef<Path>,
+) -> Result<JObject<'a>> {
+    let s = env.auto_local(path_to_string(env, path.as_ref())?);
+    env.new_object(
+        "java/io/File",
+        "(Ljava/lang/String;)V",
+        &[s.as_obj().into()],
+    )
+}
+
+fn get_path<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>, getter: &str) -> Result<PathBuf> {
+    let s = env.auto_local(
+        env.call_method(obj, getter, "()Ljava/lang/String;", &[])?
+            .l()?,
+    );
+    Ok(to_string(env, s.as_obj())?.into())
+}
+
+fn path_to_string<'a: 'b, 'b>(env: &'b JNIEnv<'a>, path: &Path) -> Result<JObject<'a>> {
+    match path.to_str() {
+        Some(s) => Ok(new_string(env, s)?.into()),
+        None => throw_error(
+            env,
+            "java/lang/IllegalArgumentException",
+            format!("Path is not valid Unicode: {}", path.display()),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::{self, assert_exception};
+    use std::path::{Path, PathBuf};
+
+    // Non-ASCII paths are rejected by java.nio.file.Paths unless the JVM's
+    // file name encoding supports them, which depends on the locale.
+    const TESTS: &[&str] = &["/tmp/some dir/file.txt", "relative/path", "."];
+
+    #[test]
+    fn test_path() {
+        test_utils::JVM_ENV.with(|env| {
+            for test in TESTS {
+                let obj = super::path_buf_to_path(env, test).unwrap();
+                assert!(env.is_instance_of(obj, "java/nio/file/Path").unwrap());
+                assert_eq!(
+                    super::path_to_path_buf(env, obj).unwrap(),
+                    PathBuf::from(test)
+                );
+            }
+
+            let obj = super::path_buf_to_file(env, "file").unwrap();
+            assert_exception(
+                env,
+                super::path_to_path_buf(env, obj),
+                "java/lang/ClassCastException",
+            );
+        });
+    }
+
+    #[test]
+    fn test_file() {
+        test_utils::JVM_ENV.with(|env| {
+            for test in TESTS.iter().chain(&["relative/\u{fc}ber"]) {
+                let obj = super::path_buf_to_file(env, Path::new(test)).unwrap();
+                assert!(env.is_instance_of(obj, "java/io/File").unwrap());
+                assert_eq!(
+                    super::file_to_path_buf(env, obj).unwrap(),
+                    PathBuf::from(test)
+                );
+            }
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_unicode() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        test_utils::JVM_ENV.with(|env| {
+            let path = Path::new(OsStr::from_bytes(b"/tmp/\xff"));
+            assert_exception(
+                env,
+                super::path_buf_to_path(env, path),
+                "java/lang/IllegalArgumentException",
+            );
+            assert_exception(
+                env,
+                super::path_buf_to_file(env, path),
+                "java/lang/IllegalArgumentException",
+            );
+        });
+    }
+}
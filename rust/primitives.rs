@@ -0,0 +1,304 @@
+use crate::exceptions::throw_error;
+use jni::{
+    errors::Result,
+    objects::{JObject, JString, JValue},
+    sys::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jshort},
+    JNIEnv,
+};
+
+/// Rust types which correspond to a Java primitive type, and which can be
+/// converted to and from the matching `java.lang` box type with
+/// [`box_primitive`] and [`unbox_primitive`].
+///
+/// | Rust type | Java type             |
+/// |-----------|-----------------------|
+/// | [`bool`]  | `java.lang.Boolean`   |
+/// | [`i8`]    | `java.lang.Byte`      |
+/// | [`u16`]   | `java.lang.Character` |
+/// | [`i16`]   | `java.lang.Short`     |
+/// | [`i32`]   | `java.lang.Integer`   |
+/// | [`i64`]   | `java.lang.Long`      |
+/// | [`f32`]   | `java.lang.Float`     |
+/// | [`f64`]   | `java.lang.Double`    |
+pub trait JavaPrimitive: Sized {
+    /// Signature of the primitive type, such as `I`.
+    const SIG: &'static str;
+
+    /// Internal name of the box class, such as `java/lang/Integer`.
+    const BOX_CLASS: &'static str;
+
+    /// Signature of the box class's static `valueOf()` method.
+    const VALUE_OF_SIG: &'static str;
+
+    /// Name of the box class's method which returns the primitive value, such
+    /// as `intValue`.
+    const UNBOX_METHOD: &'static str;
+
+    /// Signature of the box class's unboxing method.
+    const UNBOX_SIG: &'static str;
+
+    /// Convert the value into a [`JValue`].
+    fn into_jvalue(self) -> JValue<'static>;
+
+    /// Get the value out of a [`JValue`].
+    fn from_jvalue(value: JValue) -> Result<Self>;
+}
+
+macro_rules! impl_java_primitive {
+    ($ty:ty, $class:literal, $sig:literal, $unbox:literal, $variant:ident, $getter:ident) => {
+        impl JavaPrimitive for $ty {
+            const SIG: &'static str = $sig;
+            const BOX_CLASS: &'static str = $class;
+            const VALUE_OF_SIG: &'static str = concat!("(", $sig, ")L", $class, ";");
+            const UNBOX_METHOD: &'static str = $unbox;
+            const UNBOX_SIG: &'static str = concat!("()", $sig);
+
+            fn into_jvalue(self) -> JValue<'static> {
+                JValue::$variant(self.into())
+            }
+
+            fn from_jvalue(value: JValue) -> Result<Self> {
+                value.$getter().map(Into::into)
+            }
+        }
+    };
+}
+
+impl_java_primitive!(jbyte, "java/lang/Byte", "B", "byteValue", Byte, b);
+impl_java_primitive!(jchar, "java/lang/Character", "C", "charValue", Char, c);
+impl_java_primitive!(jshort, "java/lang/Short", "S", "shortValue", Short, s);
+impl_java_primitive!(jint, "java/lang/Integer", "I", "intValue", Int, i);
+impl_java_primitive!(jlong, "java/lang/Long", "J", "longValue", Long, j);
+impl_java_primitive!(jfloat, "java/lang/Float", "F", "floatValue", Float, f);
+impl_java_primitive!(jdouble, "java/lang/Double", "D", "doubleValue", Double, d);
+
+impl JavaPrimitive for bool {
+    const SIG: &'static str = "Z";
+    const BOX_CLASS: &'static str = "java/lang/Boolean";
+    const VALUE_OF_SIG: &'static str = "(Z)Ljava/lang/Boolean;";
+    const UNBOX_METHOD: &'static str = "booleanValue";
+    const UNBOX_SIG: &'static str = "()Z";
+
+    fn into_jvalue(self) -> JValue<'static> {
+        JValue::Bool(self as jboolean)
+    }
+
+    fn from_jvalue(value: JValue) -> Result<Self> {
+        value.z()
+    }
+}
+
+/// Box a Rust primitive into a new reference to its `java.lang` box type by
+/// calling the box class's `valueOf()` method.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `value` - Value to box.
+pub fn box_primitive<'a: 'b, 'b, T: JavaPrimitive>(
+    env: &'b JNIEnv<'a>,
+    value: T,
+) -> Result<JObject<'a>> {
+    env.call_static_method(
+        T::BOX_CLASS,
+        "valueOf",
+        T::VALUE_OF_SIG,
+        &[value.into_jvalue()],
+    )?
+    .l()
+}
+
+/// Box an optional Rust primitive. [`None`] is converted into `null`, and
+/// [`Some`] is boxed with [`box_primitive`].
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `value` - Value to box.
+pub fn box_option<'a: 'b, 'b, T: JavaPrimitive>(
+    env: &'b JNIEnv<'a>,
+    value: Option<T>,
+) -> Result<JObject<'a>> {
+    match value {
+        Some(value) => box_primitive(env, value),
+        None => Ok(JObject::null()),
+    }
+}
+
+/// Unbox a `java.lang` box object into the corresponding Rust primitive.
+///
+/// If the object is `null`, a `java.lang.NullPointerException` is thrown. If
+/// the object is not an instance of the expected box class, a
+/// `java.lang.ClassCastException` naming the object's actual class is thrown.
+/// In both cases, [`Error::JavaException`](jni::errors::Error::JavaException)
+/// is returned.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - Object to unbox.
+pub fn unbox_primitive<'a: 'b, 'b, T: JavaPrimitive>(
+    env: &'b JNIEnv<'a>,
+    obj: JObject<'a>,
+) -> Result<T> {
+    match unbox_option(env, obj)? {
+        Some(value) => Ok(value),
+        None => throw_error(
+            env,
+            "java/lang/NullPointerException",
+            format!("Cannot unbox null value to {}", class_name(T::BOX_CLASS)),
+        ),
+    }
+}
+
+/// Unbox a nullable `java.lang` box object. `null` is converted into
+/// [`None`], and any other object is unboxed as with [`unbox_primitive`].
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - Object to unbox.
+pub fn unbox_option<'a: 'b, 'b, T: JavaPrimitive>(
+    env: &'b JNIEnv<'a>,
+    obj: JObject<'a>,
+) -> Result<Option<T>> {
+    if env.is_same_object(obj, JObject::null())? {
+        return Ok(None);
+    }
+    check_instance_of(env, obj, T::BOX_CLASS)?;
+    let value = env.call_method(obj, T::UNBOX_METHOD, T::UNBOX_SIG, &[])?;
+    T::from_jvalue(value).map(Some)
+}
+
+/// Check that an object is an instance of the given class. If it is not, a
+/// `java.lang.ClassCastException` naming the object's actual class is thrown
+/// and [`Error::JavaException`](jni::errors::Error::JavaException) is
+/// returned.
+pub(crate) fn check_instance_of<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    obj: JObject<'a>,
+    class: &str,
+) -> Result<()> {
+    if env.is_instance_of(obj, class)? {
+        return Ok(());
+    }
+
+    let actual = env.auto_local(env.get_object_class(obj)?);
+    let name = env.auto_local(
+        env.call_method(actual.as_obj(), "getName", "()Ljava/lang/String;", &[])?
+            .l()?,
+    );
+    let name: String = env.get_string(JString::from(name.as_obj()))?.into();
+    throw_error(
+        env,
+        "java/lang/ClassCastException",
+        format!("Cannot cast {} to {}", name, class_name(class)),
+    )
+}
+
+fn class_name(class: &str) -> String {
+    class.replace('/', ".")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils;
+    use jni::{errors::Error, objects::JObject, JNIEnv};
+
+    fn exception_message<'a: 'b, 'b>(env: &'b JNIEnv<'a>, class: &str) -> String {
+        let ex = env.exception_occurred().unwrap();
+        env.exception_clear().unwrap();
+        assert!(env.is_instance_of(ex, class).unwrap());
+        let message = env
+            .call_method(ex, "getMessage", "()Ljava/lang/String;", &[])
+            .unwrap()
+            .l()
+            .unwrap();
+        env.get_string(message.into()).unwrap().into()
+    }
+
+    #[test]
+    fn test_box_primitive() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = super::box_primitive(env, 42i32).unwrap();
+            assert!(env.is_instance_of(obj, "java/lang/Integer").unwrap());
+            let value = env.call_method(obj, "intValue", "()I", &[]).unwrap();
+            assert_eq!(value.i().unwrap(), 42);
+
+            let obj = super::box_primitive(env, true).unwrap();
+            assert!(env.is_instance_of(obj, "java/lang/Boolean").unwrap());
+            let value = env.call_method(obj, "booleanValue", "()Z", &[]).unwrap();
+            assert!(value.z().unwrap());
+
+            let obj = super::box_primitive(env, 1.5f64).unwrap();
+            assert!(env.is_instance_of(obj, "java/lang/Double").unwrap());
+
+            let obj = super::box_option::<i64>(env, None).unwrap();
+            assert!(env.is_same_object(obj, JObject::null()).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        test_utils::JVM_ENV.with(|env| {
+            macro_rules! roundtrip {
+                ($value:expr) => {
+                    let value = $value;
+                    let obj = super::box_primitive(env, value).unwrap();
+                    let unboxed = super::unbox_primitive(env, obj).unwrap();
+                    assert_eq!(value, unboxed);
+                    let obj = super::box_option(env, Some(value)).unwrap();
+                    let unboxed = super::unbox_option(env, obj).unwrap();
+                    assert_eq!(Some(value), unboxed);
+                };
+            }
+
+            roundtrip!(false);
+            roundtrip!(-5i8);
+            roundtrip!(0x263au16);
+            roundtrip!(-300i16);
+            roundtrip!(i32::MIN);
+            roundtrip!(i64::MAX);
+            roundtrip!(0.25f32);
+            roundtrip!(-1e300f64);
+        });
+    }
+
+    #[test]
+    fn test_unbox_null() {
+        test_utils::JVM_ENV.with(|env| {
+            assert_eq!(
+                super::unbox_option::<i32>(env, JObject::null()).unwrap(),
+                None
+            );
+
+            let result = super::unbox_primitive::<i32>(env, JObject::null());
+            assert!(matches!(result, Err(Error::JavaException)));
+            assert_eq!(
+                exception_message(env, "java/lang/NullPointerException"),
+                "Cannot unbox null value to java.lang.Integer"
+            );
+        });
+    }
+
+    #[test]
+    fn test_unbox_wrong_type() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = env.new_string("hello").unwrap();
+            let result = super::unbox_primitive::<i64>(env, obj.into());
+            assert!(matches!(result, Err(Error::JavaException)));
+            assert_eq!(
+                exception_message(env, "java/lang/ClassCastException"),
+                "Cannot cast java.lang.String to java.lang.Long"
+            );
+
+            let obj = super::box_primitive(env, 1i32).unwrap();
+            let result = super::unbox_option::<i16>(env, obj);
+            assert!(matches!(result, Err(Error::JavaException)));
+            assert_eq!(
+                exception_message(env, "java/lang/ClassCastException"),
+                "Cannot cast java.lang.Integer to java.lang.Short"
+            );
+        });
+    }
+}
@@ -0,0 +1,1052 @@
+use crate::{
+    arrays::{byte_array_to_vec, slice_to_byte_array},
+    exceptions::throw_error,
+    iter::{iter, JIterator},
+    list::JList,
+    map::{JMap, JMapEntries},
+    math::{sign_extend, JBigInteger},
+    primitives::{box_primitive, check_instance_of, unbox_primitive},
+    strings::{new_string, to_string},
+};
+use ::serde::{
+    de::{
+        self, value::SeqDeserializer, DeserializeOwned, DeserializeSeed, EnumAccess,
+        IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+    ser::{self, Serialize},
+};
+use jni::{
+    errors::Result,
+    objects::{AutoLocal, JObject},
+    JNIEnv,
+};
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt::Display,
+};
+
+/// Serialize a Rust value into a graph of Java objects.
+///
+/// Values are mapped as follows:
+///
+/// * Booleans, signed integers and floats are boxed into their `java.lang`
+///   box types. Unsigned integers are widened into the next larger signed
+///   type, and 128-bit integers and [`u64`]s which don't fit into a `long`
+///   become `java.math.BigInteger`s.
+/// * Strings and [`char`]s become `java.lang.String`s, and byte buffers become
+///   `byte[]`s.
+/// * [`None`], `()` and unit structs become `null`.
+/// * Sequences and tuples become `java.util.ArrayList`s.
+/// * Maps and structs become `java.util.LinkedHashMap`s. Struct fields are
+///   keyed by name.
+/// * Unit enum variants become their name as a `java.lang.String`. Other enum
+///   variants become a single-entry `java.util.LinkedHashMap` from the variant
+///   name to its contents.
+///
+/// If the value cannot be serialized, a `java.lang.IllegalArgumentException`
+/// is thrown.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new objects.
+/// * `value` - Value to serialize.
+pub fn to_java<'a: 'b, 'b, T: Serialize + ?Sized>(
+    env: &'b JNIEnv<'a>,
+    value: &T,
+) -> Result<JObject<'a>> {
+    finish(env, value.serialize(Serializer { env }))
+}
+
+/// Serialize a Rust struct into an instance of a Java class.
+///
+/// The value is first serialized with [`to_java`], and must produce a
+/// `java.util.Map`. A new instance of the class is then created with its
+/// no-argument constructor, and each map entry is assigned to the field of the
+/// same name using reflection. Nested maps are converted into the type of the
+/// field they are assigned to in the same way. Integers are converted into the
+/// field's numeric type. Fields which are not present keep their default
+/// values.
+///
+/// If a field does not exist or a value cannot be assigned to it, a
+/// `java.lang.IllegalArgumentException` is thrown.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new objects.
+/// * `value` - Value to serialize.
+/// * `class` - Name of the class to instantiate.
+pub fn to_java_object<'a: 'b, 'b, T: Serialize + ?Sized>(
+    env: &'b JNIEnv<'a>,
+    value: &T,
+    class: &str,
+) -> Result<JObject<'a>> {
+    let map = env.auto_local(to_java(env, value)?);
+    check_instance_of(env, map.as_obj(), "java/util/Map")?;
+    let class = env.auto_local(env.find_class(class)?);
+    env.call_static_method(
+        "io/github/gedgygedgy/rust/serde/Pojo",
+        "fromMap",
+        "(Ljava/util/Map;Ljava/lang/Class;)Ljava/lang/Object;",
+        &[map.as_obj().into(), class.as_obj().into()],
+    )?
+    .l()
+}
+
+/// Deserialize a Rust value from a graph of Java objects.
+///
+/// This accepts everything produced by [`to_java`], as well as:
+///
+/// * Any `java.lang.Number`, which is converted through `doubleValue()` if it
+///   is not a box type or `java.math.BigInteger`.
+/// * `java.lang.Character`s.
+/// * Java enum constants, which are deserialized from their `name()`.
+/// * Any `java.lang.Iterable` or object array, which is deserialized as a
+///   sequence.
+/// * Any `java.util.Map`.
+/// * Any other object, which is deserialized as a map of its non-static,
+///   non-transient fields, read using reflection.
+///
+/// If the object graph does not match the Rust type, a
+/// `java.lang.IllegalArgumentException` is thrown.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - Object to deserialize.
+pub fn from_java<'a: 'b, 'b, T: DeserializeOwned>(
+    env: &'b JNIEnv<'a>,
+    obj: JObject<'a>,
+) -> Result<T> {
+    finish(env, T::deserialize(Deserializer { env, obj }))
+}
+
+#[derive(Debug)]
+enum Error {
+    Jni(jni::errors::Error),
+    Message(String),
+}
+
+type SerdeResult<T> = std::result::Result<T, Error>;
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Jni(err) => Display::fmt(err, f),
+            Self::Message(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Message(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Message(msg.to_string())
+    }
+}
+
+impl From<jni::errors::Error> for Error {
+    fn from(err: jni::errors::Error) -> Self {
+        Self::Jni(err)
+    }
+}
+
+fn finish<T>(env: &JNIEnv, result: SerdeResult<T>) -> Result<T> {
+    match result {
+        Ok(value) => Ok(value),
+        Err(Error::Jni(err)) => Err(err),
+        Err(Error::Message(msg)) => throw_error(env, "java/lang/IllegalArgumentException", msg),
+    }
+}
+
+struct Serializer<'a: 'b, 'b> {
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> ser::Serializer for Serializer<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+    type SerializeSeq = SerializeList<'a, 'b>;
+    type SerializeTuple = SerializeList<'a, 'b>;
+    type SerializeTupleStruct = SerializeList<'a, 'b>;
+    type SerializeTupleVariant = SerializeList<'a, 'b>;
+    type SerializeMap = SerializeMap<'a, 'b>;
+    type SerializeStruct = SerializeMap<'a, 'b>;
+    type SerializeStructVariant = SerializeMap<'a, 'b>;
+
+    fn serialize_bool(self, v: bool) -> SerdeResult<JObject<'a>> {
+        Ok(box_primitive(self.env, v)?)
+    }
+
+    fn serialize_i8(self, v: i8) -> SerdeResult<JObject<'a>> {
+        Ok(box_primitive(self.env, v)?)
+    }
+
+    fn serialize_i16(self, v: i16) -> SerdeResult<JObject<'a>> {
+        Ok(box_primitive(self.env, v)?)
+    }
+
+    fn serialize_i32(self, v: i32) -> SerdeResult<JObject<'a>> {
+        Ok(box_primitive(self.env, v)?)
+    }
+
+    fn serialize_i64(self, v: i64) -> SerdeResult<JObject<'a>> {
+        Ok(box_primitive(self.env, v)?)
+    }
+
+    fn serialize_i128(self, v: i128) -> SerdeResult<JObject<'a>> {
+        Ok(JBigInteger::from_i128(self.env, v)?.into())
+    }
+
+    fn serialize_u8(self, v: u8) -> SerdeResult<JObject<'a>> {
+        self.serialize_i16(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> SerdeResult<JObject<'a>> {
+        self.serialize_i32(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> SerdeResult<JObject<'a>> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> SerdeResult<JObject<'a>> {
+        match i64::try_from(v) {
+            Ok(v) => self.serialize_i64(v),
+            Err(_) => self.serialize_u128(v.into()),
+        }
+    }
+
+    fn serialize_u128(self, v: u128) -> SerdeResult<JObject<'a>> {
+        Ok(JBigInteger::from_u128(self.env, v)?.into())
+    }
+
+    fn serialize_f32(self, v: f32) -> SerdeResult<JObject<'a>> {
+        Ok(box_primitive(self.env, v)?)
+    }
+
+    fn serialize_f64(self, v: f64) -> SerdeResult<JObject<'a>> {
+        Ok(box_primitive(self.env, v)?)
+    }
+
+    fn serialize_char(self, v: char) -> SerdeResult<JObject<'a>> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> SerdeResult<JObject<'a>> {
+        Ok(new_string(self.env, v)?.into())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> SerdeResult<JObject<'a>> {
+        Ok(slice_to_byte_array(self.env, v)?.into())
+    }
+
+    fn serialize_none(self) -> SerdeResult<JObject<'a>> {
+        Ok(JObject::null())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> SerdeResult<JObject<'a>> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> SerdeResult<JObject<'a>> {
+        Ok(JObject::null())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> SerdeResult<JObject<'a>> {
+        Ok(JObject::null())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> SerdeResult<JObject<'a>> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> SerdeResult<JObject<'a>> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> SerdeResult<JObject<'a>> {
+        let value = value.serialize(Serializer { env: self.env })?;
+        variant_map(self.env, variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> SerdeResult<SerializeList<'a, 'b>> {
+        SerializeList::new(self.env, len, None)
+    }
+
+    fn serialize_tuple(self, len: usize) -> SerdeResult<SerializeList<'a, 'b>> {
+        SerializeList::new(self.env, Some(len), None)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> SerdeResult<SerializeList<'a, 'b>> {
+        SerializeList::new(self.env, Some(len), None)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> SerdeResult<SerializeList<'a, 'b>> {
+        SerializeList::new(self.env, Some(len), Some(variant))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> SerdeResult<SerializeMap<'a, 'b>> {
+        SerializeMap::new(self.env, None)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> SerdeResult<SerializeMap<'a, 'b>> {
+        SerializeMap::new(self.env, None)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> SerdeResult<SerializeMap<'a, 'b>> {
+        SerializeMap::new(self.env, Some(variant))
+    }
+}
+
+// Wraps the contents of a non-unit enum variant in a single-entry map. The
+// local reference to the contents is deleted.
+fn variant_map<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    variant: &str,
+    value: JObject<'a>,
+) -> SerdeResult<JObject<'a>> {
+    let value = env.auto_local(value);
+    let key = env.auto_local(JObject::from(new_string(env, variant)?));
+    let map = JMap::new_linked(env)?;
+    env.delete_local_ref(map.put(key.as_obj(), value.as_obj())?)?;
+    Ok(map.into())
+}
+
+struct SerializeList<'a: 'b, 'b> {
+    env: &'b JNIEnv<'a>,
+    list: JList<'a, 'b>,
+    variant: Option<&'static str>,
+}
+
+impl<'a: 'b, 'b> SerializeList<'a, 'b> {
+    fn new(
+        env: &'b JNIEnv<'a>,
+        len: Option<usize>,
+        variant: Option<&'static str>,
+    ) -> SerdeResult<Self> {
+        let list = match len {
+            Some(len) => JList::with_capacity(env, len)?,
+            None => JList::new(env)?,
+        };
+        Ok(Self { env, list, variant })
+    }
+
+    fn add<T: Serialize + ?Sized>(&mut self, value: &T) -> SerdeResult<()> {
+        let value = self
+            .env
+            .auto_local(value.serialize(Serializer { env: self.env })?);
+        self.list.add(value.as_obj())?;
+        Ok(())
+    }
+
+    fn finish(self) -> SerdeResult<JObject<'a>> {
+        match self.variant {
+            Some(variant) => variant_map(self.env, variant, self.list.into()),
+            None => Ok(self.list.into()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeSeq for SerializeList<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> SerdeResult<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> SerdeResult<JObject<'a>> {
+        self.finish()
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeTuple for SerializeList<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> SerdeResult<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> SerdeResult<JObject<'a>> {
+        self.finish()
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeTupleStruct for SerializeList<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> SerdeResult<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> SerdeResult<JObject<'a>> {
+        self.finish()
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeTupleVariant for SerializeList<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> SerdeResult<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> SerdeResult<JObject<'a>> {
+        self.finish()
+    }
+}
+
+struct SerializeMap<'a: 'b, 'b> {
+    env: &'b JNIEnv<'a>,
+    map: JMap<'a, 'b>,
+    key: Option<AutoLocal<'a, 'b>>,
+    variant: Option<&'static str>,
+}
+
+impl<'a: 'b, 'b> SerializeMap<'a, 'b> {
+    fn new(env: &'b JNIEnv<'a>, variant: Option<&'static str>) -> SerdeResult<Self> {
+        Ok(Self {
+            env,
+            map: JMap::new_linked(env)?,
+            key: None,
+            variant,
+        })
+    }
+
+    fn put<T: Serialize + ?Sized>(&mut self, key: JObject<'a>, value: &T) -> SerdeResult<()> {
+        let value = self
+            .env
+            .auto_local(value.serialize(Serializer { env: self.env })?);
+        self.env
+            .delete_local_ref(self.map.put(key, value.as_obj())?)?;
+        Ok(())
+    }
+
+    fn put_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> SerdeResult<()> {
+        let key = self
+            .env
+            .auto_local(JObject::from(new_string(self.env, key)?));
+        self.put(key.as_obj(), value)
+    }
+
+    fn finish(self) -> SerdeResult<JObject<'a>> {
+        match self.variant {
+            Some(variant) => variant_map(self.env, variant, self.map.into()),
+            None => Ok(self.map.into()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeMap for SerializeMap<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> SerdeResult<()> {
+        let key = key.serialize(Serializer { env: self.env })?;
+        self.key = Some(self.env.auto_local(key));
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> SerdeResult<()> {
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => {
+                return Err(Error::Message(
+                    "serialize_value called before serialize_key".to_string(),
+                ))
+            }
+        };
+        self.put(key.as_obj(), value)
+    }
+
+    fn end(self) -> SerdeResult<JObject<'a>> {
+        self.finish()
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeStruct for SerializeMap<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> SerdeResult<()> {
+        self.put_field(key, value)
+    }
+
+    fn end(self) -> SerdeResult<JObject<'a>> {
+        self.finish()
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeStructVariant for SerializeMap<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> SerdeResult<()> {
+        self.put_field(key, value)
+    }
+
+    fn end(self) -> SerdeResult<JObject<'a>> {
+        self.finish()
+    }
+}
+
+struct Deserializer<'a: 'b, 'b> {
+    env: &'b JNIEnv<'a>,
+    obj: JObject<'a>,
+}
+
+impl<'a: 'b, 'b> Deserializer<'a, 'b> {
+    fn is_instance_of(&self, class: &str) -> SerdeResult<bool> {
+        Ok(self.env.is_instance_of(self.obj, class)?)
+    }
+
+    fn enum_name(&self) -> SerdeResult<String> {
+        let name = self.env.auto_local(
+            self.env
+                .call_method(self.obj, "name", "()Ljava/lang/String;", &[])?
+                .l()?,
+        );
+        Ok(to_string(self.env, name.as_obj())?)
+    }
+
+    fn map_access(&self) -> SerdeResult<JMapAccess<'a, 'b>> {
+        Ok(JMapAccess {
+            env: self.env,
+            entries: JMap::from_env(self.env, self.obj)?.entries()?,
+            value: None,
+        })
+    }
+
+    fn visit_big_integer<'de, V: Visitor<'de>>(&self, visitor: V) -> SerdeResult<V::Value> {
+        let bytes = JBigInteger::from_env(self.env, self.obj)?.to_bytes()?;
+        if let Some(buf) = sign_extend::<16>(&bytes) {
+            let value = i128::from_be_bytes(buf);
+            // Not all visitors accept 128-bit integers, so use the smallest
+            // type that fits.
+            if let Ok(value) = i64::try_from(value) {
+                visitor.visit_i64(value)
+            } else if let Ok(value) = u64::try_from(value) {
+                visitor.visit_u64(value)
+            } else {
+                visitor.visit_i128(value)
+            }
+        } else if let Some(buf) = sign_extend::<17>(&bytes).filter(|buf| buf[0] == 0) {
+            visitor.visit_u128(u128::from_be_bytes(buf[1..].try_into().unwrap()))
+        } else {
+            Err(Error::Message("BigInteger out of range".to_string()))
+        }
+    }
+}
+
+impl<'de, 'a: 'b, 'b> de::Deserializer<'de> for Deserializer<'a, 'b> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> SerdeResult<V::Value> {
+        let env = self.env;
+        let obj = self.obj;
+
+        if obj.is_null() {
+            visitor.visit_unit()
+        } else if self.is_instance_of("java/lang/String")? {
+            visitor.visit_string(to_string(env, obj)?)
+        } else if self.is_instance_of("java/lang/Boolean")? {
+            visitor.visit_bool(unbox_primitive(env, obj)?)
+        } else if self.is_instance_of("java/lang/Integer")? {
+            visitor.visit_i32(unbox_primitive(env, obj)?)
+        } else if self.is_instance_of("java/lang/Long")? {
+            visitor.visit_i64(unbox_primitive(env, obj)?)
+        } else if self.is_instance_of("java/lang/Double")? {
+            visitor.visit_f64(unbox_primitive(env, obj)?)
+        } else if self.is_instance_of("java/lang/Short")? {
+            visitor.visit_i16(unbox_primitive(env, obj)?)
+        } else if self.is_instance_of("java/lang/Byte")? {
+            visitor.visit_i8(unbox_primitive(env, obj)?)
+        } else if self.is_instance_of("java/lang/Float")? {
+            visitor.visit_f32(unbox_primitive(env, obj)?)
+        } else if self.is_instance_of("java/math/BigInteger")? {
+            self.visit_big_integer(visitor)
+        } else if self.is_instance_of("java/lang/Number")? {
+            visitor.visit_f64(env.call_method(obj, "doubleValue", "()D", &[])?.d()?)
+        } else if self.is_instance_of("java/lang/Character")? {
+            let c: u16 = unbox_primitive(env, obj)?;
+            match char::from_u32(c.into()) {
+                Some(c) => visitor.visit_char(c),
+                None => Err(Error::Message(format!("Unpaired surrogate {:#x}", c))),
+            }
+        } else if self.is_instance_of("[B")? {
+            let bytes = byte_array_to_vec(env, obj.into_inner())?;
+            visitor.visit_seq(SeqDeserializer::new(bytes.into_iter()))
+        } else if self.is_instance_of("java/lang/Enum")? {
+            visitor.visit_string(self.enum_name()?)
+        } else if self.is_instance_of("java/util/Map")? {
+            visitor.visit_map(self.map_access()?)
+        } else if self.is_instance_of("java/lang/Iterable")? {
+            visitor.visit_seq(JSeqAccess {
+                env,
+                iter: iter(env, obj)?,
+            })
+        } else if self.is_instance_of("[Ljava/lang/Object;")? {
+            let list = env.auto_local(
+                env.call_static_method(
+                    "java/util/Arrays",
+                    "asList",
+                    "([Ljava/lang/Object;)Ljava/util/List;",
+                    &[obj.into()],
+                )?
+                .l()?,
+            );
+            visitor.visit_seq(JSeqAccess {
+                env,
+                iter: iter(env, list.as_obj())?,
+            })
+        } else {
+            let map = env.auto_local(
+                env.call_static_method(
+                    "io/github/gedgygedgy/rust/serde/Pojo",
+                    "toMap",
+                    "(Ljava/lang/Object;)Ljava/util/Map;",
+                    &[obj.into()],
+                )?
+                .l()?,
+            );
+            let map = Deserializer {
+                env,
+                obj: map.as_obj(),
+            };
+            visitor.visit_map(map.map_access()?)
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> SerdeResult<V::Value> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> SerdeResult<V::Value> {
+        if !self.obj.is_null() && self.is_instance_of("[B")? {
+            visitor.visit_byte_buf(byte_array_to_vec(self.env, self.obj.into_inner())?)
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> SerdeResult<V::Value> {
+        if self.obj.is_null() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> SerdeResult<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> SerdeResult<V::Value> {
+        if self.obj.is_null() {
+            Err(Error::Message("Expected enum, found null".to_string()))
+        } else if self.is_instance_of("java/lang/String")? {
+            let variant = to_string(self.env, self.obj)?;
+            visitor.visit_enum(IntoDeserializer::<Error>::into_deserializer(variant))
+        } else if self.is_instance_of("java/lang/Enum")? {
+            let variant = self.enum_name()?;
+            visitor.visit_enum(IntoDeserializer::<Error>::into_deserializer(variant))
+        } else if self.is_instance_of("java/util/Map")? {
+            let mut map = self.map_access()?;
+            let (key, value) = match (map.entries.next(), map.entries.next()) {
+                (Some(entry), None) => entry?,
+                _ => {
+                    return Err(Error::Message(
+                        "Expected enum map with exactly one entry".to_string(),
+                    ))
+                }
+            };
+            visitor.visit_enum(JEnumAccess {
+                env: self.env,
+                key,
+                value,
+            })
+        } else {
+            Err(Error::Message(
+                "Expected enum, string or map for enum".to_string(),
+            ))
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct JSeqAccess<'a: 'b, 'b> {
+    env: &'b JNIEnv<'a>,
+    iter: JIterator<'a, 'b>,
+}
+
+impl<'de, 'a: 'b, 'b> SeqAccess<'de> for JSeqAccess<'a, 'b> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> SerdeResult<Option<T::Value>> {
+        match self.iter.next() {
+            Some(item) => {
+                let item = item?;
+                seed.deserialize(Deserializer {
+                    env: self.env,
+                    obj: item.as_obj(),
+                })
+                .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+struct JMapAccess<'a: 'b, 'b> {
+    env: &'b JNIEnv<'a>,
+    entries: JMapEntries<'a, 'b>,
+    value: Option<AutoLocal<'a, 'b>>,
+}
+
+impl<'de, 'a: 'b, 'b> MapAccess<'de> for JMapAccess<'a, 'b> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> SerdeResult<Option<K::Value>> {
+        match self.entries.next() {
+            Some(entry) => {
+                let (key, value) = entry?;
+                self.value = Some(value);
+                seed.deserialize(Deserializer {
+                    env: self.env,
+                    obj: key.as_obj(),
+                })
+                .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> SerdeResult<V::Value> {
+        let value = match self.value.take() {
+            Some(value) => value,
+            None => {
+                return Err(Error::Message(
+                    "next_value_seed called before next_key_seed".to_string(),
+                ))
+            }
+        };
+        seed.deserialize(Deserializer {
+            env: self.env,
+            obj: value.as_obj(),
+        })
+    }
+}
+
+struct JEnumAccess<'a: 'b, 'b> {
+    env: &'b JNIEnv<'a>,
+    key: AutoLocal<'a, 'b>,
+    value: AutoLocal<'a, 'b>,
+}
+
+impl<'de, 'a: 'b, 'b> EnumAccess<'de> for JEnumAccess<'a, 'b> {
+    type Error = Error;
+    type Variant = Deserializer<'a, 'b>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> SerdeResult<(V::Value, Deserializer<'a, 'b>)> {
+        let variant = seed.deserialize(Deserializer {
+            env: self.env,
+            obj: self.key.as_obj(),
+        })?;
+        // The map entry keeps the value alive, but the local reference needs
+        // to outlive this function, so hand it over to the variant.
+        let value = self.env.new_local_ref::<JObject>(self.value.as_obj())?;
+        Ok((
+            variant,
+            Deserializer {
+                env: self.env,
+                obj: value,
+            },
+        ))
+    }
+}
+
+impl<'de, 'a: 'b, 'b> VariantAccess<'de> for Deserializer<'a, 'b> {
+    type Error = Error;
+
+    fn unit_variant(self) -> SerdeResult<()> {
+        self.env.delete_local_ref(self.obj)?;
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> SerdeResult<T::Value> {
+        let value = self.env.auto_local(self.obj);
+        seed.deserialize(Deserializer {
+            env: self.env,
+            obj: value.as_obj(),
+        })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> SerdeResult<V::Value> {
+        let value = self.env.auto_local(self.obj);
+        de::Deserializer::deserialize_seq(
+            Deserializer {
+                env: self.env,
+                obj: value.as_obj(),
+            },
+            visitor,
+        )
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> SerdeResult<V::Value> {
+        let value = self.env.auto_local(self.obj);
+        de::Deserializer::deserialize_map(
+            Deserializer {
+                env: self.env,
+                obj: value.as_obj(),
+            },
+            visitor,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::{self, assert_exception};
+    use jni::{objects::JObject, JNIEnv};
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rectangle(u32, u32),
+        Polygon { sides: u8, name: Option<String> },
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Config {
+        name: String,
+        enabled: bool,
+        level: i8,
+        big: u64,
+        huge: i128,
+        unsigned_huge: u128,
+        initial: char,
+        ratio: f32,
+        tags: Vec<String>,
+        limits: BTreeMap<String, i64>,
+        shapes: Vec<Shape>,
+        parent: Option<Box<Config>>,
+        unit: (),
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    fn config() -> Config {
+        Config {
+            name: "root".to_string(),
+            enabled: true,
+            level: -3,
+            big: u64::MAX,
+            huge: i128::MIN,
+            unsigned_huge: u128::MAX,
+            initial: '\u{1f980}',
+            ratio: 0.5,
+            tags: vec!["a".to_string(), "b".to_string()],
+            limits: vec![("max".to_string(), i64::MAX), ("min".to_string(), 0)]
+                .into_iter()
+                .collect(),
+            shapes: vec![
+                Shape::Empty,
+                Shape::Circle(1.5),
+                Shape::Rectangle(2, 3),
+                Shape::Polygon {
+                    sides: 6,
+                    name: Some("hexagon".to_string()),
+                },
+            ],
+            parent: Some(Box::new(Config {
+                name: "parent".to_string(),
+                enabled: false,
+                level: 0,
+                big: 0,
+                huge: 0,
+                unsigned_huge: 0,
+                initial: 'p',
+                ratio: 0.0,
+                tags: vec![],
+                limits: BTreeMap::new(),
+                shapes: vec![],
+                parent: None,
+                unit: (),
+            })),
+            unit: (),
+        }
+    }
+
+    fn get<'a>(env: &JNIEnv<'a>, map: JObject<'a>, key: &str) -> JObject<'a> {
+        let key = env.new_string(key).unwrap();
+        env.call_method(
+            map,
+            "get",
+            "(Ljava/lang/Object;)Ljava/lang/Object;",
+            &[key.into()],
+        )
+        .unwrap()
+        .l()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        test_utils::JVM_ENV.with(|env| {
+            let value = config();
+            let obj = super::to_java(env, &value).unwrap();
+            assert!(env.is_instance_of(obj, "java/util/HashMap").unwrap());
+
+            let big = get(env, obj, "big");
+            assert!(env.is_instance_of(big, "java/math/BigInteger").unwrap());
+            let shapes = get(env, obj, "shapes");
+            assert!(env.is_instance_of(shapes, "java/util/ArrayList").unwrap());
+            let parent = get(env, obj, "parent");
+            assert!(get(env, parent, "parent").is_null());
+
+            let result: Config = super::from_java(env, obj).unwrap();
+            assert_eq!(result, value);
+        });
+    }
+
+    #[test]
+    fn test_pojo() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = super::to_java_object(env, &Point { x: 3, y: -4 }, "java/awt/Point").unwrap();
+            assert!(env.is_instance_of(obj, "java/awt/Point").unwrap());
+            let x = env
+                .call_method(obj, "getX", "()D", &[])
+                .unwrap()
+                .d()
+                .unwrap();
+            assert_eq!(x, 3.0);
+
+            let point: Point = super::from_java(env, obj).unwrap();
+            assert_eq!(point, Point { x: 3, y: -4 });
+        });
+    }
+
+    #[test]
+    fn test_java_types() {
+        test_utils::JVM_ENV.with(|env| {
+            let array = env
+                .new_object_array(2, "java/lang/Object", JObject::null())
+                .unwrap();
+            let unit = env
+                .get_static_field(
+                    "java/util/concurrent/TimeUnit",
+                    "SECONDS",
+                    "Ljava/util/concurrent/TimeUnit;",
+                )
+                .unwrap()
+                .l()
+                .unwrap();
+            env.set_object_array_element(array, 0, unit).unwrap();
+            let bytes = env.byte_array_from_slice(&[1, 2, 255]).unwrap();
+            env.set_object_array_element(array, 1, bytes).unwrap();
+
+            let result: (String, Vec<u8>) = super::from_java(env, array.into()).unwrap();
+            assert_eq!(result, ("SECONDS".to_string(), vec![1, 2, 255]));
+        });
+    }
+
+    #[test]
+    fn test_mismatch() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = super::to_java(env, "not a point").unwrap();
+            assert_exception(
+                env,
+                super::from_java::<Point>(env, obj),
+                "java/lang/IllegalArgumentException",
+            );
+        });
+    }
+}
@@ -0,0 +1,331 @@
+use crate::iter::JIterator;
+use jni::{
+    errors::Result,
+    objects::{JMethodID, JObject},
+    signature::{JavaType, Primitive},
+    JNIEnv,
+};
+use std::{
+    collections::{BTreeSet, HashSet},
+    hash::Hash,
+};
+
+/// Wrapper for [`JObject`]s that implement `java.util.Set`. Provides methods
+/// to add, remove and look up elements, and to iterate over them.
+///
+/// Membership is determined by the set itself, so elements are compared using
+/// their Java `equals()` and `hashCode()` methods (or `compareTo()` for sorted
+/// sets), not by reference.
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JSet<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    size: JMethodID<'a>,
+    contains: JMethodID<'a>,
+    add: JMethodID<'a>,
+    remove: JMethodID<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JSet<'a, 'b> {
+    /// Create a [`JSet`] from the environment and an object. This looks up
+    /// the necessary class and method IDs to call all of the methods on it so
+    /// that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/util/Set")?);
+
+        let size = env.get_method_id(&class, "size", "()I")?;
+        let contains = env.get_method_id(&class, "contains", "(Ljava/lang/Object;)Z")?;
+        let add = env.get_method_id(&class, "add", "(Ljava/lang/Object;)Z")?;
+        let remove = env.get_method_id(&class, "remove", "(Ljava/lang/Object;)Z")?;
+        Ok(Self {
+            internal: obj,
+            size,
+            contains,
+            add,
+            remove,
+            env,
+        })
+    }
+
+    /// Create a [`JSet`] which wraps a new, empty `java.util.HashSet`.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    pub fn new(env: &'b JNIEnv<'a>) -> Result<Self> {
+        Self::new_impl(env, "java/util/HashSet")
+    }
+
+    /// Create a [`JSet`] which wraps a new, empty `java.util.LinkedHashSet`.
+    /// Unlike [`new`](JSet::new), the resulting set iterates over its elements
+    /// in the order in which they were inserted.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    pub fn new_linked(env: &'b JNIEnv<'a>) -> Result<Self> {
+        Self::new_impl(env, "java/util/LinkedHashSet")
+    }
+
+    /// Get the number of elements in the set by calling
+    /// `java.util.Set.size()`.
+    pub fn size(&self) -> Result<usize> {
+        let size = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.size,
+                JavaType::Primitive(Primitive::Int),
+                &[],
+            )?
+            .i()?;
+        Ok(size as usize)
+    }
+
+    /// Check whether the set contains an element by calling
+    /// `java.util.Set.contains()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `obj` - Element to look up.
+    pub fn contains(&self, obj: JObject<'a>) -> Result<bool> {
+        self.call_bool(self.contains, obj)
+    }
+
+    /// Add an element by calling `java.util.Set.add()`. Returns `true` if the
+    /// set did not already contain the element.
+    ///
+    /// # Arguments
+    ///
+    /// * `obj` - Element to add.
+    pub fn add(&self, obj: JObject<'a>) -> Result<bool> {
+        self.call_bool(self.add, obj)
+    }
+
+    /// Remove an element by calling `java.util.Set.remove()`. Returns `true`
+    /// if the set contained the element.
+    ///
+    /// # Arguments
+    ///
+    /// * `obj` - Element to remove.
+    pub fn remove(&self, obj: JObject<'a>) -> Result<bool> {
+        self.call_bool(self.remove, obj)
+    }
+
+    /// Get a [`JIterator`] over the elements of the set.
+    pub fn iter(&self) -> Result<JIterator<'a, 'b>> {
+        crate::iter::iter(self.env, self.internal)
+    }
+
+    fn call_bool(&self, method: JMethodID<'a>, obj: JObject<'a>) -> Result<bool> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                method,
+                JavaType::Primitive(Primitive::Boolean),
+                &[obj.into()],
+            )?
+            .z()
+    }
+
+    fn new_impl(env: &'b JNIEnv<'a>, class: &str) -> Result<Self> {
+        let class = env.auto_local(env.find_class(class)?);
+        let obj = env.new_object(&class, "()V", &[])?;
+        Self::from_env(env, obj)
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JSet<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JSet<'a, 'b>> for JObject<'a> {
+    fn from(other: JSet<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+/// Create a new `java.util.HashSet` from the given [`HashSet`], converting
+/// each element into a Java object with the given function. The local
+/// reference returned by the function is deleted once the object has been
+/// added to the set.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new set.
+/// * `set` - Set to convert into a Java set.
+/// * `f` - Function to convert each element into a Java object.
+pub fn hash_set_to_set<'a: 'b, 'b, T, S>(
+    env: &'b JNIEnv<'a>,
+    set: &HashSet<T, S>,
+    f: impl FnMut(&'b JNIEnv<'a>, &T) -> Result<JObject<'a>>,
+) -> Result<JSet<'a, 'b>> {
+    fill_set(JSet::new(env)?, set, f)
+}
+
+/// Create a new `java.util.LinkedHashSet` from the given [`BTreeSet`],
+/// converting each element into a Java object with the given function. The
+/// resulting set iterates over its elements in the same order as the original
+/// [`BTreeSet`]. The local reference returned by the function is deleted once
+/// the object has been added to the set.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new set.
+/// * `set` - Set to convert into a Java set.
+/// * `f` - Function to convert each element into a Java object.
+pub fn btree_set_to_set<'a: 'b, 'b, T>(
+    env: &'b JNIEnv<'a>,
+    set: &BTreeSet<T>,
+    f: impl FnMut(&'b JNIEnv<'a>, &T) -> Result<JObject<'a>>,
+) -> Result<JSet<'a, 'b>> {
+    fill_set(JSet::new_linked(env)?, set, f)
+}
+
+/// Get a [`HashSet`] from the given `java.util.Set`, converting each element
+/// into a Rust value with the given function. The local reference passed to
+/// the function is deleted after it returns.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `set` - Set to convert into a [`HashSet`].
+/// * `f` - Function to convert each element into a Rust value.
+pub fn set_to_hash_set<'a: 'b, 'b, T: Eq + Hash>(
+    env: &'b JNIEnv<'a>,
+    set: JObject<'a>,
+    f: impl FnMut(&'b JNIEnv<'a>, JObject<'a>) -> Result<T>,
+) -> Result<HashSet<T>> {
+    set_to_elements(env, set, f)
+}
+
+/// Get a [`BTreeSet`] from the given `java.util.Set`, converting each element
+/// into a Rust value with the given function. The local reference passed to
+/// the function is deleted after it returns.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `set` - Set to convert into a [`BTreeSet`].
+/// * `f` - Function to convert each element into a Rust value.
+pub fn set_to_btree_set<'a: 'b, 'b, T: Ord>(
+    env: &'b JNIEnv<'a>,
+    set: JObject<'a>,
+    f: impl FnMut(&'b JNIEnv<'a>, JObject<'a>) -> Result<T>,
+) -> Result<BTreeSet<T>> {
+    set_to_elements(env, set, f)
+}
+
+fn fill_set<'a: 'b, 'b, 'c, T: 'c>(
+    set: JSet<'a, 'b>,
+    elements: impl IntoIterator<Item = &'c T>,
+    mut f: impl FnMut(&'b JNIEnv<'a>, &T) -> Result<JObject<'a>>,
+) -> Result<JSet<'a, 'b>> {
+    let env = set.env;
+    for element in elements {
+        let obj = env.auto_local(f(env, element)?);
+        set.add(obj.as_obj())?;
+    }
+    Ok(set)
+}
+
+fn set_to_elements<'a: 'b, 'b, T, C: Default + Extend<T>>(
+    env: &'b JNIEnv<'a>,
+    set: JObject<'a>,
+    mut f: impl FnMut(&'b JNIEnv<'a>, JObject<'a>) -> Result<T>,
+) -> Result<C> {
+    let set = JSet::from_env(env, set)?;
+    let mut result = C::default();
+    for element in set.iter()? {
+        result.extend(Some(f(env, element?.as_obj())?));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::JSet;
+    use crate::test_utils::{self, rust_string, string};
+    use jni::objects::JObject;
+    use std::collections::{BTreeSet, HashSet};
+
+    #[test]
+    fn test_set_methods() {
+        test_utils::JVM_ENV.with(|env| {
+            let set = JSet::new(env).unwrap();
+            assert_eq!(set.size().unwrap(), 0);
+
+            assert!(set.add(string(env, "a").unwrap()).unwrap());
+            assert!(set.add(string(env, "b").unwrap()).unwrap());
+            assert!(!set.add(string(env, "a").unwrap()).unwrap());
+            assert_eq!(set.size().unwrap(), 2);
+
+            // Distinct String objects with equal contents are the same element.
+            assert!(set.contains(string(env, "a").unwrap()).unwrap());
+            assert!(!set.contains(string(env, "c").unwrap()).unwrap());
+
+            assert!(set.remove(string(env, "a").unwrap()).unwrap());
+            assert!(!set.remove(string(env, "a").unwrap()).unwrap());
+            assert_eq!(set.size().unwrap(), 1);
+
+            let elements = set
+                .iter()
+                .unwrap()
+                .map(|obj| rust_string(env, obj.unwrap().as_obj()).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(elements, vec!["b".to_string()]);
+        });
+    }
+
+    #[test]
+    fn test_hash_set_to_set() {
+        test_utils::JVM_ENV.with(|env| {
+            let set = ["x", "y", "z"].iter().cloned().collect::<HashSet<_>>();
+
+            let jset = super::hash_set_to_set(env, &set, |env, s| string(env, s)).unwrap();
+            assert_eq!(jset.size().unwrap(), 3);
+            assert!(jset.contains(string(env, "y").unwrap()).unwrap());
+
+            let obj: JObject = jset.into();
+            assert!(env.is_instance_of(obj, "java/util/HashSet").unwrap());
+
+            let result = super::set_to_hash_set(env, obj, rust_string).unwrap();
+            assert_eq!(
+                result,
+                set.iter().map(|s| s.to_string()).collect::<HashSet<_>>()
+            );
+        });
+    }
+
+    #[test]
+    fn test_btree_set_to_set() {
+        test_utils::JVM_ENV.with(|env| {
+            let set = ["c", "a", "b"].iter().cloned().collect::<BTreeSet<_>>();
+
+            let jset = super::btree_set_to_set(env, &set, |env, s| string(env, s)).unwrap();
+            let elements = jset
+                .iter()
+                .unwrap()
+                .map(|obj| rust_string(env, obj.unwrap().as_obj()).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(elements, vec!["a", "b", "c"]);
+
+            let result = super::set_to_btree_set(env, *jset, rust_string).unwrap();
+            assert_eq!(
+                result,
+                set.iter().map(|s| s.to_string()).collect::<BTreeSet<_>>()
+            );
+        });
+    }
+}
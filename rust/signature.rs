@@ -0,0 +1,345 @@
+use crate::{
+    exceptions::JPanicException,
+    future::JFuture,
+    io::{JAsyncByteChannel, JInputStream, JOutputStream},
+    iter::JIterator,
+    list::JList,
+    map::JMap,
+    math::{JBigDecimal, JBigInteger},
+    set::JSet,
+    stream::JStream,
+    time::{JDate, JDuration, JInstant, JLocalDate, JLocalDateTime, JZonedDateTime},
+    uuid::JUuid,
+};
+use jni::objects::{GlobalRef, JByteBuffer, JClass, JObject, JString, JThrowable};
+use std::{
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    task::Waker,
+    time::{Duration, SystemTime},
+};
+use uuid::Uuid;
+
+/// Get the JNI signature of a type or method from Rust types at compile time.
+///
+/// `sig!(T)` is the type signature of `T`, and `sig!((A, B) -> R)` is the
+/// signature of a method which takes arguments of type `A` and `B` and
+/// returns `R`. Every type must implement [`JavaSignature`]. The result is a
+/// `&'static str`.
+///
+/// ```
+/// use jni::objects::{JObject, JString};
+/// use jni_utils::sig;
+///
+/// assert_eq!(sig!(i32), "I");
+/// assert_eq!(sig!(Vec<JString>), "[Ljava/lang/String;");
+/// assert_eq!(sig!(() -> ()), "()V");
+/// assert_eq!(
+///     sig!((JObject, i64) -> Option<bool>),
+///     "(Ljava/lang/Object;J)Ljava/lang/Boolean;"
+/// );
+/// ```
+#[macro_export]
+macro_rules! sig {
+    (($($arg:ty),* $(,)?) -> $ret:ty) => {
+        $crate::signature::Method::<($($arg,)*), $ret>::SIGNATURE
+    };
+    ($ty:ty) => {
+        <$ty as $crate::signature::JavaSignature>::SIGNATURE
+    };
+}
+
+/// Trait for Rust types which correspond to a Java type, such as primitives,
+/// wrappers of Java objects, and arrays.
+///
+/// This is used by [`sig!`](crate::sig) to build JNI signatures at compile
+/// time, so that the signature of a method always agrees with the Rust types
+/// used to call it. Object types only describe the class which the Rust type
+/// expects; the Java method may still declare a superclass or interface.
+pub trait JavaSignature {
+    /// JNI type signature, such as `I` or `Ljava/lang/String;`.
+    const SIGNATURE: &'static str;
+}
+
+// Signatures can't be concatenated into an array of exactly the right size
+// with generic types, so they are built in the smallest of several buffer
+// sizes which fits. Only the buffer which is used ends up in the binary, so at
+// most half of it is wasted.
+macro_rules! concat_signature {
+    ($($part:expr),* $(,)?) => {
+        const PARTS: &'static [&'static str] = &[$($part),*];
+        const LEN: usize = signature_len(Self::PARTS);
+        const BUFFER_16: SignatureBuffer<16> = SignatureBuffer::concat(Self::PARTS);
+        const BUFFER_32: SignatureBuffer<32> = SignatureBuffer::concat(Self::PARTS);
+        const BUFFER_64: SignatureBuffer<64> = SignatureBuffer::concat(Self::PARTS);
+        const BUFFER_128: SignatureBuffer<128> = SignatureBuffer::concat(Self::PARTS);
+        const BUFFER_256: SignatureBuffer<256> = SignatureBuffer::concat(Self::PARTS);
+        const BUFFER_512: SignatureBuffer<512> = SignatureBuffer::concat(Self::PARTS);
+        const BUFFER_1024: SignatureBuffer<1024> = SignatureBuffer::concat(Self::PARTS);
+
+        const CONCAT: &'static str = match Self::LEN {
+            0..=16 => Self::BUFFER_16.as_str(),
+            17..=32 => Self::BUFFER_32.as_str(),
+            33..=64 => Self::BUFFER_64.as_str(),
+            65..=128 => Self::BUFFER_128.as_str(),
+            129..=256 => Self::BUFFER_256.as_str(),
+            257..=512 => Self::BUFFER_512.as_str(),
+            513..=1024 => Self::BUFFER_1024.as_str(),
+            _ => panic!("JNI signature is too long"),
+        };
+    };
+}
+
+const fn signature_len(parts: &[&str]) -> usize {
+    let mut len = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        len += parts[i].len();
+        i += 1;
+    }
+    len
+}
+
+#[doc(hidden)]
+pub struct SignatureBuffer<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> SignatureBuffer<N> {
+    // Buffers which are too small are still built, but are never used.
+    const fn concat(parts: &[&str]) -> Self {
+        let mut bytes = [0; N];
+        let mut len = 0;
+        let mut i = 0;
+        while i < parts.len() {
+            let part = parts[i].as_bytes();
+            let mut j = 0;
+            while j < part.len() && len < N {
+                bytes[len] = part[j];
+                len += 1;
+                j += 1;
+            }
+            i += 1;
+        }
+        Self { bytes, len }
+    }
+
+    const fn as_str(&self) -> &str {
+        match std::str::from_utf8(self.bytes.split_at(self.len).0) {
+            Ok(s) => s,
+            Err(_) => panic!("JNI signature is not valid UTF-8"),
+        }
+    }
+}
+
+/// Signature of a method with arguments `A` (a tuple) and return type `R`.
+/// Use [`sig!`](crate::sig) rather than naming this directly.
+#[doc(hidden)]
+pub struct Method<A, R>(PhantomData<fn(A) -> R>);
+
+impl<A: JavaArguments, R: JavaSignature> Method<A, R> {
+    concat_signature!("(", A::SIGNATURE, ")", R::SIGNATURE);
+
+    pub const SIGNATURE: &'static str = Self::CONCAT;
+}
+
+/// Trait for tuples of method arguments. This is an implementation detail of
+/// [`sig!`](crate::sig).
+#[doc(hidden)]
+pub trait JavaArguments {
+    /// Concatenated type signatures of the arguments.
+    const SIGNATURE: &'static str;
+}
+
+struct ArgumentsOf<T>(PhantomData<T>);
+
+macro_rules! impl_arguments {
+    ($($t:ident),*) => {
+        impl<$($t: JavaSignature),*> ArgumentsOf<($($t,)*)> {
+            concat_signature!($($t::SIGNATURE),*);
+        }
+
+        impl<$($t: JavaSignature),*> JavaArguments for ($($t,)*) {
+            const SIGNATURE: &'static str = ArgumentsOf::<Self>::CONCAT;
+        }
+    };
+}
+
+impl_arguments!();
+impl_arguments!(A);
+impl_arguments!(A, B);
+impl_arguments!(A, B, C);
+impl_arguments!(A, B, C, D);
+impl_arguments!(A, B, C, D, E);
+impl_arguments!(A, B, C, D, E, F);
+impl_arguments!(A, B, C, D, E, F, G);
+impl_arguments!(A, B, C, D, E, F, G, H);
+impl_arguments!(A, B, C, D, E, F, G, H, I);
+impl_arguments!(A, B, C, D, E, F, G, H, I, J);
+impl_arguments!(A, B, C, D, E, F, G, H, I, J, K);
+impl_arguments!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+macro_rules! impl_signature {
+    ($($ty:ty => $sig:expr),* $(,)?) => {
+        $(
+            impl JavaSignature for $ty {
+                const SIGNATURE: &'static str = $sig;
+            }
+        )*
+    };
+}
+
+macro_rules! impl_wrapper_signature {
+    ($($ty:ident => $class:literal),* $(,)?) => {
+        $(
+            impl<'a: 'b, 'b> JavaSignature for $ty<'a, 'b> {
+                const SIGNATURE: &'static str = concat!("L", $class, ";");
+            }
+        )*
+    };
+}
+
+impl_signature! {
+    () => "V",
+    bool => "Z",
+    i8 => "B",
+    u16 => "C",
+    i16 => "S",
+    i32 => "I",
+    i64 => "J",
+    f32 => "F",
+    f64 => "D",
+    JObject<'_> => "Ljava/lang/Object;",
+    JString<'_> => "Ljava/lang/String;",
+    JClass<'_> => "Ljava/lang/Class;",
+    JThrowable<'_> => "Ljava/lang/Throwable;",
+    JByteBuffer<'_> => "Ljava/nio/ByteBuffer;",
+    GlobalRef => "Ljava/lang/Object;",
+    String => "Ljava/lang/String;",
+    str => "Ljava/lang/String;",
+    Uuid => "Ljava/util/UUID;",
+    Duration => "Ljava/time/Duration;",
+    SystemTime => "Ljava/time/Instant;",
+    PathBuf => "Ljava/nio/file/Path;",
+    Path => "Ljava/nio/file/Path;",
+    Waker => "Lio/github/gedgygedgy/rust/task/Waker;",
+}
+
+impl_wrapper_signature! {
+    JPanicException => "io/github/gedgygedgy/rust/panic/PanicException",
+    JFuture => "io/github/gedgygedgy/rust/future/Future",
+    JInputStream => "java/io/InputStream",
+    JOutputStream => "java/io/OutputStream",
+    JAsyncByteChannel => "java/nio/channels/AsynchronousByteChannel",
+    JIterator => "java/util/Iterator",
+    JList => "java/util/List",
+    JMap => "java/util/Map",
+    JBigInteger => "java/math/BigInteger",
+    JBigDecimal => "java/math/BigDecimal",
+    JSet => "java/util/Set",
+    JStream => "io/github/gedgygedgy/rust/stream/Stream",
+    JDuration => "java/time/Duration",
+    JInstant => "java/time/Instant",
+    JDate => "java/util/Date",
+    JLocalDate => "java/time/LocalDate",
+    JLocalDateTime => "java/time/LocalDateTime",
+    JZonedDateTime => "java/time/ZonedDateTime",
+    JUuid => "java/util/UUID",
+}
+
+impl<T: JavaSignature + ?Sized> JavaSignature for &T {
+    const SIGNATURE: &'static str = T::SIGNATURE;
+}
+
+struct ArrayOf<T>(PhantomData<T>);
+
+impl<T: JavaSignature> ArrayOf<T> {
+    concat_signature!("[", T::SIGNATURE);
+}
+
+/// Java array of `T`.
+impl<T: JavaSignature> JavaSignature for [T] {
+    const SIGNATURE: &'static str = ArrayOf::<T>::CONCAT;
+}
+
+/// Java array of `T`.
+impl<T: JavaSignature> JavaSignature for Vec<T> {
+    const SIGNATURE: &'static str = ArrayOf::<T>::CONCAT;
+}
+
+/// Same as `T`, except that primitives are replaced with their box classes,
+/// like the [`FromJava`](crate::convert::FromJava) implementation of
+/// [`Option<T>`].
+impl<T: JavaSignature> JavaSignature for Option<T> {
+    const SIGNATURE: &'static str = boxed(T::SIGNATURE);
+}
+
+const fn boxed(sig: &'static str) -> &'static str {
+    match sig.as_bytes() {
+        b"Z" => "Ljava/lang/Boolean;",
+        b"B" => "Ljava/lang/Byte;",
+        b"C" => "Ljava/lang/Character;",
+        b"S" => "Ljava/lang/Short;",
+        b"I" => "Ljava/lang/Integer;",
+        b"J" => "Ljava/lang/Long;",
+        b"F" => "Ljava/lang/Float;",
+        b"D" => "Ljava/lang/Double;",
+        _ => sig,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{future::JFuture, uuid::JUuid};
+    use jni::objects::{JObject, JString};
+    use std::task::Waker;
+
+    #[test]
+    fn test_type_signatures() {
+        assert_eq!(sig!(()), "V");
+        assert_eq!(sig!(u16), "C");
+        assert_eq!(sig!(JObject), "Ljava/lang/Object;");
+        assert_eq!(sig!(&str), "Ljava/lang/String;");
+        assert_eq!(sig!(JUuid), "Ljava/util/UUID;");
+        assert_eq!(sig!([i8]), "[B");
+        assert_eq!(sig!(Vec<Vec<f64>>), "[[D");
+        assert_eq!(sig!(Vec<JString>), "[Ljava/lang/String;");
+        assert_eq!(sig!(Option<i32>), "Ljava/lang/Integer;");
+        assert_eq!(sig!(Option<String>), "Ljava/lang/String;");
+        assert_eq!(sig!(Option<Vec<i32>>), "[I");
+    }
+
+    #[test]
+    fn test_method_signatures() {
+        assert_eq!(sig!(() -> ()), "()V");
+        assert_eq!(sig!((bool) -> ()), "(Z)V");
+        assert_eq!(
+            sig!((Waker,) -> JFuture),
+            "(Lio/github/gedgygedgy/rust/task/Waker;)Lio/github/gedgygedgy/rust/future/Future;"
+        );
+        assert_eq!(
+            sig!((JObject, i64, &[u16]) -> Option<f32>),
+            "(Ljava/lang/Object;J[C)Ljava/lang/Float;"
+        );
+
+        let waker = "Lio/github/gedgygedgy/rust/task/Waker;";
+        assert_eq!(
+            sig!((Waker, Waker, Waker, Waker, Waker) -> Vec<Waker>),
+            format!("({})[{}", waker.repeat(5), waker)
+        );
+    }
+
+    #[test]
+    fn test_generic_signature() {
+        fn array_of<T: super::JavaSignature>() -> &'static str {
+            sig!((T) -> Vec<T>)
+        }
+
+        assert_eq!(array_of::<i64>(), "(J)[J");
+        assert_eq!(
+            array_of::<String>(),
+            "(Ljava/lang/String;)[Ljava/lang/String;"
+        );
+    }
+}
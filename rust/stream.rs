@@ -10,7 +10,7 @@ use static_assertions::assert_impl_all;
 use std::{
     convert::TryFrom,
     pin::Pin,
-    task::{Context, Poll},
+    task::{Context, Poll, Waker},
 };
 
 /// Wrapper for [`JObject`]s that implement
@@ -40,11 +40,8 @@ impl<'a: 'b, 'b> JStream<'a, 'b> {
     pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
         let class = env.auto_local(env.find_class("io/github/gedgygedgy/rust/stream/Stream")?);
 
-        let poll_next = env.get_method_id(
-            &class,
-            "pollNext",
-            "(Lio/github/gedgygedgy/rust/task/Waker;)Lio/github/gedgygedgy/rust/task/PollResult;",
-        )?;
+        let poll_next =
+            env.get_method_id(&class, "pollNext", crate::sig!((Waker) -> JPollResult))?;
         Ok(Self {
             internal: obj,
             poll_next,
@@ -165,33 +162,11 @@ impl Stream for JSendStream {
 
 assert_impl_all!(JSendStream: Send);
 
-struct JStreamPoll<'a: 'b, 'b> {
-    internal: JObject<'a>,
-    get: JMethodID<'a>,
-    env: &'b JNIEnv<'a>,
-}
-
-impl<'a: 'b, 'b> JStreamPoll<'a, 'b> {
-    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
-        let class = env.auto_local(env.find_class("io/github/gedgygedgy/rust/stream/StreamPoll")?);
-
-        let get = env.get_method_id(&class, "get", "()Ljava/lang/Object;")?;
-        Ok(Self {
-            internal: obj,
-            get,
-            env,
-        })
-    }
-
-    pub fn get(&self) -> Result<JObject<'a>> {
-        self.env
-            .call_method_unchecked(
-                self.internal,
-                self.get,
-                JavaType::Object("java/lang/Object".into()),
-                &[],
-            )?
-            .l()
+crate::java_class! {
+    struct JStreamPoll = "io/github/gedgygedgy/rust/stream/StreamPoll" {
+        methods {
+            fn get() -> JObject<'a> = "get", "()Ljava/lang/Object;";
+        }
     }
 }
 
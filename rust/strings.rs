@@ -0,0 +1,230 @@
+use crate::{exceptions::throw_error, primitives::check_instance_of};
+use jni::{
+    errors::{Error, Result},
+    objects::{JObject, JString},
+    sys::{self, jsize},
+    JNIEnv,
+};
+
+// Calls a raw JNI function which isn't wrapped by JNIEnv.
+macro_rules! raw_call {
+    ($raw:expr, $name:ident $(, $arg:expr)*) => {
+        match (**$raw).$name {
+            Some(method) => method($raw $(, $arg)*),
+            None => return Err(Error::JNIEnvMethodNotFound(stringify!($name))),
+        }
+    };
+}
+
+/// Convert a `java.lang.String` into a Rust [`String`].
+///
+/// Unlike [`JNIEnv::get_string()`], this does not go through Java's modified
+/// UTF-8 encoding. Instead, the string's UTF-16 contents are copied with
+/// `GetStringRegion` and decoded directly, so supplementary characters and
+/// embedded NULs are handled correctly. Strings which consist only of
+/// non-NUL ASCII characters take a fast path which copies the bytes with
+/// `GetStringUTFRegion` and skips decoding entirely.
+///
+/// If the string contains an unpaired surrogate, which cannot be represented
+/// in a Rust [`String`], a `java.lang.IllegalArgumentException` is thrown and
+/// [`Error::JavaException`] is returned. Use [`to_string_lossy`] to replace
+/// unpaired surrogates instead. If the object is not a `java.lang.String`, a
+/// `java.lang.ClassCastException` is thrown.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - String to convert.
+pub fn to_string<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<String> {
+    match to_string_internal(env, obj)? {
+        Ok(s) => Ok(s),
+        Err(chars) => match String::from_utf16(&chars) {
+            Ok(s) => Ok(s),
+            Err(err) => throw_error(env, "java/lang/IllegalArgumentException", err.to_string()),
+        },
+    }
+}
+
+/// Convert a `java.lang.String` into a Rust [`String`], replacing any unpaired
+/// surrogates with [`U+FFFD REPLACEMENT CHARACTER`](std::char::REPLACEMENT_CHARACTER).
+///
+/// See [`to_string`] for more details.
+///
+/// # Arguments
+///
+/// * `env` - Java environment to use.
+/// * `obj` - String to convert.
+pub fn to_string_lossy<'a: 'b, 'b>(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<String> {
+    Ok(match to_string_internal(env, obj)? {
+        Ok(s) => s,
+        Err(chars) => String::from_utf16_lossy(&chars),
+    })
+}
+
+/// Create a new `java.lang.String` from a Rust string.
+///
+/// Strings which consist only of non-NUL ASCII characters are passed to
+/// `NewStringUTF` as-is. All other strings are encoded as UTF-16 and passed to
+/// `NewString`, rather than being re-encoded as modified UTF-8.
+///
+/// # Arguments
+///
+/// * `env` - Java environment in which to create the new string.
+/// * `s` - String to convert.
+pub fn new_string<'a: 'b, 'b>(env: &'b JNIEnv<'a>, s: &str) -> Result<JString<'a>> {
+    if is_plain_ascii(s.as_bytes()) {
+        return env.new_string(s);
+    }
+
+    let chars = s.encode_utf16().collect::<Vec<_>>();
+    let raw = env.get_native_interface();
+    let obj = unsafe { raw_call!(raw, NewString, chars.as_ptr(), chars.len() as jsize) };
+    if obj.is_null() {
+        return Err(if env.exception_check()? {
+            Error::JavaException
+        } else {
+            Error::NullPtr("NewString result")
+        });
+    }
+    Ok(JString::from(obj))
+}
+
+// Returns Ok if the fast path could be taken, or Err with the string's UTF-16
+// contents otherwise.
+fn to_string_internal<'a: 'b, 'b>(
+    env: &'b JNIEnv<'a>,
+    obj: JObject<'a>,
+) -> Result<std::result::Result<String, Vec<u16>>> {
+    if obj.is_null() {
+        return Err(Error::NullPtr("to_string obj argument"));
+    }
+    check_instance_of(env, obj, "java/lang/String")?;
+
+    let raw = env.get_native_interface();
+    let obj = obj.into_inner() as sys::jstring;
+    unsafe {
+        let len = raw_call!(raw, GetStringLength, obj);
+        let utf_len = raw_call!(raw, GetStringUTFLength, obj);
+
+        // In modified UTF-8, every character other than 0x01-0x7F takes up at
+        // least two bytes, so the lengths only match if the string is plain
+        // ASCII.
+        if len == utf_len {
+            // Some implementations write a trailing NUL after the region.
+            let mut bytes = vec![0u8; len as usize + 1];
+            raw_call!(
+                raw,
+                GetStringUTFRegion,
+                obj,
+                0,
+                len,
+                bytes.as_mut_ptr() as *mut _
+            );
+            check_exception(env)?;
+            bytes.truncate(len as usize);
+            debug_assert!(is_plain_ascii(&bytes));
+            Ok(Ok(String::from_utf8_unchecked(bytes)))
+        } else {
+            let mut chars = vec![0u16; len as usize];
+            raw_call!(raw, GetStringRegion, obj, 0, len, chars.as_mut_ptr());
+            check_exception(env)?;
+            Ok(Err(chars))
+        }
+    }
+}
+
+fn check_exception(env: &JNIEnv) -> Result<()> {
+    if env.exception_check()? {
+        Err(Error::JavaException)
+    } else {
+        Ok(())
+    }
+}
+
+fn is_plain_ascii(bytes: &[u8]) -> bool {
+    bytes.iter().all(|b| (0x01..0x80).contains(b))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::{self, assert_exception};
+    use jni::{errors::Error, objects::JObject};
+
+    const TESTS: &[&str] = &[
+        "",
+        "Hello, world!",
+        "caf\u{e9}",
+        "embedded\0nul",
+        "\u{1f980} supplementary \u{10ffff}",
+        "\u{65e5}\u{672c}\u{8a9e}",
+    ];
+
+    #[test]
+    fn test_new_string() {
+        test_utils::JVM_ENV.with(|env| {
+            for test in TESTS {
+                let obj = super::new_string(env, test).unwrap();
+                let len = env
+                    .call_method(obj, "length", "()I", &[])
+                    .unwrap()
+                    .i()
+                    .unwrap();
+                assert_eq!(len as usize, test.encode_utf16().count());
+                for (i, expected) in test.encode_utf16().enumerate() {
+                    let actual = env
+                        .call_method(obj, "charAt", "(I)C", &[(i as i32).into()])
+                        .unwrap()
+                        .c()
+                        .unwrap();
+                    assert_eq!(actual, expected);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        test_utils::JVM_ENV.with(|env| {
+            for test in TESTS {
+                let obj = super::new_string(env, test).unwrap();
+                assert_eq!(super::to_string(env, *obj).unwrap(), *test);
+                assert_eq!(super::to_string_lossy(env, *obj).unwrap(), *test);
+            }
+        });
+    }
+
+    #[test]
+    fn test_unpaired_surrogate() {
+        test_utils::JVM_ENV.with(|env| {
+            let chars = env.new_char_array(3).unwrap();
+            env.set_char_array_region(chars, 0, &[0x61, 0xd800, 0x62])
+                .unwrap();
+            let obj = env
+                .new_object("java/lang/String", "([C)V", &[chars.into()])
+                .unwrap();
+
+            assert_eq!(super::to_string_lossy(env, obj).unwrap(), "a\u{fffd}b");
+
+            assert_exception(
+                env,
+                super::to_string(env, obj),
+                "java/lang/IllegalArgumentException",
+            );
+        });
+    }
+
+    #[test]
+    fn test_wrong_type() {
+        test_utils::JVM_ENV.with(|env| {
+            let obj = env.new_object("java/lang/Object", "()V", &[]).unwrap();
+            assert_exception(
+                env,
+                super::to_string(env, obj),
+                "java/lang/ClassCastException",
+            );
+
+            let result = super::to_string(env, JObject::null());
+            assert!(matches!(result, Err(Error::NullPtr(_))));
+        });
+    }
+}
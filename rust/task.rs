@@ -1,9 +1,4 @@
-use ::jni::{
-    errors::Result,
-    objects::{JMethodID, JObject},
-    signature::JavaType,
-    JNIEnv,
-};
+use ::jni::{errors::Result, objects::JObject, JNIEnv};
 use std::task::Waker;
 
 /// Wraps the given waker in a `io.github.gedgygedgy.rust.task.Waker` object.
@@ -29,64 +24,17 @@ pub fn waker<'a: 'b, 'b>(env: &'b JNIEnv<'a>, waker: Waker) -> Result<JObject<'a
     Ok(obj)
 }
 
-/// Wrapper for [`JObject`]s that implement
-/// `io.github.gedgygedgy.rust.task.PollResult`. Provides method to get the
-/// poll result.
-///
-/// Looks up the class and method IDs on creation rather than for every method
-/// call.
-pub struct JPollResult<'a: 'b, 'b> {
-    internal: JObject<'a>,
-    get: JMethodID<'a>,
-    env: &'b JNIEnv<'a>,
-}
-
-impl<'a: 'b, 'b> JPollResult<'a, 'b> {
-    /// Create a [`JPollResult`] from the environment and an object. This looks
-    /// up the necessary class and method IDs to call all of the methods on it
-    /// so that extra work doesn't need to be done on every method call.
-    ///
-    /// # Arguments
-    ///
-    /// * `env` - Java environment to use.
-    /// * `obj` - Object to wrap.
-    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
-        let class = env.auto_local(env.find_class("io/github/gedgygedgy/rust/task/PollResult")?);
-
-        let get = env.get_method_id(&class, "get", "()Ljava/lang/Object;")?;
-        Ok(Self {
-            internal: obj,
-            get,
-            env,
-        })
-    }
-
-    /// Gets the object associated with the [`JPollResult`] by calling
-    /// `io.github.gedgygedgy.rust.task.PollResult.get()`. Can throw an
-    /// exception.
-    pub fn get(&self) -> Result<JObject<'a>> {
-        self.env
-            .call_method_unchecked(
-                self.internal,
-                self.get,
-                JavaType::Object("java/lang/Object".into()),
-                &[],
-            )?
-            .l()
-    }
-}
-
-impl<'a: 'b, 'b> ::std::ops::Deref for JPollResult<'a, 'b> {
-    type Target = JObject<'a>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.internal
-    }
-}
-
-impl<'a: 'b, 'b> From<JPollResult<'a, 'b>> for JObject<'a> {
-    fn from(other: JPollResult<'a, 'b>) -> JObject<'a> {
-        other.internal
+crate::java_class! {
+    /// Wrapper for [`JObject`]s that implement
+    /// `io.github.gedgygedgy.rust.task.PollResult`. Provides method to get the
+    /// poll result.
+    pub struct JPollResult = "io/github/gedgygedgy/rust/task/PollResult" {
+        methods {
+            /// Gets the object associated with the [`JPollResult`] by calling
+            /// `io.github.gedgygedgy.rust.task.PollResult.get()`. Can throw an
+            /// exception.
+            pub fn get() -> JObject<'a> = "get", "()Ljava/lang/Object;";
+        }
     }
 }
 
@@ -0,0 +1,1228 @@
+use crate::exceptions::throw_error;
+use jni::{
+    errors::Result,
+    objects::{AutoLocal, JMethodID, JObject},
+    signature::{JavaType, Primitive},
+    sys::{jint, jlong},
+    JNIEnv,
+};
+use std::{
+    convert::TryFrom,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+const NANOS_PER_MILLI: u32 = 1_000_000;
+
+/// Wrapper for [`JObject`]s that contain `java.time.Duration`. Provides
+/// methods to convert to and from a [`Duration`].
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JDuration<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    get_seconds: JMethodID<'a>,
+    get_nano: JMethodID<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JDuration<'a, 'b> {
+    /// Create a [`JDuration`] from the environment and an object. This looks
+    /// up the necessary class and method IDs to call all of the methods on it
+    /// so that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/time/Duration")?);
+        Self::from_env_impl(env, obj, class)
+    }
+
+    /// Create a [`JDuration`] which wraps a new `java.time.Duration` created
+    /// from a given [`Duration`]. If the duration is too long to be
+    /// represented, a `java.lang.ArithmeticException` is thrown.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `duration` - [`Duration`] to convert into a `java.time.Duration`.
+    pub fn new(env: &'b JNIEnv<'a>, duration: Duration) -> Result<Self> {
+        let seconds = match jlong::try_from(duration.as_secs()) {
+            Ok(seconds) => seconds,
+            Err(_) => {
+                return throw_error(
+                    env,
+                    "java/lang/ArithmeticException",
+                    "Duration is too long for java.time.Duration",
+                )
+            }
+        };
+        Self::new_impl(env, seconds, duration.subsec_nanos())
+    }
+
+    /// Convert the `java.time.Duration` into a [`Duration`]. If the duration
+    /// is negative, a `java.lang.ArithmeticException` is thrown.
+    pub fn as_duration(&self) -> Result<Duration> {
+        let (seconds, nanos) = self.seconds_and_nanos()?;
+        match u64::try_from(seconds) {
+            Ok(seconds) => Ok(Duration::new(seconds, nanos)),
+            Err(_) => throw_error(
+                self.env,
+                "java/lang/ArithmeticException",
+                "Negative java.time.Duration cannot be converted to Duration",
+            ),
+        }
+    }
+
+    fn seconds_and_nanos(&self) -> Result<(jlong, u32)> {
+        let seconds = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.get_seconds,
+                JavaType::Primitive(Primitive::Long),
+                &[],
+            )?
+            .j()?;
+        let nanos = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.get_nano,
+                JavaType::Primitive(Primitive::Int),
+                &[],
+            )?
+            .i()?;
+        Ok((seconds, nanos as u32))
+    }
+
+    fn new_impl(env: &'b JNIEnv<'a>, seconds: jlong, nanos: u32) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/time/Duration")?);
+        let obj = env
+            .call_static_method(
+                &class,
+                "ofSeconds",
+                "(JJ)Ljava/time/Duration;",
+                &[seconds.into(), (nanos as jlong).into()],
+            )?
+            .l()?;
+        Self::from_env_impl(env, obj, class)
+    }
+
+    fn from_env_impl(
+        env: &'b JNIEnv<'a>,
+        obj: JObject<'a>,
+        class: AutoLocal<'a, 'b>,
+    ) -> Result<Self> {
+        let get_seconds = env.get_method_id(&class, "getSeconds", "()J")?;
+        let get_nano = env.get_method_id(&class, "getNano", "()I")?;
+        Ok(Self {
+            internal: obj,
+            get_seconds,
+            get_nano,
+            env,
+        })
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JDuration<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JDuration<'a, 'b>> for JObject<'a> {
+    fn from(other: JDuration<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `java.time.Instant`. Provides methods
+/// to convert to and from a [`SystemTime`].
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JInstant<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    get_epoch_second: JMethodID<'a>,
+    get_nano: JMethodID<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JInstant<'a, 'b> {
+    /// Create a [`JInstant`] from the environment and an object. This looks
+    /// up the necessary class and method IDs to call all of the methods on it
+    /// so that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/time/Instant")?);
+        Self::from_env_impl(env, obj, class)
+    }
+
+    /// Create a [`JInstant`] which wraps a new `java.time.Instant` created
+    /// from a given [`SystemTime`]. If the time is outside the range of
+    /// `java.time.Instant`, a `java.lang.ArithmeticException` is thrown.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `time` - [`SystemTime`] to convert into a `java.time.Instant`.
+    pub fn new(env: &'b JNIEnv<'a>, time: SystemTime) -> Result<Self> {
+        match epoch_seconds_and_nanos(time) {
+            Some((seconds, nanos)) => Self::new_impl(env, seconds, nanos),
+            None => throw_error(
+                env,
+                "java/lang/ArithmeticException",
+                "SystemTime is out of range for java.time.Instant",
+            ),
+        }
+    }
+
+    /// Convert the `java.time.Instant` into a [`SystemTime`]. If the instant
+    /// is outside the range of [`SystemTime`], a
+    /// `java.lang.ArithmeticException` is thrown.
+    pub fn as_system_time(&self) -> Result<SystemTime> {
+        let (seconds, nanos) = self.seconds_and_nanos()?;
+        match system_time_from_epoch(seconds, nanos) {
+            Some(time) => Ok(time),
+            None => throw_error(
+                self.env,
+                "java/lang/ArithmeticException",
+                "java.time.Instant is out of range for SystemTime",
+            ),
+        }
+    }
+
+    fn seconds_and_nanos(&self) -> Result<(jlong, u32)> {
+        let seconds = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.get_epoch_second,
+                JavaType::Primitive(Primitive::Long),
+                &[],
+            )?
+            .j()?;
+        let nanos = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.get_nano,
+                JavaType::Primitive(Primitive::Int),
+                &[],
+            )?
+            .i()?;
+        Ok((seconds, nanos as u32))
+    }
+
+    fn new_impl(env: &'b JNIEnv<'a>, seconds: jlong, nanos: u32) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/time/Instant")?);
+        let obj = env
+            .call_static_method(
+                &class,
+                "ofEpochSecond",
+                "(JJ)Ljava/time/Instant;",
+                &[seconds.into(), (nanos as jlong).into()],
+            )?
+            .l()?;
+        Self::from_env_impl(env, obj, class)
+    }
+
+    fn from_env_impl(
+        env: &'b JNIEnv<'a>,
+        obj: JObject<'a>,
+        class: AutoLocal<'a, 'b>,
+    ) -> Result<Self> {
+        let get_epoch_second = env.get_method_id(&class, "getEpochSecond", "()J")?;
+        let get_nano = env.get_method_id(&class, "getNano", "()I")?;
+        Ok(Self {
+            internal: obj,
+            get_epoch_second,
+            get_nano,
+            env,
+        })
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JInstant<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JInstant<'a, 'b>> for JObject<'a> {
+    fn from(other: JInstant<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `java.util.Date`. Provides methods to
+/// convert to and from a [`SystemTime`].
+///
+/// `java.util.Date` only has millisecond precision. Rather than silently
+/// truncating, converting a [`SystemTime`] with a sub-millisecond component
+/// throws a `java.lang.ArithmeticException`.
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JDate<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    get_time: JMethodID<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JDate<'a, 'b> {
+    /// Create a [`JDate`] from the environment and an object. This looks up
+    /// the necessary class and method IDs to call all of the methods on it so
+    /// that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/util/Date")?);
+        Self::from_env_impl(env, obj, class)
+    }
+
+    /// Create a [`JDate`] which wraps a new `java.util.Date` created from a
+    /// given [`SystemTime`]. If the time has a sub-millisecond component or
+    /// is out of range, a `java.lang.ArithmeticException` is thrown.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `time` - [`SystemTime`] to convert into a `java.util.Date`.
+    pub fn new(env: &'b JNIEnv<'a>, time: SystemTime) -> Result<Self> {
+        let (seconds, nanos) = match epoch_seconds_and_nanos(time) {
+            Some(result) => result,
+            None => {
+                return throw_error(
+                    env,
+                    "java/lang/ArithmeticException",
+                    "SystemTime is out of range for java.util.Date",
+                )
+            }
+        };
+        Self::new_impl(env, seconds, nanos)
+    }
+
+    /// Convert the `java.util.Date` into a [`SystemTime`]. If the date is
+    /// outside the range of [`SystemTime`], a `java.lang.ArithmeticException`
+    /// is thrown.
+    pub fn as_system_time(&self) -> Result<SystemTime> {
+        let (seconds, nanos) = self.seconds_and_nanos()?;
+        match system_time_from_epoch(seconds, nanos) {
+            Some(time) => Ok(time),
+            None => throw_error(
+                self.env,
+                "java/lang/ArithmeticException",
+                "java.util.Date is out of range for SystemTime",
+            ),
+        }
+    }
+
+    fn seconds_and_nanos(&self) -> Result<(jlong, u32)> {
+        let millis = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.get_time,
+                JavaType::Primitive(Primitive::Long),
+                &[],
+            )?
+            .j()?;
+        Ok((
+            millis.div_euclid(1000),
+            millis.rem_euclid(1000) as u32 * NANOS_PER_MILLI,
+        ))
+    }
+
+    fn new_impl(env: &'b JNIEnv<'a>, seconds: jlong, nanos: u32) -> Result<Self> {
+        if nanos % NANOS_PER_MILLI != 0 {
+            return throw_error(
+                env,
+                "java/lang/ArithmeticException",
+                "java.util.Date cannot represent sub-millisecond precision",
+            );
+        }
+        let millis = match seconds
+            .checked_mul(1000)
+            .and_then(|millis| millis.checked_add((nanos / NANOS_PER_MILLI) as jlong))
+        {
+            Some(millis) => millis,
+            None => {
+                return throw_error(
+                    env,
+                    "java/lang/ArithmeticException",
+                    "Time is out of range for java.util.Date",
+                )
+            }
+        };
+
+        let class = env.auto_local(env.find_class("java/util/Date")?);
+        let obj = env.new_object(&class, "(J)V", &[millis.into()])?;
+        Self::from_env_impl(env, obj, class)
+    }
+
+    fn from_env_impl(
+        env: &'b JNIEnv<'a>,
+        obj: JObject<'a>,
+        class: AutoLocal<'a, 'b>,
+    ) -> Result<Self> {
+        let get_time = env.get_method_id(&class, "getTime", "()J")?;
+        Ok(Self {
+            internal: obj,
+            get_time,
+            env,
+        })
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JDate<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JDate<'a, 'b>> for JObject<'a> {
+    fn from(other: JDate<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `java.time.LocalDate`. Provides
+/// methods to get the year, month and day.
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JLocalDate<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    get_year: JMethodID<'a>,
+    get_month_value: JMethodID<'a>,
+    get_day_of_month: JMethodID<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JLocalDate<'a, 'b> {
+    /// Create a [`JLocalDate`] from the environment and an object. This looks
+    /// up the necessary class and method IDs to call all of the methods on it
+    /// so that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/time/LocalDate")?);
+        Self::from_env_impl(env, obj, class)
+    }
+
+    /// Create a [`JLocalDate`] which wraps a new `java.time.LocalDate` created
+    /// by calling `java.time.LocalDate.of()`. If the date is invalid, the
+    /// resulting `java.time.DateTimeException` is left pending.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `year` - Year.
+    /// * `month` - Month, from 1 to 12.
+    /// * `day` - Day of the month, from 1 to 31.
+    pub fn new(env: &'b JNIEnv<'a>, year: i32, month: u32, day: u32) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/time/LocalDate")?);
+        let obj = env
+            .call_static_method(
+                &class,
+                "of",
+                "(III)Ljava/time/LocalDate;",
+                &[year.into(), (month as jint).into(), (day as jint).into()],
+            )?
+            .l()?;
+        Self::from_env_impl(env, obj, class)
+    }
+
+    /// Get the year.
+    pub fn year(&self) -> Result<i32> {
+        self.call_int(self.get_year)
+    }
+
+    /// Get the month, from 1 to 12.
+    pub fn month(&self) -> Result<u32> {
+        Ok(self.call_int(self.get_month_value)? as u32)
+    }
+
+    /// Get the day of the month, from 1 to 31.
+    pub fn day(&self) -> Result<u32> {
+        Ok(self.call_int(self.get_day_of_month)? as u32)
+    }
+
+    fn call_int(&self, method: JMethodID<'a>) -> Result<jint> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                method,
+                JavaType::Primitive(Primitive::Int),
+                &[],
+            )?
+            .i()
+    }
+
+    fn from_env_impl(
+        env: &'b JNIEnv<'a>,
+        obj: JObject<'a>,
+        class: AutoLocal<'a, 'b>,
+    ) -> Result<Self> {
+        let get_year = env.get_method_id(&class, "getYear", "()I")?;
+        let get_month_value = env.get_method_id(&class, "getMonthValue", "()I")?;
+        let get_day_of_month = env.get_method_id(&class, "getDayOfMonth", "()I")?;
+        Ok(Self {
+            internal: obj,
+            get_year,
+            get_month_value,
+            get_day_of_month,
+            env,
+        })
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JLocalDate<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JLocalDate<'a, 'b>> for JObject<'a> {
+    fn from(other: JLocalDate<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `java.time.LocalDateTime`. Provides
+/// methods to split it into a date and a time of day.
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JLocalDateTime<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    to_local_date: JMethodID<'a>,
+    to_local_time: JMethodID<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JLocalDateTime<'a, 'b> {
+    /// Create a [`JLocalDateTime`] from the environment and an object. This
+    /// looks up the necessary class and method IDs to call all of the methods
+    /// on it so that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/time/LocalDateTime")?);
+        Self::from_env_impl(env, obj, class)
+    }
+
+    /// Create a [`JLocalDateTime`] which wraps a new
+    /// `java.time.LocalDateTime` created from a date and a time of day. If
+    /// the time of day is out of range, the resulting
+    /// `java.time.DateTimeException` is left pending.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `date` - Date.
+    /// * `time` - Time of day, as a [`Duration`] since midnight.
+    pub fn new(env: &'b JNIEnv<'a>, date: &JLocalDate<'a, 'b>, time: Duration) -> Result<Self> {
+        let nano_of_day = match jlong::try_from(time.as_nanos()) {
+            Ok(nano_of_day) => nano_of_day,
+            Err(_) => jlong::MAX,
+        };
+        let local_time = env.auto_local(
+            env.call_static_method(
+                "java/time/LocalTime",
+                "ofNanoOfDay",
+                "(J)Ljava/time/LocalTime;",
+                &[nano_of_day.into()],
+            )?
+            .l()?,
+        );
+
+        let class = env.auto_local(env.find_class("java/time/LocalDateTime")?);
+        let obj = env
+            .call_static_method(
+                &class,
+                "of",
+                "(Ljava/time/LocalDate;Ljava/time/LocalTime;)Ljava/time/LocalDateTime;",
+                &[date.internal.into(), local_time.as_obj().into()],
+            )?
+            .l()?;
+        Self::from_env_impl(env, obj, class)
+    }
+
+    /// Get the date part by calling `java.time.LocalDateTime.toLocalDate()`.
+    pub fn date(&self) -> Result<JLocalDate<'a, 'b>> {
+        let obj = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.to_local_date,
+                JavaType::Object("java/time/LocalDate".into()),
+                &[],
+            )?
+            .l()?;
+        JLocalDate::from_env(self.env, obj)
+    }
+
+    /// Get the time of day, as a [`Duration`] since midnight.
+    pub fn time(&self) -> Result<Duration> {
+        let local_time = self.env.auto_local(
+            self.env
+                .call_method_unchecked(
+                    self.internal,
+                    self.to_local_time,
+                    JavaType::Object("java/time/LocalTime".into()),
+                    &[],
+                )?
+                .l()?,
+        );
+        let nano_of_day = self
+            .env
+            .call_method(local_time.as_obj(), "toNanoOfDay", "()J", &[])?
+            .j()?;
+        Ok(Duration::from_nanos(nano_of_day as u64))
+    }
+
+    fn from_env_impl(
+        env: &'b JNIEnv<'a>,
+        obj: JObject<'a>,
+        class: AutoLocal<'a, 'b>,
+    ) -> Result<Self> {
+        let to_local_date = env.get_method_id(&class, "toLocalDate", "()Ljava/time/LocalDate;")?;
+        let to_local_time = env.get_method_id(&class, "toLocalTime", "()Ljava/time/LocalTime;")?;
+        Ok(Self {
+            internal: obj,
+            to_local_date,
+            to_local_time,
+            env,
+        })
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JLocalDateTime<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JLocalDateTime<'a, 'b>> for JObject<'a> {
+    fn from(other: JLocalDateTime<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `java.time.ZonedDateTime`. Provides
+/// methods to get the instant it represents and its offset from UTC.
+///
+/// Conversions from Rust always produce a `java.time.ZonedDateTime` with a
+/// fixed `java.time.ZoneOffset`, since Rust's time types don't carry region
+/// IDs such as `Europe/Paris`.
+///
+/// Looks up the class and method IDs on creation rather than for every method
+/// call.
+pub struct JZonedDateTime<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    to_instant: JMethodID<'a>,
+    to_local_date_time: JMethodID<'a>,
+    get_offset: JMethodID<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JZonedDateTime<'a, 'b> {
+    /// Create a [`JZonedDateTime`] from the environment and an object. This
+    /// looks up the necessary class and method IDs to call all of the methods
+    /// on it so that extra work doesn't need to be done on every method call.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `obj` - Object to wrap.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let class = env.auto_local(env.find_class("java/time/ZonedDateTime")?);
+        Self::from_env_impl(env, obj, class)
+    }
+
+    /// Create a [`JZonedDateTime`] which wraps a new
+    /// `java.time.ZonedDateTime` created from a given [`SystemTime`] and
+    /// offset from UTC.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Java environment to use.
+    /// * `time` - [`SystemTime`] to convert.
+    /// * `offset_seconds` - Offset from UTC, in seconds.
+    pub fn new(env: &'b JNIEnv<'a>, time: SystemTime, offset_seconds: i32) -> Result<Self> {
+        let instant = env.auto_local(JObject::from(JInstant::new(env, time)?));
+        Self::new_impl(env, instant, offset_seconds)
+    }
+
+    /// Get the instant by calling `java.time.ZonedDateTime.toInstant()`.
+    pub fn instant(&self) -> Result<JInstant<'a, 'b>> {
+        let obj = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.to_instant,
+                JavaType::Object("java/time/Instant".into()),
+                &[],
+            )?
+            .l()?;
+        JInstant::from_env(self.env, obj)
+    }
+
+    /// Get the local date and time by calling
+    /// `java.time.ZonedDateTime.toLocalDateTime()`.
+    pub fn local_date_time(&self) -> Result<JLocalDateTime<'a, 'b>> {
+        let obj = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.to_local_date_time,
+                JavaType::Object("java/time/LocalDateTime".into()),
+                &[],
+            )?
+            .l()?;
+        JLocalDateTime::from_env(self.env, obj)
+    }
+
+    /// Get the offset from UTC, in seconds.
+    pub fn offset_seconds(&self) -> Result<i32> {
+        let offset = self.env.auto_local(
+            self.env
+                .call_method_unchecked(
+                    self.internal,
+                    self.get_offset,
+                    JavaType::Object("java/time/ZoneOffset".into()),
+                    &[],
+                )?
+                .l()?,
+        );
+        self.env
+            .call_method(offset.as_obj(), "getTotalSeconds", "()I", &[])?
+            .i()
+    }
+
+    /// Convert the `java.time.ZonedDateTime` into a [`SystemTime`]. This
+    /// discards the offset.
+    pub fn as_system_time(&self) -> Result<SystemTime> {
+        let instant = self.instant()?;
+        let _auto_local = self.env.auto_local(instant.internal);
+        instant.as_system_time()
+    }
+
+    fn new_impl(
+        env: &'b JNIEnv<'a>,
+        instant: AutoLocal<'a, 'b>,
+        offset_seconds: i32,
+    ) -> Result<Self> {
+        let offset = env.auto_local(
+            env.call_static_method(
+                "java/time/ZoneOffset",
+                "ofTotalSeconds",
+                "(I)Ljava/time/ZoneOffset;",
+                &[offset_seconds.into()],
+            )?
+            .l()?,
+        );
+
+        let class = env.auto_local(env.find_class("java/time/ZonedDateTime")?);
+        let obj = env
+            .call_static_method(
+                &class,
+                "ofInstant",
+                "(Ljava/time/Instant;Ljava/time/ZoneId;)Ljava/time/ZonedDateTime;",
+                &[instant.as_obj().into(), offset.as_obj().into()],
+            )?
+            .l()?;
+        Self::from_env_impl(env, obj, class)
+    }
+
+    fn from_env_impl(
+        env: &'b JNIEnv<'a>,
+        obj: JObject<'a>,
+        class: AutoLocal<'a, 'b>,
+    ) -> Result<Self> {
+        let to_instant = env.get_method_id(&class, "toInstant", "()Ljava/time/Instant;")?;
+        let to_local_date_time =
+            env.get_method_id(&class, "toLocalDateTime", "()Ljava/time/LocalDateTime;")?;
+        let get_offset = env.get_method_id(&class, "getOffset", "()Ljava/time/ZoneOffset;")?;
+        Ok(Self {
+            internal: obj,
+            to_instant,
+            to_local_date_time,
+            get_offset,
+            env,
+        })
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JZonedDateTime<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JZonedDateTime<'a, 'b>> for JObject<'a> {
+    fn from(other: JZonedDateTime<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+// Splits a SystemTime into seconds since the epoch (rounded down) and
+// nanoseconds, the same way as java.time.Instant.
+fn epoch_seconds_and_nanos(time: SystemTime) -> Option<(jlong, u32)> {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => Some((
+            jlong::try_from(duration.as_secs()).ok()?,
+            duration.subsec_nanos(),
+        )),
+        Err(err) => {
+            let duration = err.duration();
+            let seconds = jlong::try_from(duration.as_secs()).ok()?;
+            Some(if duration.subsec_nanos() == 0 {
+                (-seconds, 0)
+            } else {
+                (
+                    (-seconds).checked_sub(1)?,
+                    NANOS_PER_SEC - duration.subsec_nanos(),
+                )
+            })
+        }
+    }
+}
+
+fn system_time_from_epoch(seconds: jlong, nanos: u32) -> Option<SystemTime> {
+    if seconds >= 0 {
+        UNIX_EPOCH.checked_add(Duration::new(seconds as u64, nanos))
+    } else {
+        UNIX_EPOCH
+            .checked_sub(Duration::from_secs(seconds.unsigned_abs()))?
+            .checked_add(Duration::from_nanos(nanos as u64))
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_support {
+    use super::NANOS_PER_SEC;
+    use super::{JDate, JDuration, JInstant, JLocalDate, JLocalDateTime, JZonedDateTime};
+    use crate::exceptions::throw_error;
+    use chrono::{
+        DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone,
+        Timelike, Utc,
+    };
+    use jni::{errors::Result, objects::JObject, sys::jlong, JNIEnv};
+    use std::time::Duration;
+
+    fn out_of_range<T>(env: &JNIEnv, what: &str) -> Result<T> {
+        throw_error(
+            env,
+            "java/lang/ArithmeticException",
+            format!("{} is out of range", what),
+        )
+    }
+
+    impl<'a: 'b, 'b> JDuration<'a, 'b> {
+        /// Create a [`JDuration`] which wraps a new `java.time.Duration`
+        /// created from a given [`TimeDelta`].
+        ///
+        /// # Arguments
+        ///
+        /// * `env` - Java environment to use.
+        /// * `delta` - [`TimeDelta`] to convert into a `java.time.Duration`.
+        pub fn from_time_delta(env: &'b JNIEnv<'a>, delta: TimeDelta) -> Result<Self> {
+            let mut seconds = delta.num_seconds();
+            let mut nanos = delta.subsec_nanos();
+            if nanos < 0 {
+                seconds -= 1;
+                nanos += NANOS_PER_SEC as i32;
+            }
+            Self::new_impl(env, seconds, nanos as u32)
+        }
+
+        /// Convert the `java.time.Duration` into a [`TimeDelta`]. If the
+        /// duration is outside the range of [`TimeDelta`], a
+        /// `java.lang.ArithmeticException` is thrown.
+        pub fn as_time_delta(&self) -> Result<TimeDelta> {
+            let (seconds, nanos) = self.seconds_and_nanos()?;
+            match TimeDelta::new(seconds, nanos) {
+                Some(delta) => Ok(delta),
+                None => out_of_range(self.env, "java.time.Duration"),
+            }
+        }
+    }
+
+    impl<'a: 'b, 'b> JInstant<'a, 'b> {
+        /// Create a [`JInstant`] which wraps a new `java.time.Instant` created
+        /// from a given [`DateTime`].
+        ///
+        /// # Arguments
+        ///
+        /// * `env` - Java environment to use.
+        /// * `time` - [`DateTime`] to convert into a `java.time.Instant`.
+        pub fn from_date_time<Tz: TimeZone>(
+            env: &'b JNIEnv<'a>,
+            time: &DateTime<Tz>,
+        ) -> Result<Self> {
+            let (seconds, nanos) = date_time_seconds_and_nanos(env, time)?;
+            Self::new_impl(env, seconds, nanos)
+        }
+
+        /// Convert the `java.time.Instant` into a [`DateTime`]. If the
+        /// instant is outside the range of [`DateTime`], a
+        /// `java.lang.ArithmeticException` is thrown.
+        pub fn as_date_time(&self) -> Result<DateTime<Utc>> {
+            let (seconds, nanos) = self.seconds_and_nanos()?;
+            match DateTime::from_timestamp(seconds, nanos) {
+                Some(time) => Ok(time),
+                None => out_of_range(self.env, "java.time.Instant"),
+            }
+        }
+    }
+
+    impl<'a: 'b, 'b> JDate<'a, 'b> {
+        /// Create a [`JDate`] which wraps a new `java.util.Date` created from a
+        /// given [`DateTime`]. If the time has a sub-millisecond component, a
+        /// `java.lang.ArithmeticException` is thrown.
+        ///
+        /// # Arguments
+        ///
+        /// * `env` - Java environment to use.
+        /// * `time` - [`DateTime`] to convert into a `java.util.Date`.
+        pub fn from_date_time<Tz: TimeZone>(
+            env: &'b JNIEnv<'a>,
+            time: &DateTime<Tz>,
+        ) -> Result<Self> {
+            let (seconds, nanos) = date_time_seconds_and_nanos(env, time)?;
+            Self::new_impl(env, seconds, nanos)
+        }
+
+        /// Convert the `java.util.Date` into a [`DateTime`]. If the date is
+        /// outside the range of [`DateTime`], a
+        /// `java.lang.ArithmeticException` is thrown.
+        pub fn as_date_time(&self) -> Result<DateTime<Utc>> {
+            let (seconds, nanos) = self.seconds_and_nanos()?;
+            match DateTime::from_timestamp(seconds, nanos) {
+                Some(time) => Ok(time),
+                None => out_of_range(self.env, "java.util.Date"),
+            }
+        }
+    }
+
+    impl<'a: 'b, 'b> JLocalDate<'a, 'b> {
+        /// Create a [`JLocalDate`] which wraps a new `java.time.LocalDate`
+        /// created from a given [`NaiveDate`].
+        ///
+        /// # Arguments
+        ///
+        /// * `env` - Java environment to use.
+        /// * `date` - [`NaiveDate`] to convert into a `java.time.LocalDate`.
+        pub fn from_naive_date(env: &'b JNIEnv<'a>, date: NaiveDate) -> Result<Self> {
+            Self::new(env, date.year(), date.month(), date.day())
+        }
+
+        /// Convert the `java.time.LocalDate` into a [`NaiveDate`]. If the date
+        /// is outside the range of [`NaiveDate`], a
+        /// `java.lang.ArithmeticException` is thrown.
+        pub fn as_naive_date(&self) -> Result<NaiveDate> {
+            match NaiveDate::from_ymd_opt(self.year()?, self.month()?, self.day()?) {
+                Some(date) => Ok(date),
+                None => out_of_range(self.env, "java.time.LocalDate"),
+            }
+        }
+    }
+
+    impl<'a: 'b, 'b> JLocalDateTime<'a, 'b> {
+        /// Create a [`JLocalDateTime`] which wraps a new
+        /// `java.time.LocalDateTime` created from a given [`NaiveDateTime`].
+        /// If the time falls within a leap second, which Java does not
+        /// support, a `java.lang.ArithmeticException` is thrown.
+        ///
+        /// # Arguments
+        ///
+        /// * `env` - Java environment to use.
+        /// * `time` - [`NaiveDateTime`] to convert into a
+        ///   `java.time.LocalDateTime`.
+        pub fn from_naive_date_time(env: &'b JNIEnv<'a>, time: NaiveDateTime) -> Result<Self> {
+            let time_of_day = time.time();
+            if time_of_day.nanosecond() >= NANOS_PER_SEC {
+                return throw_error(
+                    env,
+                    "java/lang/ArithmeticException",
+                    "java.time.LocalDateTime cannot represent leap seconds",
+                );
+            }
+            let date = JLocalDate::from_naive_date(env, time.date())?;
+            let _auto_local = env.auto_local(date.internal);
+            Self::new(
+                env,
+                &date,
+                Duration::new(
+                    time_of_day.num_seconds_from_midnight() as u64,
+                    time_of_day.nanosecond(),
+                ),
+            )
+        }
+
+        /// Convert the `java.time.LocalDateTime` into a [`NaiveDateTime`]. If
+        /// the date is outside the range of [`NaiveDateTime`], a
+        /// `java.lang.ArithmeticException` is thrown.
+        pub fn as_naive_date_time(&self) -> Result<NaiveDateTime> {
+            let date = self.date()?;
+            let _auto_local = self.env.auto_local(date.internal);
+            let date = date.as_naive_date()?;
+            let time = self.time()?;
+            let time = NaiveTime::from_num_seconds_from_midnight_opt(
+                time.as_secs() as u32,
+                time.subsec_nanos(),
+            )
+            .unwrap();
+            Ok(date.and_time(time))
+        }
+    }
+
+    impl<'a: 'b, 'b> JZonedDateTime<'a, 'b> {
+        /// Create a [`JZonedDateTime`] which wraps a new
+        /// `java.time.ZonedDateTime` created from a given [`DateTime`].
+        ///
+        /// # Arguments
+        ///
+        /// * `env` - Java environment to use.
+        /// * `time` - [`DateTime`] to convert into a
+        ///   `java.time.ZonedDateTime`.
+        pub fn from_date_time(env: &'b JNIEnv<'a>, time: &DateTime<FixedOffset>) -> Result<Self> {
+            let instant = env.auto_local(JObject::from(JInstant::from_date_time(env, time)?));
+            Self::new_impl(env, instant, time.offset().local_minus_utc())
+        }
+
+        /// Convert the `java.time.ZonedDateTime` into a [`DateTime`] with the
+        /// same offset from UTC. If the time is outside the range of
+        /// [`DateTime`], a `java.lang.ArithmeticException` is thrown.
+        pub fn as_date_time(&self) -> Result<DateTime<FixedOffset>> {
+            let instant = self.instant()?;
+            let _auto_local = self.env.auto_local(instant.internal);
+            let time = instant.as_date_time()?;
+            match FixedOffset::east_opt(self.offset_seconds()?) {
+                Some(offset) => Ok(time.with_timezone(&offset)),
+                None => out_of_range(self.env, "java.time.ZoneOffset"),
+            }
+        }
+    }
+
+    fn date_time_seconds_and_nanos<Tz: TimeZone>(
+        env: &JNIEnv,
+        time: &DateTime<Tz>,
+    ) -> Result<(jlong, u32)> {
+        let nanos = time.timestamp_subsec_nanos();
+        if nanos >= NANOS_PER_SEC {
+            return throw_error(
+                env,
+                "java/lang/ArithmeticException",
+                "java.time cannot represent leap seconds",
+            );
+        }
+        Ok((time.timestamp(), nanos))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{JDate, JDuration, JInstant, JLocalDate, JLocalDateTime, JZonedDateTime};
+    use crate::test_utils::{self, assert_exception, to_string};
+    use jni::errors::Error;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_duration() {
+        test_utils::JVM_ENV.with(|env| {
+            let duration = Duration::new(90061, 5_000_000);
+            let obj = JDuration::new(env, duration).unwrap();
+            assert_eq!(to_string(env, *obj), "PT25H1M1.005S");
+            assert_eq!(obj.as_duration().unwrap(), duration);
+
+            let obj = env
+                .call_static_method(
+                    "java/time/Duration",
+                    "ofMillis",
+                    "(J)Ljava/time/Duration;",
+                    &[(-1i64).into()],
+                )
+                .unwrap()
+                .l()
+                .unwrap();
+            let obj = JDuration::from_env(env, obj).unwrap();
+            assert_exception(env, obj.as_duration(), "java/lang/ArithmeticException");
+
+            assert_exception(
+                env,
+                JDuration::new(env, Duration::from_secs(u64::MAX)),
+                "java/lang/ArithmeticException",
+            );
+        });
+    }
+
+    #[test]
+    fn test_instant() {
+        test_utils::JVM_ENV.with(|env| {
+            for (time, expected) in &[
+                (
+                    UNIX_EPOCH + Duration::new(1_600_000_000, 123_456_789),
+                    "2020-09-13T12:26:40.123456789Z",
+                ),
+                (UNIX_EPOCH, "1970-01-01T00:00:00Z"),
+                (
+                    UNIX_EPOCH - Duration::new(1, 250_000_000),
+                    "1969-12-31T23:59:58.750Z",
+                ),
+            ] {
+                let obj = JInstant::new(env, *time).unwrap();
+                assert_eq!(to_string(env, *obj), *expected);
+                assert_eq!(obj.as_system_time().unwrap(), *time);
+            }
+        });
+    }
+
+    #[test]
+    fn test_date() {
+        test_utils::JVM_ENV.with(|env| {
+            for millis in &[1_600_000_000_123i64, -1_250] {
+                let time = if *millis >= 0 {
+                    UNIX_EPOCH + Duration::from_millis(*millis as u64)
+                } else {
+                    UNIX_EPOCH - Duration::from_millis(millis.unsigned_abs())
+                };
+                let obj = JDate::new(env, time).unwrap();
+                let actual = env.call_method(*obj, "getTime", "()J", &[]).unwrap();
+                assert_eq!(actual.j().unwrap(), *millis);
+                assert_eq!(obj.as_system_time().unwrap(), time);
+            }
+
+            let time = UNIX_EPOCH + Duration::new(1, 1);
+            assert_exception(env, JDate::new(env, time), "java/lang/ArithmeticException");
+        });
+    }
+
+    #[test]
+    fn test_local_date_time() {
+        test_utils::JVM_ENV.with(|env| {
+            let date = JLocalDate::new(env, 2021, 7, 4).unwrap();
+            assert_eq!(to_string(env, *date), "2021-07-04");
+            assert_eq!(date.year().unwrap(), 2021);
+            assert_eq!(date.month().unwrap(), 7);
+            assert_eq!(date.day().unwrap(), 4);
+
+            let time = Duration::new(13 * 3600 + 5 * 60 + 9, 1_000);
+            let date_time = JLocalDateTime::new(env, &date, time).unwrap();
+            assert_eq!(to_string(env, *date_time), "2021-07-04T13:05:09.000001");
+            assert_eq!(date_time.time().unwrap(), time);
+            let date = date_time.date().unwrap();
+            assert_eq!(to_string(env, *date), "2021-07-04");
+
+            let result = JLocalDate::new(env, 2021, 2, 30);
+            assert!(matches!(result, Err(Error::JavaException)));
+            env.exception_clear().unwrap();
+        });
+    }
+
+    #[test]
+    fn test_zoned_date_time() {
+        test_utils::JVM_ENV.with(|env| {
+            let time = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+            let obj = JZonedDateTime::new(env, time, -5 * 3600).unwrap();
+            assert_eq!(to_string(env, *obj), "2020-09-13T07:26:40-05:00");
+            assert_eq!(obj.offset_seconds().unwrap(), -5 * 3600);
+            assert_eq!(obj.as_system_time().unwrap(), time);
+
+            let date_time = obj.local_date_time().unwrap();
+            assert_eq!(to_string(env, *date_time), "2020-09-13T07:26:40");
+        });
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono() {
+        use chrono::{DateTime, FixedOffset, NaiveDate, TimeDelta, Utc};
+
+        test_utils::JVM_ENV.with(|env| {
+            let delta = TimeDelta::new(-2, 500_000_000).unwrap();
+            let obj = JDuration::from_time_delta(env, delta).unwrap();
+            assert_eq!(to_string(env, *obj), "PT-1.5S");
+            assert_eq!(obj.as_time_delta().unwrap(), delta);
+
+            let time: DateTime<Utc> = "2020-09-13T12:26:40.123456789Z".parse().unwrap();
+            let obj = JInstant::from_date_time(env, &time).unwrap();
+            assert_eq!(to_string(env, *obj), "2020-09-13T12:26:40.123456789Z");
+            assert_eq!(obj.as_date_time().unwrap(), time);
+
+            assert_exception(
+                env,
+                JDate::from_date_time(env, &time),
+                "java/lang/ArithmeticException",
+            );
+            let time: DateTime<Utc> = "1969-12-31T23:59:59.999Z".parse().unwrap();
+            let obj = JDate::from_date_time(env, &time).unwrap();
+            assert_eq!(obj.as_date_time().unwrap(), time);
+
+            let date = NaiveDate::from_ymd_opt(2021, 7, 4).unwrap();
+            let obj = JLocalDate::from_naive_date(env, date).unwrap();
+            assert_eq!(obj.as_naive_date().unwrap(), date);
+
+            let date_time = date.and_hms_nano_opt(13, 5, 9, 42).unwrap();
+            let obj = JLocalDateTime::from_naive_date_time(env, date_time).unwrap();
+            assert_eq!(to_string(env, *obj), "2021-07-04T13:05:09.000000042");
+            assert_eq!(obj.as_naive_date_time().unwrap(), date_time);
+
+            let leap = date.and_hms_nano_opt(23, 59, 59, 1_500_000_000).unwrap();
+            assert_exception(
+                env,
+                JLocalDateTime::from_naive_date_time(env, leap),
+                "java/lang/ArithmeticException",
+            );
+
+            let time: DateTime<FixedOffset> = "2020-09-13T07:26:40+05:30".parse().unwrap();
+            let obj = JZonedDateTime::from_date_time(env, &time).unwrap();
+            assert_eq!(to_string(env, *obj), "2020-09-13T07:26:40+05:30");
+            assert_eq!(obj.as_date_time().unwrap(), time);
+
+            let obj = env
+                .call_static_method(
+                    "java/time/LocalDate",
+                    "of",
+                    "(III)Ljava/time/LocalDate;",
+                    &[1_000_000i32.into(), 1i32.into(), 1i32.into()],
+                )
+                .unwrap()
+                .l()
+                .unwrap();
+            let obj = JLocalDate::from_env(env, obj).unwrap();
+            assert_exception(env, obj.as_naive_date(), "java/lang/ArithmeticException");
+        });
+    }
+}